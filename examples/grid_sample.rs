@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// A level select screen: a wide banner across the top, a tall boss level on the right, and the other levels filling the rest.
+	let mut levels = bevy_ui_builder::widgets::Grid::<MyUI>::new(4)
+		.with_gap(Val::Percent(1f32), Val::Percent(1f32))
+		.with_padding(UiRect::all(Val::Percent(2f32)))
+		.push(bevy_ui_builder::widgets::TextLabel::<MyUI>::new("World 1")).at(0, 0).with_span(1, 4)
+		.push(bevy_ui_builder::widgets::TextButton::<MyUI>::new("Boss")).at(1, 3).with_span(3, 1);
+	for level in 1..=9
+	{
+		levels = levels.push(bevy_ui_builder::widgets::TextButton::<MyUI>::new(format!("Level {}", level)));
+	}
+	levels
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
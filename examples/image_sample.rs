@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+use bevy_ui_builder::widgets::{ImageFit, ImageWidget};
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.run();
+}
+
+fn build_root
+(
+	mut commands: Commands,
+	mut ui_tree: ResMut<UIHierarchy<MyUI>>,
+	theme: Res<CurrentThemeData<MyUI>>,
+	asset_server: Res<AssetServer>,
+)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	let example_texture: Handle<Image> = asset_server.load("Example.png");
+	// The same image with each fit mode.
+	let mut fits = bevy_ui_builder::widgets::Row::<MyUI>::new()
+		.with_fill_portion(0.5f32);
+	for fit in [ImageFit::Contain, ImageFit::Cover, ImageFit::Stretch, ImageFit::None]
+	{
+		fits = fits.push(ImageWidget::<MyUI>::new(example_texture.clone())
+			.with_fit(fit)
+			.with_margin(UiRect::all(Val::Percent(1f32)))
+			.with_paint_mode(bevy_ui_builder::theme::PaintMode::BackgroundContainer));
+	}
+	// A tinted logo that keeps its proportions, and a nine-sliced frame around a label.
+	let logo = ImageWidget::<MyUI>::new(example_texture.clone())
+		.with_tint(true)
+		.with_auto_aspect_ratio(true);
+	let framed = ImageWidget::<MyUI>::new(example_texture)
+		.with_nine_slice(4f32)
+		.push(bevy_ui_builder::widgets::TextLabel::<MyUI>::new("Framed panel")
+			.with_paint_mode(bevy_ui_builder::theme::PaintMode::Invisible));
+	let bottom = bevy_ui_builder::widgets::Row::<MyUI>::new()
+		.push(logo)
+		.push(framed)
+		.with_fill_portion(0.5f32);
+	bevy_ui_builder::widgets::Column::<MyUI>::new()
+		.push(fits)
+		.push(bottom)
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// Only the visible rows are spawned, the rest are built as they scroll into view.
+	let list_view = bevy_ui_builder::widgets::ListView::new(20_000, |index| bevy_ui_builder::widgets::TextButton::<MyUI>::new(format!("Item {}", index + 1)))
+		.with_row_height(48f32)
+		.with_fill_portion(0.5f32);
+	bevy_ui_builder::widgets::Row::<MyUI>::new()
+		.push(bevy_ui_builder::widgets::create_space(0.25f32))
+		.push(list_view)
+		.push(bevy_ui_builder::widgets::create_space(0.25f32))
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+use bevy_ui_builder::widgets::{Accelerator, Menu, MenuBar, MenuItem, TextButton};
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.add_systems(Update, log_menu_actions)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// A level editor, right-click the entity for its context menu.
+	let file = Menu::new()
+		.push(MenuItem::new("New level").with_accelerator(Accelerator::ctrl(KeyCode::KeyN)))
+		.push(MenuItem::new("Open...").with_accelerator(Accelerator::ctrl(KeyCode::KeyO)))
+		.push_submenu("Open recent", Menu::new()
+			.push(MenuItem::new("forest.ron"))
+			.push(MenuItem::new("castle.ron")))
+		.push_separator()
+		.push(MenuItem::new("Save").with_accelerator(Accelerator::ctrl(KeyCode::KeyS)))
+		.push(MenuItem::new("Export").with_disabled(true));
+	let view = Menu::new()
+		.push(MenuItem::new("Grid").with_checked(true).with_accelerator(Accelerator::ctrl(KeyCode::KeyG)))
+		.push(MenuItem::new("Gizmos").with_checked(false));
+	let entity_menu = Menu::new()
+		.push(MenuItem::new("Duplicate").with_accelerator(Accelerator::ctrl(KeyCode::KeyD)))
+		.push_separator()
+		.push(MenuItem::new("Delete").with_accelerator(Accelerator::new(KeyCode::Delete)));
+	bevy_ui_builder::widgets::Column::<MyUI>::new()
+		.push(MenuBar::<MyUI>::new()
+			.push_menu("File", file)
+			.push_menu("View", view))
+		.push(TextButton::<MyUI>::new("Player spawn")
+			.with_context_menu(entity_menu))
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+}
+
+fn log_menu_actions(mut actions: EventReader<MenuAction>)
+{
+	for action in actions.read()
+	{
+		info!("{} {:?}", action.action, action.checked);
+	}
+}
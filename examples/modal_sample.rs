@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+use bevy_ui_builder::widgets::{ConfirmDialog, TextButton};
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+#[derive(Component)]
+pub struct QuitButton;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.add_systems(Update, (ask_before_quitting, quit_when_confirmed))
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	let quit_button = TextButton::<MyUI>::new("Quit")
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	commands.entity(quit_button).insert(QuitButton);
+	bevy_ui_builder::widgets::Column::<MyUI>::new()
+		.push(bevy_ui_builder::widgets::create_space(0.45f32))
+		.push(quit_button)
+		.push(bevy_ui_builder::widgets::create_space(0.45f32))
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
+
+fn ask_before_quitting
+(
+	mut commands: Commands,
+	mut ui_tree: ResMut<UIHierarchy<MyUI>>,
+	theme: Res<CurrentThemeData<MyUI>>,
+	button_query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
+)
+{
+	if button_query.iter().any(|interaction| *interaction == Interaction::Pressed)
+	{
+		ConfirmDialog::<MyUI>::new("Are you sure you want to quit?")
+			.with_confirm_text("Quit")
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+}
+
+fn quit_when_confirmed(mut results: EventReader<ConfirmDialogResult>, mut exit_writer: EventWriter<AppExit>)
+{
+	if results.read().any(|result| result.confirmed)
+		{ exit_writer.send(AppExit::Success); }
+}
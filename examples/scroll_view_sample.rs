@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// Ten screens of content, fifty buttons each taking a fiftieth of it.
+	let mut scroll_view = bevy_ui_builder::widgets::ScrollView::<_>::new()
+		.with_content_height(Val::Percent(1000f32))
+		.with_fill_portion(0.5f32);
+	for index in 0..50
+	{
+		scroll_view = scroll_view.push(bevy_ui_builder::widgets::TextButton::<_>::new(format!("Save slot {}", index + 1))
+			.with_fill_portion(0.02f32));
+	}
+	bevy_ui_builder::widgets::Row::<MyUI>::new()
+		.push(bevy_ui_builder::widgets::create_space(0.25f32))
+		.push(scroll_view)
+		.push(bevy_ui_builder::widgets::create_space(0.25f32))
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
@@ -19,6 +19,9 @@ fn main()
 			UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Menu)
 				// Change here to see the different themes
 				// .with_theme(bevy_ui_builder::theme::TRANSPARENT.clone())
+				// .with_theme(bevy_ui_builder::theme::HIGH_CONTRAST_DARK.clone())
+				// Or preview a theme as seen with a colour vision deficiency
+				// .with_theme(bevy_ui_builder::theme::DARK.simulate(bevy_ui_builder::theme::Cvd::Deuteranopia))
 				.register_root_builder(build_root)
 		)
 		.run();
@@ -65,9 +68,28 @@ fn build_sample_widgets() -> impl Into<Box<dyn WidgetBuilder<MyUI>>>
 	let text_input = bevy_ui_builder::widgets::TextInput::<_>::new("Text Input".to_string().into());
 	let text_button = bevy_ui_builder::widgets::TextButton::<_>::new("Text Button");
 	let checkbox = bevy_ui_builder::widgets::CheckBox::<_>::new();
+	let switch = bevy_ui_builder::widgets::Switch::<_>::new()
+		.with_checked(true);
+	let dropdown = bevy_ui_builder::widgets::Dropdown::<_>::new(["Low", "Medium", "High"]);
+	let slider = bevy_ui_builder::widgets::Slider::<_>::new(0.0, 100.0)
+		.with_step(5.0)
+		.with_value(50.0)
+		.with_label_format(|value| format!("{value:.0}%"));
+	let radio_group = bevy_ui_builder::widgets::RadioGroup::<_>::new(["Easy", "Normal", "Hard"])
+		.with_direction(FlexDirection::Row)
+		.with_selected(1);
+	let progress_bar = bevy_ui_builder::widgets::ProgressBar::<_>::new(0.45)
+		.with_percentage_label();
+	let meter = bevy_ui_builder::widgets::Meter::<_>::new(0.7, 5);
 	bevy_ui_builder::widgets::Column::<_>::new()
 		.push(text_label)
 		.push(text_input)
 		.push(text_button)
 		.push(checkbox)
+		.push(switch)
+		.push(dropdown)
+		.push(slider)
+		.push(radio_group)
+		.push(progress_bar)
+		.push(meter)
 }
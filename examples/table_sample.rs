@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+use bevy_ui_builder::widgets::{Table, TableColumn, TableColumnWidth, TextButton};
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+#[derive(Clone)]
+pub struct Server
+{
+	name: String,
+	map: &'static str,
+	players: u32,
+	ping: u32,
+}
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.add_systems(Update, log_selected_server)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// A server browser, click a column's title to sort by it.
+	let maps = ["Forest", "Castle", "Harbour", "Desert"];
+	let servers = (0..40).map(|index| Server
+	{
+		name: format!("Server {index}"),
+		map: maps[index % maps.len()],
+		players: (index as u32 * 7) % 33,
+		ping: 20 + (index as u32 * 37) % 180,
+	});
+	Table::<MyUI, Server>::new(servers)
+		.push_column(TableColumn::text("Name", |server: &Server| server.name.clone())
+			.with_width(TableColumnWidth::Fill(2.0))
+			.with_sort_key(|server| server.name.clone()))
+		.push_column(TableColumn::text("Map", |server: &Server| server.map.to_string())
+			.with_sort_key(|server| server.map))
+		.push_column(TableColumn::text("Players", |server: &Server| format!("{}/32", server.players))
+			.with_width(TableColumnWidth::Px(100.0))
+			.with_sort_key(|server| server.players))
+		.push_column(TableColumn::text("Ping", |server: &Server| server.ping.to_string())
+			.with_width(TableColumnWidth::Px(80.0))
+			.with_sort_key(|server| server.ping))
+		.push_column(TableColumn::new("", |_: &Server| TextButton::<MyUI>::new("Join"))
+			.with_width(TableColumnWidth::Px(100.0)))
+		.with_sort(3, true)
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+}
+
+fn log_selected_server(mut selection_events: EventReader<TableRowSelected>, row_query: Query<&TableRow>)
+{
+	for event in selection_events.read()
+	{
+		if let Some(server) = row_query.get(event.row).ok().and_then(|row| row.value::<Server>())
+			{ info!("Selected {} ({} ms)", server.name, server.ping); }
+	}
+}
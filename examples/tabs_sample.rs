@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// A settings screen, LB/RB or Ctrl+Tab switch between the pages, and every option explains itself in a tooltip.
+	let video = bevy_ui_builder::widgets::Column::<_>::new()
+		.push(bevy_ui_builder::widgets::Dropdown::<_>::new(["1280x720", "1920x1080", "2560x1440"])
+			.with_tooltip("The size of the window, in pixels."))
+		.push(bevy_ui_builder::widgets::CheckBox::<_>::new()
+			.with_tooltip("Limits the frame rate to the display's refresh rate."))
+		.push(bevy_ui_builder::widgets::NumberInput::<_, (), u32>::new(144).with_range(30, 360).with_step(5)
+			.with_tooltip("The highest frame rate to run at."));
+	let audio = bevy_ui_builder::widgets::Column::<_>::new()
+		.push(bevy_ui_builder::widgets::Slider::<_>::new(0f32, 100f32).with_value(80f32).with_value_label(true)
+			.with_tooltip("The master volume."))
+		.push(bevy_ui_builder::widgets::Switch::<_>::new().with_checked(true)
+			.with_tooltip("Keeps playing music while the game is in the background."));
+	let controls = bevy_ui_builder::widgets::Column::<_>::new()
+		.push(bevy_ui_builder::widgets::Slider::<_>::new(0.1f32, 2f32).with_step(0.1f32).with_value(1f32).with_value_label(true)
+			.with_tooltip("How fast the camera turns."));
+	let tabs = bevy_ui_builder::widgets::Tabs::<_>::new()
+		.push_tab("Video", video)
+		.push_tab("Audio", audio)
+		.push_tab("Controls", controls)
+		.with_fill_portion(0.5f32);
+	bevy_ui_builder::widgets::Row::<MyUI>::new()
+		.push(bevy_ui_builder::widgets::create_space(0.25f32))
+		.push(tabs)
+		.push(bevy_ui_builder::widgets::create_space(0.25f32))
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
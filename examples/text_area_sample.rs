@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// An in-game note editor, type enough to see it scroll with the caret.
+	bevy_ui_builder::widgets::Column::<MyUI>::new()
+		.push(bevy_ui_builder::widgets::TextLabel::<MyUI>::from_markup("[size=1.25]Notes[/size] - [color=primary]Enter[/color] starts a new line").with_fill_portion(0.1f32))
+		.push(bevy_ui_builder::widgets::TextArea::<MyUI>::new()
+			.with_text("Find the key to the lighthouse.\nAsk the fisherman about the storm.")
+			.with_placeholder("Write something down...")
+			.with_line_numbers(true)
+			.with_fill_portion(0.9f32))
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands)
+		;
+}
@@ -49,7 +49,7 @@ fn build_root
 
 	let example_texture = asset_server.load("Example.png");
 	let example_button = bevy_ui_builder::widgets::base_button::BaseButton::<MyUI, ExampleTextureButton>::new()
-		.with_image(example_texture.into())
+		.with_image(example_texture.clone().into())
 		// Optionally disable auto styling
 		// .with_auto_style(false)
 		// Or add your own styling
@@ -57,9 +57,16 @@ fn build_root
 		// .with_active_image(...)
 		;
 
+	// Icons next to (or instead of) a label, tinted like the label's text.
+	let icon_button = bevy_ui_builder::widgets::IconButton::<MyUI>::new(example_texture.clone(), "Settings");
+	let icon_only_button = bevy_ui_builder::widgets::IconButton::<MyUI>::icon_only(example_texture, "Close")
+		.with_aspect_ratio(1f32);
+
 	let column = column
 		.push(bevy_ui_builder::widgets::create_space(1f32))
 		.push(example_button)
+		.push(icon_button)
+		.push(icon_only_button)
 		.push(bevy_ui_builder::widgets::create_space(1f32))
 		.with_fill_portion(2f32);
 	bevy_ui_builder::widgets::Row::<_>::new()
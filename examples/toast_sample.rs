@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+use bevy_ui_builder::widgets::{TextButton, ToastCorner, Toasts};
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+#[derive(Component)]
+pub struct ToastButton(ToastSeverity);
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.add_systems(Update, show_toast_on_press)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	let mut column = bevy_ui_builder::widgets::Column::<MyUI>::new();
+	for (text, severity) in [("Info", ToastSeverity::Info), ("Success", ToastSeverity::Success), ("Warning", ToastSeverity::Warning), ("Destructive", ToastSeverity::Destructive)]
+	{
+		let button = TextButton::<MyUI>::new(text)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		commands.entity(button).insert(ToastButton(severity));
+		column = column.push(button);
+	}
+	column.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	Toasts::<MyUI>::new()
+		.with_corner(ToastCorner::BottomRight)
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+}
+
+fn show_toast_on_press(button_query: Query<(&Interaction, &ToastButton), Changed<Interaction>>, mut toast_writer: EventWriter<ShowToast>)
+{
+	for (interaction, button) in button_query.iter()
+	{
+		if *interaction != Interaction::Pressed
+			{ continue; }
+		let text = match button.0
+		{
+			ToastSeverity::Info => "A new version is available.",
+			ToastSeverity::Success => "Settings saved.",
+			ToastSeverity::Warning => "The controller's battery is low.",
+			ToastSeverity::Destructive => "Couldn't connect to the server.",
+		};
+		toast_writer.send(ShowToast::new(text, button.0));
+	}
+}
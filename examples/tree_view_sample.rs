@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_ui_builder::prelude::*;
+use bevy_ui_builder::widgets::{TreeSelectionMode, TreeView};
+
+#[derive(Default, States, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplicationState
+{
+	#[default]
+	Startup,
+}
+
+#[derive(Default, Component)]
+pub struct MyUI;
+
+#[derive(Clone)]
+pub struct Skill
+{
+	name: &'static str,
+	unlocks: Vec<Skill>,
+}
+
+fn skill(name: &'static str, unlocks: Vec<Skill>) -> Skill
+{
+	Skill { name, unlocks }
+}
+
+fn main()
+{
+	App::new()
+		.add_plugins(DefaultPlugins)
+		.init_state::<ApplicationState>()
+		.add_plugins(UIEventsPlugin)
+		.add_plugins
+		(
+			bevy_ui_builder::UIBuilderPlugin::<MyUI, _>::new(ApplicationState::Startup)
+				.register_root_builder(build_root)
+		)
+		.add_systems(Update, log_selection)
+		.run();
+}
+
+fn build_root(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<MyUI>>, theme: Res<CurrentThemeData<MyUI>>)
+{
+	commands.spawn(Camera2dBundle::default())
+		.insert(MyUI);
+	// A skill tree, Ctrl and Shift select several skills.
+	let combat = skill("Combat", vec![
+		skill("Swords", vec![skill("Parry", vec![]), skill("Riposte", vec![])]),
+		skill("Archery", vec![skill("Volley", vec![])]),
+	]);
+	let magic = skill("Magic", vec![skill("Fire", vec![skill("Fireball", vec![])]), skill("Frost", vec![])]);
+	TreeView::<MyUI, Skill>::new([combat, magic], |skill| skill.unlocks.clone(), |skill| skill.name.to_string())
+		.with_has_children(|skill| !skill.unlocks.is_empty())
+		.with_selection_mode(TreeSelectionMode::Multiple)
+		.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+}
+
+fn log_selection(mut selection_events: EventReader<TreeSelectionChanged>, node_query: Query<&TreeNode>)
+{
+	for event in selection_events.read()
+	{
+		let names: Vec<&str> = event.selected.iter()
+			.filter_map(|entity| node_query.get(*entity).ok()?.value::<Skill>())
+			.map(|skill| skill.name)
+			.collect();
+		info!("Selected {:?}", names);
+	}
+}
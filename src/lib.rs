@@ -42,6 +42,193 @@ impl Plugin for UIEventsPlugin
 					widgets::checkbox::handle_checkbox_toggle
 				)
 			)
+			.add_event::<widgets::dropdown::DropdownSelectionChanged>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::dropdown::init_dropdown_options,
+					widgets::dropdown::open_dropdown_on_press,
+					widgets::dropdown::close_dropdown_on_cancel,
+					widgets::dropdown::select_dropdown_option,
+					widgets::dropdown::apply_dropdown_state,
+					widgets::dropdown::position_dropdown_popups,
+				)
+					.chain()
+			)
+			.add_event::<widgets::slider::SliderChanged>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::slider::drag_slider,
+					widgets::slider::adjust_slider_with_input,
+					widgets::slider::update_slider_visuals,
+				)
+					.chain()
+					.after(NavRequestSystem)
+			)
+			.add_event::<widgets::radio::RadioSelectionChanged>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::radio::select_radio_option,
+					widgets::radio::navigate_radio_group,
+					widgets::radio::apply_radio_selection,
+				)
+					.chain()
+					.after(NavRequestSystem)
+			)
+			.add_systems
+			(
+				Update,
+				(
+					widgets::progress::update_progress_bars,
+					widgets::progress::animate_indeterminate_progress,
+					widgets::progress::update_meters,
+				)
+			)
+			.add_systems
+			(
+				Update,
+				(
+					widgets::scroll_view::scroll_with_wheel,
+					widgets::scroll_view::scroll_with_input,
+					widgets::scroll_view::drag_scroll_thumb,
+					widgets::scroll_view::scroll_focused_into_view,
+					widgets::scroll_view::apply_scroll_offset,
+				)
+					.chain()
+					.after(NavRequestSystem)
+			)
+			.add_systems
+			(
+				Update,
+				(
+					widgets::text_area::move_text_area_caret,
+					widgets::text_area::update_text_area_sections,
+					widgets::text_area::update_line_numbers,
+					widgets::text_area::scroll_to_caret,
+				)
+					.chain()
+					.after(NavRequestSystem)
+					.after(widgets::text_input::handle_text_input)
+					.after(widgets::scroll_view::scroll_focused_into_view)
+					.before(widgets::scroll_view::apply_scroll_offset)
+			)
+			.add_event::<widgets::tabs::TabChanged>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::tabs::select_tab_on_press,
+					widgets::tabs::switch_tabs_with_shortcuts,
+				)
+					.chain()
+					.after(NavRequestSystem)
+			)
+			.add_event::<widgets::modal::CloseModal>()
+			.add_event::<widgets::modal::ModalClosed>()
+			.add_event::<widgets::modal::ConfirmDialogResult>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::modal::trap_focus_in_modals,
+					widgets::modal::close_modals
+						.before(widgets::dropdown::close_dropdown_on_cancel),
+				)
+					.chain()
+					.after(NavRequestSystem)
+			)
+			.init_resource::<widgets::tooltip::TooltipSettings>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::tooltip::update_tooltips,
+					widgets::tooltip::position_tooltips,
+				)
+					.chain()
+					.after(NavRequestSystem)
+			)
+			.add_systems(Update, widgets::image_widget::fit_images.before(widgets::ensure_aspect_ratio))
+			.add_systems
+			(
+				Update,
+				(
+					widgets::icon_button::swap_icons_on_focus,
+					widgets::icon_button::keep_accessible_names,
+				)
+			)
+			.add_systems
+			(
+				Update,
+				(
+					widgets::grid::place_grid_cells,
+					widgets::grid::navigate_grids
+						.after(NavRequestSystem),
+				)
+			)
+			.add_systems
+			(
+				Update,
+				(
+					widgets::number_input::number_input_systems::<i32>(),
+					widgets::number_input::number_input_systems::<i64>(),
+					widgets::number_input::number_input_systems::<u32>(),
+					widgets::number_input::number_input_systems::<usize>(),
+					widgets::number_input::number_input_systems::<f32>(),
+					widgets::number_input::number_input_systems::<f64>(),
+				)
+			)
+			.add_event::<widgets::menu::MenuAction>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::menu::init_menu_items,
+					widgets::menu::close_menus
+						.after(widgets::modal::close_modals),
+					widgets::menu::open_menus,
+					widgets::menu::activate_menu_items,
+					widgets::menu::update_menu_checks,
+					widgets::menu::apply_menu_state,
+					widgets::menu::position_menu_popups,
+				)
+					.chain()
+					.after(NavRequestSystem)
+					.after(widgets::base_button::send_pressed_on_keyboard)
+			)
+			.add_event::<widgets::tree_view::TreeSelectionChanged>()
+			.add_event::<widgets::tree_view::TreeNodeToggled>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::tree_view::press_tree_nodes,
+					widgets::tree_view::navigate_trees,
+					widgets::tree_view::apply_tree_expansion,
+				)
+					.chain()
+					.after(NavRequestSystem)
+					.after(widgets::base_button::send_pressed_on_keyboard)
+			)
+			.add_event::<widgets::table::TableSorted>()
+			.add_event::<widgets::table::TableRowSelected>()
+			.add_systems
+			(
+				Update,
+				(
+					widgets::table::sort_tables_on_press,
+					widgets::table::select_table_rows,
+				)
+					.after(NavRequestSystem)
+					.after(widgets::base_button::send_pressed_on_keyboard)
+			)
+			.add_event::<widgets::toast::ShowToast>()
+			.add_systems(Update, widgets::toast::animate_toasts)
 			.add_plugins(DefaultNavigationPlugins)
 			.insert_resource
 			(
@@ -59,7 +246,24 @@ impl Plugin for UIEventsPlugin
 // This resource describes the UI tree of named elements.
 
 #[derive(Resource)]
-pub struct UIHierarchy<U: Component>(pub Arc<Mutex<indextree::Arena<TypeId>>>, pub std::marker::PhantomData<U>);
+pub struct UIHierarchy<U: Component>(pub Arc<Mutex<indextree::Arena<UINode>>>, pub std::marker::PhantomData<U>);
+
+/// A named element in the UIHierarchy.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct UINode
+{
+	pub type_id: TypeId,
+	/// Only used to show the hierarchy, see TreeView::from_ui_hierarchy.
+	pub type_name: &'static str,
+}
+
+impl UINode
+{
+	pub fn of<T: std::any::Any>() -> Self
+	{
+		Self { type_id: TypeId::of::<T>(), type_name: std::any::type_name::<T>() }
+	}
+}
 
 // unsafe impl<U: Component> Send for UIHierarchy<U> {}
 // unsafe impl<U: Component> Sync for UIHierarchy<U> {}
@@ -82,6 +286,11 @@ pub struct UIBuilderPlugin<D: Component, S: States>
 	pub builders: Mutex<HashMap<TypeId, SystemConfigs>>,
 	pub change_detectors: HashMap<TypeId, Vec<BoxedSystem>>,
 	pub root_builder: Mutex<Option<SystemConfigs>>,
+	/// If set, the theme follows the operating system's light/dark preference instead of `theme`.
+	pub follow_system_theme: Option<theme::FollowSystemTheme>,
+	/// If set, the first theme is picked from this source's light/dark preference, `theme` is used until it answers.
+	/// Cleared by `with_theme`.
+	pub detect_system_theme: Option<Arc<dyn theme::SystemThemeSource>>,
 	pub state: S,
 	_d: std::marker::PhantomData<D>,
 }
@@ -92,10 +301,13 @@ impl<D: Component, S: States> UIBuilderPlugin<D, S>
 	{
 		let result = Self
 		{
-			theme: theme::ThemeData::default(),
+			// The fallback until the system theme is detected.
+			theme: theme::themes::DARK.clone(),
 			builders: Default::default(),
 			change_detectors: Default::default(),
 			root_builder: None.into(),
+			follow_system_theme: None,
+			detect_system_theme: Some(theme::default_system_theme_source()),
 			state: state,
 			_d: std::marker::PhantomData,
 		};
@@ -105,6 +317,21 @@ impl<D: Component, S: States> UIBuilderPlugin<D, S>
 	pub fn with_theme(mut self, theme: theme::ThemeData) -> Self
 	{
 		self.theme = theme;
+		self.detect_system_theme = None;
+		self
+	}
+
+	/// Detects the first theme with `source` instead of the default source.
+	pub fn with_system_theme_source(mut self, source: impl theme::SystemThemeSource) -> Self
+	{
+		self.detect_system_theme = Some(Arc::new(source));
+		self
+	}
+
+	/// Polls the system light/dark preference, and swaps between the given light and dark themes when it changes.
+	pub fn follow_system_theme(mut self, follow_system_theme: theme::FollowSystemTheme) -> Self
+	{
+		self.follow_system_theme = Some(follow_system_theme);
 		self
 	}
 
@@ -168,7 +395,33 @@ impl<D: Component + Default + std::any::Any, S: States> Plugin for UIBuilderPlug
 		// let root_builder = unlocked_builders.remove(&root_component_id).unwrap();
 		let root_builder = self.root_builder.lock().unwrap().take().unwrap();
 		let mut ui_tree = indextree::Arena::new();
-		ui_tree.new_node(D::default().type_id());
+		ui_tree.new_node(UINode::of::<D>());
+		let initial_theme = self.theme.clone();
+		if let Some(follow_system_theme) = &self.follow_system_theme
+		{
+			// The source picks the theme once its first query finishes, which doesn't block startup.
+			app
+				.insert_resource(theme::SystemThemeFollower::<D>::new(follow_system_theme.clone()))
+				.add_systems
+				(
+					Update,
+					theme::follow_system_theme::<D>
+						.before(theme::repaint_on_theme_change::<D>)
+				);
+		}
+		else if let Some(source) = &self.detect_system_theme
+		{
+			// Like following, detection doesn't block startup.
+			app
+				.insert_resource(theme::SystemThemeDetection::<D>::new(source))
+				.add_systems
+				(
+					Update,
+					theme::apply_detected_system_theme::<D>
+						.run_if(resource_exists::<theme::SystemThemeDetection<D>>)
+						.before(theme::repaint_on_theme_change::<D>)
+				);
+		}
 		app
 			.add_systems(OnEnter(self.state.clone()), root_builder.into_configs())
 			// Insert the UIHierarchy resource.
@@ -209,8 +462,55 @@ impl<D: Component + Default + std::any::Any, S: States> Plugin for UIBuilderPlug
 					.run_if(in_state(self.state.clone()))
 					.after(NavRequestSystem)
 			)
+			.add_systems
+			(
+				Update,
+				(
+					theme::repaint_on_theme_change::<D>,
+					// Widgets that colour themselves differently adjust their targets and tweens around the generic systems.
+					(
+						widgets::base_button::shade_repainted_buttons::<D>,
+						widgets::image_widget::tint_images::<D>,
+					),
+					theme::start_colour_tweens::<D>,
+					widgets::rich_text::start_rich_text_tweens::<D>,
+					theme::animate_colours::<D>,
+					widgets::rich_text::colour_rich_text::<D>,
+				)
+					.chain()
+					.after(widgets::base_button::style_button_on_focus::<D>)
+					.after(widgets::base_button::style_button_on_pressed::<D>)
+			)
+			.add_systems
+			(
+				Update,
+				(
+					widgets::switch::apply_switch_state::<D>
+						.after(widgets::checkbox::toggle_checkbox)
+						.before(theme::repaint_on_theme_change::<D>),
+					widgets::switch::animate_switch_thumbs::<D>,
+					widgets::list_view::update_list_views::<D>
+						.after(widgets::scroll_view::apply_scroll_offset),
+					widgets::tabs::apply_active_tab::<D>
+						.after(widgets::tabs::switch_tabs_with_shortcuts)
+						.before(theme::repaint_on_theme_change::<D>),
+					widgets::toast::show_toasts::<D>
+						.before(widgets::toast::animate_toasts),
+					widgets::tree_view::build_tree_children::<D>
+						.after(widgets::tree_view::navigate_trees),
+					widgets::tree_view::apply_tree_selection::<D>
+						.after(widgets::tree_view::press_tree_nodes)
+						.before(theme::repaint_on_theme_change::<D>),
+					widgets::table::apply_table_sort::<D>
+						.after(widgets::table::sort_tables_on_press)
+						.before(theme::repaint_on_theme_change::<D>),
+					widgets::table::apply_table_selection::<D>
+						.after(widgets::table::select_table_rows)
+						.before(theme::repaint_on_theme_change::<D>),
+				)
+			)
 			.add_systems(OnExit(self.state.clone()), Self::destroy_ui_on_exit)
-			.insert_resource(theme::CurrentThemeData::<D>(self.theme.clone(), PhantomData))
+			.insert_resource(theme::CurrentThemeData::<D>(initial_theme, PhantomData))
 			;
 	}
 }
@@ -251,4 +551,60 @@ mod tests
 		let test_resource = app.world_mut().get_resource::<TestResource>().expect("TestResource not inserted");
 		assert_eq!(test_resource.0, MAGIC_NUMBER);
 	}
+
+	#[test]
+	fn follows_system_theme()
+	{
+		use theme::{FollowSystemTheme, MockSystemTheme, SystemThemePreference};
+		let mut app = App::new();
+		test::PretendWindowPlugin.build(&mut app);
+		bevy::state::app::StatesPlugin.build(&mut app);
+		app.init_state::<test::TestApplicationState>();
+		let source = MockSystemTheme::new(SystemThemePreference::Light);
+		let plugin = UIBuilderPlugin::<test::TestUI, _>::new(test::TestApplicationState::Startup)
+			.register_root_builder(|| {})
+			.follow_system_theme(FollowSystemTheme::default().with_source(source.clone()));
+		plugin.build(&mut app);
+		UIEventsPlugin.build(&mut app);
+		// The crate's tests build Bevy without multi_threaded, so the task pool answers each query as it is spawned.
+		app.update();
+		assert_eq!(app.world().resource::<theme::CurrentThemeData<test::TestUI>>().0.base, theme::LIGHT.base);
+
+		source.set(SystemThemePreference::Dark);
+		app.world_mut().resource_mut::<Time>().advance_by(std::time::Duration::from_secs(2));
+		app.update();
+		assert_eq!(app.world().resource::<theme::CurrentThemeData<test::TestUI>>().0.base, theme::DARK.base);
+
+		// Unknown preferences keep the current theme.
+		source.set(SystemThemePreference::Unknown);
+		app.world_mut().resource_mut::<Time>().advance_by(std::time::Duration::from_secs(2));
+		app.update();
+		assert_eq!(app.world().resource::<theme::CurrentThemeData<test::TestUI>>().0.base, theme::DARK.base);
+	}
+
+	#[test]
+	fn detects_the_system_theme_without_following_it()
+	{
+		use theme::{MockSystemTheme, SystemThemePreference};
+		let mut app = App::new();
+		test::PretendWindowPlugin.build(&mut app);
+		bevy::state::app::StatesPlugin.build(&mut app);
+		app.init_state::<test::TestApplicationState>();
+		let source = MockSystemTheme::new(SystemThemePreference::Light);
+		UIBuilderPlugin::<test::TestUI, _>::new(test::TestApplicationState::Startup)
+			.register_root_builder(|| {})
+			.with_system_theme_source(source.clone())
+			.build(&mut app);
+		UIEventsPlugin.build(&mut app);
+		// The fallback is used until the first update picks up the answer.
+		assert_eq!(app.world().resource::<theme::CurrentThemeData<test::TestUI>>().0.base, theme::DARK.base);
+		app.update();
+		assert_eq!(app.world().resource::<theme::CurrentThemeData<test::TestUI>>().0.base, theme::LIGHT.base);
+		assert!(!app.world().contains_resource::<theme::SystemThemeDetection<test::TestUI>>());
+
+		source.set(SystemThemePreference::Dark);
+		app.world_mut().resource_mut::<Time>().advance_by(std::time::Duration::from_secs(2));
+		app.update();
+		assert_eq!(app.world().resource::<theme::CurrentThemeData<test::TestUI>>().0.base, theme::LIGHT.base);
+	}
 }
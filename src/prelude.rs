@@ -1,10 +1,24 @@
 pub use crate::widgets::WidgetBuilder;
 pub use crate::widgets::Widget;
+pub use crate::widgets::tooltip::WithTooltip;
+pub use crate::widgets::menu::WithContextMenu;
 pub use crate::widgets::ParentData;
 
 // Needed to query for widget contents:
 pub use crate::widgets::text_input::EditableText;
 pub use crate::widgets::checkbox::CheckBoxState;
+pub use crate::widgets::dropdown::{DropdownSelection, DropdownSelectionChanged};
+pub use crate::widgets::slider::{SliderValue, SliderChanged};
+pub use crate::widgets::radio::{RadioSelection, RadioSelectionChanged};
+pub use crate::widgets::progress::Progress;
+pub use crate::widgets::scroll_view::ScrollOffset;
+pub use crate::widgets::tabs::{ActiveTab, TabChanged};
+pub use crate::widgets::modal::{CloseModal, ModalClosed, ConfirmDialogResult};
+pub use crate::widgets::number_input::{NumberValue, Num};
+pub use crate::widgets::toast::{ShowToast, ToastSeverity};
+pub use crate::widgets::menu::{MenuAction, MenuItemState};
+pub use crate::widgets::tree_view::{TreeNode, TreeSelection, TreeSelectionChanged, TreeNodeToggled};
+pub use crate::widgets::table::{TableRow, TableSelection, TableSorted, TableRowSelected};
 
 pub use crate::theme::CurrentThemeData;
 pub use crate::theme::Theme;
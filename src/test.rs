@@ -1,6 +1,7 @@
 // Tests and utilities for testing the library.
 
 use bevy::prelude::*;
+use bevy_alt_ui_navigation_lite::prelude::{FocusState, Focusable};
 
 pub struct PretendWindowPlugin;
 
@@ -13,12 +14,70 @@ impl Plugin for PretendWindowPlugin
 			.add_event::<bevy::window::CursorMoved>()
 			.add_event::<bevy::input::touch::TouchInput>()
 			.add_event::<bevy::input::mouse::MouseButtonInput>()
+			.add_event::<bevy::input::mouse::MouseWheel>()
 			.init_resource::<bevy::input::ButtonInput<bevy::input::keyboard::KeyCode>>()
 			.init_resource::<bevy::input::ButtonInput<bevy::input::mouse::MouseButton>>()
 			.init_resource::<bevy::input::ButtonInput<bevy::input::gamepad::GamepadButton>>()
 			.init_resource::<bevy::input::Axis<bevy::input::gamepad::GamepadAxis>>()
+			.init_resource::<bevy::input::gamepad::Gamepads>()
 			.init_resource::<bevy::ecs::event::Events<bevy::window::ReceivedCharacter>>()
 			.init_resource::<bevy::ui::UiStack>()
+			.init_resource::<Assets<Image>>()
+			.init_resource::<Time>() // Colour transitions and timers need a clock, advance it manually with Time::advance_by.
 			;
 	}
 }
+
+#[derive(Default, States, Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum TestApplicationState
+{
+	#[default]
+	Startup,
+}
+
+/// Creates an app with the UI plugins, that builds its UI with `root_builder` on the first update.
+pub fn build_test_app<D: Component + Default, M>(root_builder: impl IntoSystemConfigs<M>) -> App
+{
+	let mut app = App::new();
+	PretendWindowPlugin.build(&mut app);
+	bevy::state::app::StatesPlugin.build(&mut app);
+	app.init_state::<TestApplicationState>();
+	crate::UIBuilderPlugin::<D, _>::new(TestApplicationState::Startup)
+		.with_theme(crate::theme::DARK.clone())
+		.register_root_builder(root_builder)
+		.build(&mut app);
+	crate::UIEventsPlugin.build(&mut app);
+	app
+}
+
+/// The UI marker the widget tests build their UIs with.
+#[derive(Default, Component)]
+pub struct TestUI;
+
+/// The focused entity, if any.
+pub fn focused(app: &mut App) -> Option<Entity>
+{
+	app.world_mut().query::<(Entity, &Focusable)>().iter(app.world())
+		.find(|(_, focusable)| focusable.state() == FocusState::Focused)
+		.map(|(entity, _)| entity)
+}
+
+/// Presses and releases `key`, updating once while it's down and once after it's released.
+pub fn press_key(app: &mut App, key: KeyCode)
+{
+	app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+	app.update();
+	let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+	keyboard_input.release(key);
+	keyboard_input.clear();
+	app.update();
+}
+
+/// Presses and releases a button (anything with an Interaction), updating once while it's pressed and once after it's released.
+pub fn press(app: &mut App, entity: Entity)
+{
+	*app.world_mut().get_mut::<Interaction>(entity).unwrap() = Interaction::Pressed;
+	app.update();
+	*app.world_mut().get_mut::<Interaction>(entity).unwrap() = Interaction::None;
+	app.update();
+}
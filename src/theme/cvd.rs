@@ -0,0 +1,54 @@
+// Colour vision deficiency simulation, used to preview and validate themes for colour-blind players.
+
+use bevy::prelude::*;
+
+/// The kinds of dichromacy that can be simulated with `ThemeData::simulate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cvd
+{
+	/// Missing red (L) cones.
+	Protanopia,
+	/// Missing green (M) cones.
+	Deuteranopia,
+	/// Missing blue (S) cones.
+	Tritanopia,
+}
+
+impl Cvd
+{
+	pub const ALL: [Cvd; 3] = [Cvd::Protanopia, Cvd::Deuteranopia, Cvd::Tritanopia];
+
+	/// Matrices from Machado, Oliveira & Fernandes (2009), at full severity. These operate on linear RGB.
+	fn matrix(&self) -> [[f32; 3]; 3]
+	{
+		match self
+		{
+			Cvd::Protanopia =>
+			[
+				[0.152286, 1.052583, -0.204868],
+				[0.114503, 0.786281, 0.099216],
+				[-0.003882, -0.048116, 1.051998],
+			],
+			Cvd::Deuteranopia =>
+			[
+				[0.367322, 0.860646, -0.227968],
+				[0.280085, 0.672501, 0.047413],
+				[-0.011820, 0.042940, 0.968881],
+			],
+			Cvd::Tritanopia =>
+			[
+				[1.255528, -0.076749, -0.178779],
+				[-0.078411, 0.930809, 0.147602],
+				[0.004733, 0.691367, 0.303900],
+			],
+		}
+	}
+
+	/// Returns the colour as it would be perceived with this deficiency.
+	pub fn simulate_colour(&self, colour: Color) -> Color
+	{
+		let LinearRgba { red, green, blue, alpha } = colour.into();
+		let [r, g, b] = self.matrix().map(|row| (row[0] * red + row[1] * green + row[2] * blue).clamp(0.0, 1.0));
+		LinearRgba::new(r, g, b, alpha).into()
+	}
+}
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{collections::HashMap, marker::PhantomData};
 
 use bevy::prelude::*;
 
@@ -7,6 +7,15 @@ pub use themes::*;
 
 pub mod dimensions;
 
+pub mod cvd;
+pub use cvd::Cvd;
+
+pub mod system;
+pub use system::*;
+
+pub mod transition;
+pub use transition::*;
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Theme
 {
@@ -14,8 +23,16 @@ pub enum Theme
 	Primary,
 	Secondary,
 	Tertiary,
+	/// For dangerous actions and damage. There is no separate container role, so containers use the same colours.
+	Destructive,
+	/// For confirmations and completed actions. Like Destructive, containers use the same colours.
+	Success,
+	/// For things that need attention but aren't errors. Like Destructive, containers use the same colours.
+	Warning,
 
 	Custom(Color, Color),
+	/// A custom background, with the container colours and foregrounds derived from it.
+	CustomBackground(Color),
 
 	#[default]
 	Auto
@@ -51,7 +68,11 @@ impl Theme
 			Theme::Primary => theme.primary,
 			Theme::Secondary => theme.secondary,
 			Theme::Tertiary => theme.tertiary,
+			Theme::Destructive => theme.destructive,
+			Theme::Success => theme.success,
+			Theme::Warning => theme.warning,
 			Theme::Custom(background, _) => *background,
+			Theme::CustomBackground(background) => *background,
 			Theme::Auto => theme.base,
 		}
 	}
@@ -64,7 +85,11 @@ impl Theme
 			Theme::Primary => theme.primary_foreground,
 			Theme::Secondary => theme.secondary_foreground,
 			Theme::Tertiary => theme.tertiary_foreground,
+			Theme::Destructive => theme.destructive_foreground,
+			Theme::Success => theme.success_foreground,
+			Theme::Warning => theme.warning_foreground,
 			Theme::Custom(_, foreground) => *foreground,
+			Theme::CustomBackground(background) => background.on_colour(),
 			Theme::Auto => theme.base_foreground,
 		}
 	}
@@ -77,7 +102,11 @@ impl Theme
 			Theme::Primary => theme.primary_container,
 			Theme::Secondary => theme.secondary_container,
 			Theme::Tertiary => theme.tertiary_container,
+			Theme::Destructive => theme.destructive,
+			Theme::Success => theme.success,
+			Theme::Warning => theme.warning,
 			Theme::Custom(background, _) => *background,
+			Theme::CustomBackground(background) => container_of(*background),
 			Theme::Auto => theme.base_container,
 		}
 	}
@@ -90,19 +119,88 @@ impl Theme
 			Theme::Primary => theme.primary_container_foreground,
 			Theme::Secondary => theme.secondary_container_foreground,
 			Theme::Tertiary => theme.tertiary_container_foreground,
+			Theme::Destructive => theme.destructive_foreground,
+			Theme::Success => theme.success_foreground,
+			Theme::Warning => theme.warning_foreground,
 			Theme::Custom(_, foreground) => *foreground,
+			Theme::CustomBackground(background) => container_of(*background).on_colour(),
 			Theme::Auto => theme.base_container_foreground,
 		}
 	}
 }
 
+/// Derives a container colour from a background, the same way the built-in themes relate their roles:
+/// light backgrounds get darker containers, dark backgrounds get lighter ones.
+pub fn container_of(background: Color) -> Color
+{
+	if get_contrast_ratio(background, Color::WHITE) >= get_contrast_ratio(background, Color::BLACK)
+		{ background.lighten(0.25) }
+	else
+		{ background.darken(0.25) }
+}
+
+/// Logs a warning (in debug builds only) if an explicitly chosen foreground is not readable on its background.
+pub fn warn_if_inaccessible(background: Color, foreground: Color)
+{
+	if cfg!(debug_assertions) && !is_contrast_accessible(background, foreground)
+	{
+		warn!
+		(
+			"Foreground {:?} on background {:?} has a contrast ratio of {:.2}, below the accessible {CONTRAST_ACCESSIBILITY_RATIO}. Consider using with_background(...) instead.",
+			foreground.to_srgba(),
+			background.to_srgba(),
+			get_contrast_ratio(background, foreground),
+		);
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaintMode
 {
 	Background,
 	BackgroundContainer,
+	/// Paints the background with the foreground colour, for small indicators (slider thumbs, etc.) that must stand out from their surroundings.
+	Foreground,
 	Invisible,
 }
 
+impl PaintMode
+{
+	pub fn get_background(&self, theme: Theme, theme_data: &ThemeData) -> Color
+	{
+		match self
+		{
+			PaintMode::Background => theme.get_background(theme_data),
+			PaintMode::BackgroundContainer => theme.get_background_container(theme_data),
+			PaintMode::Foreground => theme.get_foreground(theme_data),
+			PaintMode::Invisible => Color::NONE,
+		}
+	}
+
+	/// Borders use the opposite role to the background, so they stand out from it.
+	pub fn get_border(&self, theme: Theme, theme_data: &ThemeData) -> Color
+	{
+		match self
+		{
+			PaintMode::Background => theme.get_background_container(theme_data),
+			PaintMode::BackgroundContainer => theme.get_background(theme_data),
+			PaintMode::Foreground => theme.get_foreground(theme_data),
+			PaintMode::Invisible => Color::NONE,
+		}
+	}
+
+	/// Invisible widgets draw their text as if they were painted with the background role.
+	pub fn get_foreground(&self, theme: Theme, theme_data: &ThemeData) -> Color
+	{
+		match self
+		{
+			PaintMode::BackgroundContainer => theme.get_foreground_container(theme_data),
+			PaintMode::Foreground => theme.get_background(theme_data),
+			_ => theme.get_foreground(theme_data),
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct ThemeData
 {
@@ -132,8 +230,19 @@ pub struct ThemeData
 	pub destructive: Color,
 	pub destructive_foreground: Color,
 
+	pub success: Color,
+	pub success_foreground: Color,
+
+	pub warning: Color,
+	pub warning_foreground: Color,
+
 	/// This font will override the default font for all widgets that do not specify a custom font.
 	pub default_font: Option<Handle<Font>>,
+	/// Fonts rich text can switch to with `[font=name]`. `[b]` and `[i]` use the fonts named "bold", "italic" and "bold_italic".
+	pub fonts: HashMap<String, Handle<Font>>,
+
+	/// How colour changes (focus, presses, theme changes) are animated.
+	pub transition: ColourTransition,
 }
 
 #[derive(Debug, Clone, Resource)]
@@ -142,39 +251,296 @@ pub struct CurrentThemeData<W>(pub ThemeData, pub PhantomData<W>);
 #[derive(Debug, Clone, PartialEq, Component)]
 pub struct CurrentTheme<W>(pub Theme, pub PhantomData<W>);
 
+/// Remembers the resolved theme and paint mode a widget was built with, so it can be repainted when the theme data changes.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct ThemePaint<W>
+{
+	pub theme: Theme,
+	pub paint_mode: PaintMode,
+	pub phantom: PhantomData<W>,
+}
+
+impl<W> ThemePaint<W>
+{
+	pub fn new(theme: Theme, paint_mode: PaintMode) -> Self
+	{
+		Self { theme, paint_mode, phantom: PhantomData }
+	}
+
+	/// The background and border colours a widget painted this way should have.
+	pub fn node_target(&self, theme_data: &ThemeData) -> ColourTarget
+	{
+		ColourTarget
+		{
+			background: Some(self.paint_mode.get_background(self.theme, theme_data)),
+			border: Some(self.paint_mode.get_border(self.theme, theme_data)),
+			text: None,
+			image: None,
+		}
+	}
+
+	/// The background and text colours a text entity painted this way should have.
+	pub fn text_target(&self, theme_data: &ThemeData) -> ColourTarget
+	{
+		ColourTarget
+		{
+			background: Some(self.paint_mode.get_background(self.theme, theme_data)),
+			border: None,
+			text: Some(self.paint_mode.get_foreground(self.theme, theme_data)),
+			image: None,
+		}
+	}
+}
+
+/// Whether a widget should be repainted this frame: the theme data was replaced, or its ThemePaint was changed.
+pub fn needs_repaint<U: Component>(theme_data: &Res<CurrentThemeData<U>>, paint: &Ref<ThemePaint<U>>) -> bool
+{
+	(theme_data.is_changed() && !theme_data.is_added()) || (paint.is_changed() && !paint.is_added())
+}
+
+/// Repaints every themed widget when the theme data is replaced, and any widget whose ThemePaint was changed.
+/// The new colours are animated to through their ColourTarget.
+/// Widgets that colour themselves differently adjust the target after this and before start_colour_tweens (see needs_repaint).
+pub fn repaint_on_theme_change<U: Component>
+(
+	theme_data: Res<CurrentThemeData<U>>,
+	mut query: Query<(Ref<ThemePaint<U>>, &mut ColourTarget, Has<Text>)>,
+)
+{
+	for (paint, mut target, is_text) in query.iter_mut()
+	{
+		if !needs_repaint(&theme_data, &paint)
+			{ continue; }
+		*target = if is_text { paint.text_target(&theme_data.0) } else { paint.node_target(&theme_data.0) };
+	}
+}
+
 /// See https://m3.material.io/foundations/accessible-design/patterns#c06040d0-f7dd-43d8-af92-384bbb3b0544
 pub const CONTRAST_ACCESSIBILITY_RATIO: f64 = 4.5;
+/// The enhanced (WCAG AAA) ratio, targeted by the high contrast themes.
+pub const CONTRAST_ENHANCED_RATIO: f64 = 7.0;
 
 pub fn is_contrast_accessible(color1: Color, color2: Color) -> bool
 {
 	get_contrast_ratio(color1, color2) >= CONTRAST_ACCESSIBILITY_RATIO
 }
+/// Colour operations used to derive themes from a handful of base colours.
+///
+/// These work in OKLCH rather than HSL, so a change in lightness is perceived as the same change regardless of hue,
+/// and the hue does not drift as a colour gets lighter or darker.
+/// Results are always mapped back into the sRGB gamut by reducing chroma, never by clipping channels.
 pub trait ShiftColour
 {
+	/// Moves the perceptual lightness towards white by `amount` (0.0..=1.0) of the remaining distance.
 	fn lighten(self, amount: f32) -> Self;
+	/// Moves the perceptual lightness towards black by `amount` (0.0..=1.0) of the remaining distance.
 	fn darken(self, amount: f32) -> Self;
+	/// Interpolates between this colour and `other` in Oklab, `t` = 0.0 being this colour.
+	fn mix(self, other: Self, t: f32) -> Self;
+	/// Sets the perceptual lightness of this colour, 0.0 being black and 100.0 being white.
+	fn tone(self, tone: f32) -> Self;
+	/// Sets the OKLCH chroma of this colour, reduced if needed to stay displayable.
+	fn with_chroma(self, chroma: f32) -> Self;
+	/// Picks a foreground of the same hue that is accessible on top of this colour.
+	fn on_colour(self) -> Self;
+}
+
+fn is_in_gamut(colour: Oklcha) -> bool
+{
+	const EPSILON: f32 = 1e-4;
+	let LinearRgba { red, green, blue, .. } = colour.into();
+	[red, green, blue].iter().all(|channel| (-EPSILON..=1.0 + EPSILON).contains(channel))
+}
+
+/// Clamps lightness, then reduces chroma (keeping hue) until the colour fits in sRGB.
+fn map_to_gamut(colour: Oklcha) -> Color
+{
+	let colour = Oklcha
+	{
+		lightness: colour.lightness.clamp(0.0, 1.0),
+		chroma: colour.chroma.max(0.0),
+		..colour
+	};
+	if is_in_gamut(colour)
+		{ return colour.into(); }
+	let (mut low, mut high) = (0.0, colour.chroma);
+	for _ in 0..16
+	{
+		let chroma = (low + high) / 2.0;
+		if is_in_gamut(colour.with_chroma(chroma))
+			{ low = chroma; }
+		else
+			{ high = chroma; }
+	}
+	colour.with_chroma(low).into()
 }
 
 impl ShiftColour for Color
 {
 	fn lighten(self, amount: f32) -> Self
 	{
-		let Hsla { hue, saturation, lightness, alpha } = self.into()
-			else { unreachable!("Color::as_hsla() returned a non-HSLA color") };
-		Color::hsla(hue, saturation, lightness + amount, alpha)
+		let colour: Oklcha = self.into();
+		let lightness = colour.lightness + (1.0 - colour.lightness) * amount.clamp(0.0, 1.0);
+		map_to_gamut(colour.with_lightness(lightness))
 	}
 
 	fn darken(self, amount: f32) -> Self
 	{
-		let Hsla { hue, saturation, lightness, alpha } = self.into()
-			else { unreachable!("Color::as_hsla() returned a non-HSLA color") };
-		Color::hsla(hue, saturation, lightness - amount, alpha)
+		let colour: Oklcha = self.into();
+		let lightness = colour.lightness * (1.0 - amount.clamp(0.0, 1.0));
+		map_to_gamut(colour.with_lightness(lightness))
+	}
+
+	fn mix(self, other: Self, t: f32) -> Self
+	{
+		let from: Oklaba = self.into();
+		let to: Oklaba = other.into();
+		Mix::mix(&from, &to, t.clamp(0.0, 1.0)).into()
+	}
+
+	fn tone(self, tone: f32) -> Self
+	{
+		let colour: Oklcha = self.into();
+		map_to_gamut(colour.with_lightness(tone.clamp(0.0, 100.0) / 100.0))
+	}
+
+	fn with_chroma(self, chroma: f32) -> Self
+	{
+		let colour: Oklcha = self.into();
+		map_to_gamut(colour.with_chroma(chroma))
 	}
 
+	fn on_colour(self) -> Self
+	{
+		let background = self.with_alpha(1.0);
+		let prefers_light = get_contrast_ratio(background, Color::WHITE) >= get_contrast_ratio(background, Color::BLACK);
+		// Start with a slightly tinted foreground, and head towards pure white/black until it is readable.
+		let tones = if prefers_light { [95.0, 97.5, 99.0] } else { [20.0, 12.5, 5.0] };
+		let Oklcha { chroma, .. } = background.into();
+		let tinted = background.with_chroma(chroma.min(0.02));
+		tones
+			.into_iter()
+			.map(|tone| tinted.tone(tone))
+			.find(|foreground| is_contrast_accessible(background, *foreground))
+			// One of white or black always reaches at least ~4.58:1.
+			.unwrap_or(if prefers_light { Color::WHITE } else { Color::BLACK })
+	}
 }
 
 impl ThemeData
 {
+	/// Looks up a colour by the name of its role ("primary", "destructive_foreground", etc.), or parses a hex colour ("#ff8800").
+	pub fn colour_by_name(&self, name: &str) -> Option<Color>
+	{
+		let colour = match name
+		{
+			"base" => self.base,
+			"base_foreground" => self.base_foreground,
+			"base_container" => self.base_container,
+			"base_container_foreground" => self.base_container_foreground,
+			"primary" => self.primary,
+			"primary_foreground" => self.primary_foreground,
+			"primary_container" => self.primary_container,
+			"primary_container_foreground" => self.primary_container_foreground,
+			"secondary" => self.secondary,
+			"secondary_foreground" => self.secondary_foreground,
+			"secondary_container" => self.secondary_container,
+			"secondary_container_foreground" => self.secondary_container_foreground,
+			"tertiary" => self.tertiary,
+			"tertiary_foreground" => self.tertiary_foreground,
+			"tertiary_container" => self.tertiary_container,
+			"tertiary_container_foreground" => self.tertiary_container_foreground,
+			"disabled" => self.disabled,
+			"disabled_foreground" => self.disabled_foreground,
+			"destructive" => self.destructive,
+			"destructive_foreground" => self.destructive_foreground,
+			"success" => self.success,
+			"success_foreground" => self.success_foreground,
+			"warning" => self.warning,
+			"warning_foreground" => self.warning_foreground,
+			_ => return Srgba::hex(name).ok().map(Color::from),
+		};
+		Some(colour)
+	}
+
+	/// Every background paired with the foreground that is drawn on top of it.
+	pub fn role_pairs(&self) -> [(Color, Color); 12]
+	{
+		[
+			(self.base, self.base_foreground),
+			(self.base_container, self.base_container_foreground),
+			(self.primary, self.primary_foreground),
+			(self.primary_container, self.primary_container_foreground),
+			(self.secondary, self.secondary_foreground),
+			(self.secondary_container, self.secondary_container_foreground),
+			(self.tertiary, self.tertiary_foreground),
+			(self.tertiary_container, self.tertiary_container_foreground),
+			(self.disabled, self.disabled_foreground),
+			(self.destructive, self.destructive_foreground),
+			(self.success, self.success_foreground),
+			(self.warning, self.warning_foreground),
+		]
+	}
+
+	/// Returns a copy of this theme with `f` applied to every colour.
+	pub fn map_colours(&self, f: impl Fn(Color) -> Color) -> ThemeData
+	{
+		ThemeData
+		{
+			base: f(self.base),
+			base_foreground: f(self.base_foreground),
+			base_container: f(self.base_container),
+			base_container_foreground: f(self.base_container_foreground),
+
+			primary: f(self.primary),
+			primary_foreground: f(self.primary_foreground),
+			primary_container: f(self.primary_container),
+			primary_container_foreground: f(self.primary_container_foreground),
+
+			secondary: f(self.secondary),
+			secondary_foreground: f(self.secondary_foreground),
+			secondary_container: f(self.secondary_container),
+			secondary_container_foreground: f(self.secondary_container_foreground),
+
+			tertiary: f(self.tertiary),
+			tertiary_foreground: f(self.tertiary_foreground),
+			tertiary_container: f(self.tertiary_container),
+			tertiary_container_foreground: f(self.tertiary_container_foreground),
+
+			disabled: f(self.disabled),
+			disabled_foreground: f(self.disabled_foreground),
+
+			destructive: f(self.destructive),
+			destructive_foreground: f(self.destructive_foreground),
+
+			success: f(self.success),
+			success_foreground: f(self.success_foreground),
+
+			warning: f(self.warning),
+			warning_foreground: f(self.warning_foreground),
+
+			default_font: self.default_font.clone(),
+			fonts: self.fonts.clone(),
+
+			transition: self.transition,
+		}
+	}
+
+	/// Returns this theme as it would be seen with the given colour vision deficiency.
+	/// Useful for previewing a theme, or validating it in tests.
+	pub fn simulate(&self, cvd: Cvd) -> ThemeData
+	{
+		self.map_colours(|colour| cvd.simulate_colour(colour))
+	}
+
+	/// Returns true if every role pair reaches the given contrast ratio.
+	pub fn meets_contrast(&self, ratio: f64) -> bool
+	{
+		self.role_pairs()
+			.iter()
+			.all(|(background, foreground)| get_contrast_ratio(*background, *foreground) >= ratio)
+	}
+
 	#[allow(dead_code)] // This is used in the tests, and should be used by users implementing their own themes.
 	fn is_accessible(&self)
 	{
@@ -187,6 +553,49 @@ impl ThemeData
 		assert!(is_contrast_accessible(self.tertiary_container, self.tertiary_container_foreground));
 		assert!(is_contrast_accessible(self.disabled, self.disabled_foreground));
 		assert!(is_contrast_accessible(self.destructive, self.destructive_foreground));
+		assert!(is_contrast_accessible(self.success, self.success_foreground));
+		assert!(is_contrast_accessible(self.warning, self.warning_foreground));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn lighten_does_not_saturate_to_white()
+	{
+		let lightened = colours::ENGLISH_VIOLET.lighten(0.9);
+		assert_ne!(lightened.to_srgba(), Color::WHITE.to_srgba());
+		let Oklcha { hue: original_hue, .. } = (*colours::ENGLISH_VIOLET).into();
+		let Oklcha { hue, .. } = lightened.into();
+		assert!((hue - original_hue).abs() < 5.0, "Hue drifted from {original_hue} to {hue}");
+	}
+
+	#[test]
+	fn on_colour_is_accessible()
+	{
+		for tone in (0..=100).step_by(5)
+		{
+			for colour in [*colours::RAISIN_BLACK, *colours::ENGLISH_VIOLET, *colours::WALNUT_BROWN, *RED]
+			{
+				let background = colour.tone(tone as f32);
+				assert!(is_contrast_accessible(background, background.on_colour()), "No accessible foreground for tone {tone}");
+			}
+		}
+	}
+
+	#[test]
+	fn custom_background_is_accessible()
+	{
+		let theme_data = themes::DARK.clone();
+		for background in [bevy::color::palettes::basic::RED.into(), bevy::color::palettes::basic::LIME.into(), Color::WHITE, Color::BLACK]
+		{
+			let theme = Theme::CustomBackground(background);
+			assert!(is_contrast_accessible(theme.get_background(&theme_data), theme.get_foreground(&theme_data)));
+			assert!(is_contrast_accessible(theme.get_background_container(&theme_data), theme.get_foreground_container(&theme_data)));
+		}
 	}
 }
 
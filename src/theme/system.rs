@@ -0,0 +1,253 @@
+// Detecting the operating system's light/dark preference, and following it at runtime.
+
+use std::{marker::PhantomData, sync::{mpsc, Arc, Mutex}, time::Duration};
+
+use bevy::{prelude::*, tasks::{AsyncComputeTaskPool, TaskPool}};
+
+use super::{CurrentThemeData, ThemeData};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SystemThemePreference
+{
+	Light,
+	Dark,
+	/// The source could not tell, or the platform has no preference.
+	#[default]
+	Unknown,
+}
+
+/// Somewhere the operating system's light/dark preference can be read from.
+///
+/// This is queried on the async compute task pool, so implementations may block (the `dark-light` crate can, on some Linux setups).
+pub trait SystemThemeSource: Send + Sync + 'static
+{
+	fn preference(&self) -> SystemThemePreference;
+}
+
+/// Reads the preference with the `dark-light` crate.
+#[cfg(feature = "dark-light")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DarkLightSource;
+
+#[cfg(feature = "dark-light")]
+impl SystemThemeSource for DarkLightSource
+{
+	fn preference(&self) -> SystemThemePreference
+	{
+		match dark_light::detect()
+		{
+			dark_light::Mode::Dark => SystemThemePreference::Dark,
+			dark_light::Mode::Light => SystemThemePreference::Light,
+			dark_light::Mode::Default => SystemThemePreference::Unknown,
+		}
+	}
+}
+
+/// Never has a preference, used when no other source is available.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSystemTheme;
+
+impl SystemThemeSource for NoSystemTheme
+{
+	fn preference(&self) -> SystemThemePreference
+	{
+		SystemThemePreference::Unknown
+	}
+}
+
+/// A source whose preference is set by hand, for tests (or for a "follow system" toggle driven by something else).
+#[derive(Debug, Clone, Default)]
+pub struct MockSystemTheme(pub Arc<Mutex<SystemThemePreference>>);
+
+impl MockSystemTheme
+{
+	pub fn new(preference: SystemThemePreference) -> Self
+	{
+		Self(Arc::new(Mutex::new(preference)))
+	}
+
+	pub fn set(&self, preference: SystemThemePreference)
+	{
+		*self.0.lock().unwrap() = preference;
+	}
+}
+
+impl SystemThemeSource for MockSystemTheme
+{
+	fn preference(&self) -> SystemThemePreference
+	{
+		*self.0.lock().unwrap()
+	}
+}
+
+/// The best source available with the enabled features.
+pub fn default_system_theme_source() -> Arc<dyn SystemThemeSource>
+{
+	#[cfg(feature = "dark-light")]
+	{ Arc::new(DarkLightSource) }
+	#[cfg(not(feature = "dark-light"))]
+	{ Arc::new(NoSystemTheme) }
+}
+
+/// Swaps the current theme between `light` and `dark` whenever the system preference changes.
+#[derive(Clone)]
+pub struct FollowSystemTheme
+{
+	pub light: ThemeData,
+	pub dark: ThemeData,
+	pub source: Arc<dyn SystemThemeSource>,
+	pub poll_interval: Duration,
+}
+
+impl FollowSystemTheme
+{
+	pub fn new(light: ThemeData, dark: ThemeData) -> Self
+	{
+		Self
+		{
+			light,
+			dark,
+			source: default_system_theme_source(),
+			poll_interval: Duration::from_secs(1),
+		}
+	}
+
+	pub fn with_source(mut self, source: impl SystemThemeSource) -> Self
+	{
+		self.source = Arc::new(source);
+		self
+	}
+
+	pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self
+	{
+		self.poll_interval = poll_interval;
+		self
+	}
+
+	/// The theme to use for a preference, or None if the current theme should be kept.
+	pub fn theme_for(&self, preference: SystemThemePreference) -> Option<&ThemeData>
+	{
+		match preference
+		{
+			SystemThemePreference::Light => Some(&self.light),
+			SystemThemePreference::Dark => Some(&self.dark),
+			SystemThemePreference::Unknown => None,
+		}
+	}
+}
+
+impl Default for FollowSystemTheme
+{
+	fn default() -> Self
+	{
+		Self::new(super::themes::LIGHT.clone(), super::themes::DARK.clone())
+	}
+}
+
+#[derive(Resource)]
+pub struct SystemThemeFollower<U>
+{
+	pub settings: FollowSystemTheme,
+	pub last_preference: SystemThemePreference,
+	pub timer: Timer,
+	/// The query in flight, if any.
+	pub query: Option<SystemThemeQuery>,
+	pub phantom: PhantomData<U>,
+}
+
+impl<U> SystemThemeFollower<U>
+{
+	/// Starts querying the source straight away, so the first theme is picked as soon as possible.
+	pub fn new(settings: FollowSystemTheme) -> Self
+	{
+		let timer = Timer::new(settings.poll_interval, TimerMode::Repeating);
+		let query = Some(SystemThemeQuery::spawn(&settings.source));
+		Self { settings, last_preference: SystemThemePreference::Unknown, timer, query, phantom: PhantomData }
+	}
+}
+
+/// Reads a source's preference on the async compute task pool.
+/// Without Bevy's multi_threaded feature the pool runs the query before `spawn` returns (or, on wasm, on the next turn of the event loop).
+pub struct SystemThemeQuery(Mutex<mpsc::Receiver<SystemThemePreference>>);
+
+impl SystemThemeQuery
+{
+	pub fn spawn(source: &Arc<dyn SystemThemeSource>) -> Self
+	{
+		let (sender, receiver) = mpsc::channel();
+		let source = source.clone();
+		AsyncComputeTaskPool::get_or_init(TaskPool::default)
+			.spawn(async move { let _ = sender.send(source.preference()); })
+			.detach();
+		Self(Mutex::new(receiver))
+	}
+
+	/// The preference, once the query has finished. A source that panicked has no preference.
+	pub fn try_get(&self) -> Option<SystemThemePreference>
+	{
+		match self.0.lock().unwrap().try_recv()
+		{
+			Ok(preference) => Some(preference),
+			Err(mpsc::TryRecvError::Disconnected) => Some(SystemThemePreference::Unknown),
+			Err(mpsc::TryRecvError::Empty) => None,
+		}
+	}
+}
+
+/// Starts a query every poll interval (unless one is still running), and applies the results of finished ones.
+pub fn follow_system_theme<U: Component>
+(
+	time: Res<Time>,
+	mut follower: ResMut<SystemThemeFollower<U>>,
+	mut theme_data: ResMut<CurrentThemeData<U>>,
+)
+{
+	if follower.timer.tick(time.delta()).just_finished() && follower.query.is_none()
+		{ follower.query = Some(SystemThemeQuery::spawn(&follower.settings.source)); }
+	let Some(preference) = follower.query.as_ref().and_then(SystemThemeQuery::try_get)
+		else { return; };
+	follower.query = None;
+	if preference == follower.last_preference
+		{ return; }
+	follower.last_preference = preference;
+	if let Some(theme) = follower.settings.theme_for(preference)
+	{
+		theme_data.0 = theme.clone();
+	}
+}
+
+/// Picks the first theme from the system preference, for apps that don't set a theme or follow the system.
+/// The query doesn't hold up startup, the plugin's fallback theme is used until it answers.
+#[derive(Resource)]
+pub struct SystemThemeDetection<U>
+{
+	pub query: SystemThemeQuery,
+	pub phantom: PhantomData<U>,
+}
+
+impl<U> SystemThemeDetection<U>
+{
+	pub fn new(source: &Arc<dyn SystemThemeSource>) -> Self
+	{
+		Self { query: SystemThemeQuery::spawn(source), phantom: PhantomData }
+	}
+}
+
+/// Applies the detected theme once the query answers (keeping the fallback if the system has no preference), then stops.
+pub fn apply_detected_system_theme<U: Component>
+(
+	mut commands: Commands,
+	detection: Res<SystemThemeDetection<U>>,
+	mut theme_data: ResMut<CurrentThemeData<U>>,
+)
+{
+	let Some(preference) = detection.query.try_get()
+		else { return; };
+	commands.remove_resource::<SystemThemeDetection<U>>();
+	match preference
+	{
+		SystemThemePreference::Light => theme_data.0 = super::themes::LIGHT.clone(),
+		SystemThemePreference::Dark => theme_data.0 = super::themes::DARK.clone(),
+		SystemThemePreference::Unknown => (),
+	}
+}
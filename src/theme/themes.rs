@@ -4,74 +4,114 @@ use super::*;
 
 pub static GRAY : Lazy<Color> = Lazy::new(|| bevy::color::palettes::basic::GRAY.into());
 pub static RED : Lazy<Color> = Lazy::new(|| bevy::color::palettes::basic::RED.into());
+pub static GREEN : Lazy<Color> = Lazy::new(|| Color::srgb(0.18, 0.55, 0.3));
+pub static AMBER : Lazy<Color> = Lazy::new(|| Color::srgb(0.85, 0.6, 0.1));
 
 pub static LIGHT: Lazy<ThemeData> = Lazy::new
 (
 	||
-	ThemeData
 	{
-		base: Color::WHITE.darken(0.33),
-		base_foreground: Color::BLACK,
-		base_container: Color::WHITE,
-		base_container_foreground: Color::BLACK,
+		let base = Color::WHITE.tone(85.0);
+		let primary_container = colours::ENGLISH_VIOLET.tone(55.0);
+		let secondary_container = colours::RAISIN_BLACK.tone(55.0);
+		let tertiary_container = colours::WALNUT_BROWN.tone(55.0);
+		let disabled = GRAY.tone(55.0);
+		let destructive = RED.tone(45.0);
+		let success = GREEN.tone(45.0);
+		let warning = AMBER.tone(75.0);
+		ThemeData
+		{
+			base,
+			base_foreground: base.on_colour(),
+			base_container: Color::WHITE,
+			base_container_foreground: Color::BLACK,
 
-		primary: colours::ENGLISH_VIOLET.lighten(0.50),
-		primary_foreground: *colours::ENGLISH_VIOLET,
-		primary_container: colours::ENGLISH_VIOLET.lighten(0.33),
-		primary_container_foreground: colours::ENGLISH_VIOLET.lighten(0.9),
+			primary: colours::ENGLISH_VIOLET.tone(85.0),
+			primary_foreground: colours::ENGLISH_VIOLET.tone(25.0),
+			primary_container,
+			primary_container_foreground: primary_container.on_colour(),
 
-		secondary: colours::RAISIN_BLACK.lighten(0.50),
-		secondary_foreground: *colours::RAISIN_BLACK,
-		secondary_container: colours::RAISIN_BLACK.lighten(0.33),
-		secondary_container_foreground: colours::RAISIN_BLACK.lighten(0.9),
+			secondary: colours::RAISIN_BLACK.tone(85.0),
+			secondary_foreground: colours::RAISIN_BLACK.tone(25.0),
+			secondary_container,
+			secondary_container_foreground: secondary_container.on_colour(),
 
-		tertiary: colours::WALNUT_BROWN.lighten(0.50),
-		tertiary_foreground: *colours::WALNUT_BROWN,
-		tertiary_container: colours::WALNUT_BROWN.lighten(0.20),
-		tertiary_container_foreground: colours::WALNUT_BROWN.lighten(0.9),
+			tertiary: colours::WALNUT_BROWN.tone(85.0),
+			tertiary_foreground: colours::WALNUT_BROWN.tone(25.0),
+			tertiary_container,
+			tertiary_container_foreground: tertiary_container.on_colour(),
 
-		disabled: GRAY.darken(0.33),
-		disabled_foreground: Color::WHITE,
+			disabled,
+			disabled_foreground: disabled.on_colour(),
 
-		destructive: RED.darken(0.33),
-		destructive_foreground: Color::WHITE,
+			destructive,
+			destructive_foreground: destructive.on_colour(),
 
-		default_font: None,
+			success,
+			success_foreground: success.on_colour(),
+
+			warning,
+			warning_foreground: warning.on_colour(),
+
+			default_font: None,
+			fonts: HashMap::new(),
+
+			transition: ColourTransition::default(),
+		}
 	}
 );
 
 pub static DARK: Lazy<ThemeData> = Lazy::new
 (
 	||
-	ThemeData
 	{
-		base: Color::BLACK.lighten(0.33),
-		base_foreground: Color::WHITE,
-		base_container: Color::BLACK,
-		base_container_foreground: Color::WHITE,
+		let base = Color::BLACK.tone(30.0);
+		let primary = colours::ENGLISH_VIOLET.darken(0.20);
+		let secondary = *colours::RAISIN_BLACK;
+		let tertiary = colours::WALNUT_BROWN.darken(0.33);
+		let disabled = GRAY.tone(40.0);
+		let destructive = RED.tone(45.0);
+		let success = GREEN.tone(45.0);
+		let warning = AMBER.tone(75.0);
+		ThemeData
+		{
+			base,
+			base_foreground: base.on_colour(),
+			base_container: Color::BLACK,
+			base_container_foreground: Color::WHITE,
 
-		primary: colours::ENGLISH_VIOLET.darken(0.20),
-		primary_foreground: colours::ENGLISH_VIOLET.lighten(0.75),
-		primary_container: *colours::ENGLISH_VIOLET,
-		primary_container_foreground: colours::ENGLISH_VIOLET.lighten(0.9),
+			primary,
+			primary_foreground: colours::ENGLISH_VIOLET.tone(88.0),
+			primary_container: *colours::ENGLISH_VIOLET,
+			primary_container_foreground: colours::ENGLISH_VIOLET.on_colour(),
 
-		secondary: *colours::RAISIN_BLACK,
-		secondary_foreground: colours::RAISIN_BLACK.lighten(0.75),
-		secondary_container: colours::RAISIN_BLACK.lighten(0.33),
-		secondary_container_foreground: colours::RAISIN_BLACK.lighten(0.9),
+			secondary,
+			secondary_foreground: colours::RAISIN_BLACK.tone(88.0),
+			secondary_container: colours::RAISIN_BLACK.lighten(0.33),
+			secondary_container_foreground: colours::RAISIN_BLACK.lighten(0.33).on_colour(),
 
-		tertiary: colours::WALNUT_BROWN.darken(0.33),
-		tertiary_foreground: colours::WALNUT_BROWN.lighten(0.75),
-		tertiary_container: *colours::WALNUT_BROWN,
-		tertiary_container_foreground: colours::WALNUT_BROWN.lighten(0.9),
+			tertiary,
+			tertiary_foreground: colours::WALNUT_BROWN.tone(88.0),
+			tertiary_container: *colours::WALNUT_BROWN,
+			tertiary_container_foreground: colours::WALNUT_BROWN.on_colour(),
 
-		disabled: GRAY.darken(0.33),
-		disabled_foreground: Color::WHITE,
+			disabled,
+			disabled_foreground: disabled.on_colour(),
 
-		destructive: RED.darken(0.33),
-		destructive_foreground: Color::WHITE,
+			destructive,
+			destructive_foreground: destructive.on_colour(),
 
-		default_font: None,
+			success,
+			success_foreground: success.on_colour(),
+
+			warning,
+			warning_foreground: warning.on_colour(),
+
+			default_font: None,
+			fonts: HashMap::new(),
+
+			transition: ColourTransition::default(),
+		}
 	}
 );
 
@@ -82,46 +122,115 @@ pub static TRANSPARENT: Lazy<ThemeData> = Lazy::new
 	||
 	ThemeData
 	{
-		base: Color::BLACK.lighten(0.33),
-		base_foreground: Color::WHITE,
 		base_container: Color::BLACK.with_alpha(0.0),
+		primary_container: DARK.primary_container.with_alpha(DEFAULT_TRANSPARENT_OPACITY),
+		secondary_container: DARK.secondary_container.with_alpha(DEFAULT_TRANSPARENT_OPACITY),
+		tertiary_container: DARK.tertiary_container.with_alpha(DEFAULT_TRANSPARENT_OPACITY),
+		..DARK.clone()
+	}
+);
+
+
+/// Targets `CONTRAST_ENHANCED_RATIO` (7:1) for every role, using pure white text on deep tones.
+pub static HIGH_CONTRAST_DARK: Lazy<ThemeData> = Lazy::new
+(
+	||
+	ThemeData
+	{
+		base: Color::BLACK,
+		base_foreground: Color::WHITE,
+		base_container: Color::BLACK.tone(12.0),
 		base_container_foreground: Color::WHITE,
 
-		primary: colours::ENGLISH_VIOLET.darken(0.20),
-		primary_foreground: colours::ENGLISH_VIOLET.lighten(0.75),
-		primary_container: colours::ENGLISH_VIOLET.with_alpha(DEFAULT_TRANSPARENT_OPACITY),
-		primary_container_foreground: colours::ENGLISH_VIOLET.lighten(0.9),
+		primary: colours::ENGLISH_VIOLET.tone(20.0),
+		primary_foreground: Color::WHITE,
+		primary_container: colours::ENGLISH_VIOLET.tone(32.0),
+		primary_container_foreground: Color::WHITE,
 
-		secondary: *colours::RAISIN_BLACK,
-		secondary_foreground: colours::RAISIN_BLACK.lighten(0.75),
-		secondary_container: colours::RAISIN_BLACK.lighten(0.33).with_alpha(DEFAULT_TRANSPARENT_OPACITY),
-		secondary_container_foreground: colours::RAISIN_BLACK.lighten(0.9),
+		secondary: colours::RAISIN_BLACK.tone(20.0),
+		secondary_foreground: Color::WHITE,
+		secondary_container: colours::RAISIN_BLACK.tone(32.0),
+		secondary_container_foreground: Color::WHITE,
 
-		tertiary: colours::WALNUT_BROWN.darken(0.33),
-		tertiary_foreground: colours::WALNUT_BROWN.lighten(0.75),
-		tertiary_container: colours::WALNUT_BROWN.with_alpha(DEFAULT_TRANSPARENT_OPACITY),
-		tertiary_container_foreground: colours::WALNUT_BROWN.lighten(0.9),
+		tertiary: colours::WALNUT_BROWN.tone(20.0),
+		tertiary_foreground: Color::WHITE,
+		tertiary_container: colours::WALNUT_BROWN.tone(32.0),
+		tertiary_container_foreground: Color::WHITE,
 
-		disabled: GRAY.darken(0.33),
+		disabled: GRAY.tone(32.0),
 		disabled_foreground: Color::WHITE,
 
-		destructive: RED.darken(0.33),
+		destructive: RED.tone(36.0),
 		destructive_foreground: Color::WHITE,
 
+		success: GREEN.tone(36.0),
+		success_foreground: Color::WHITE,
+
+		warning: AMBER.tone(84.0),
+		warning_foreground: Color::BLACK,
+
 		default_font: None,
+		fonts: HashMap::new(),
+
+		transition: ColourTransition::default(),
 	}
 );
 
+/// Targets `CONTRAST_ENHANCED_RATIO` (7:1) for every role, using pure black text on pale tones.
+pub static HIGH_CONTRAST_LIGHT: Lazy<ThemeData> = Lazy::new
+(
+	||
+	ThemeData
+	{
+		base: Color::WHITE,
+		base_foreground: Color::BLACK,
+		base_container: Color::WHITE.tone(94.0),
+		base_container_foreground: Color::BLACK,
+
+		primary: colours::ENGLISH_VIOLET.tone(94.0),
+		primary_foreground: Color::BLACK,
+		primary_container: colours::ENGLISH_VIOLET.tone(84.0),
+		primary_container_foreground: Color::BLACK,
+
+		secondary: colours::RAISIN_BLACK.tone(94.0),
+		secondary_foreground: Color::BLACK,
+		secondary_container: colours::RAISIN_BLACK.tone(84.0),
+		secondary_container_foreground: Color::BLACK,
+
+		tertiary: colours::WALNUT_BROWN.tone(94.0),
+		tertiary_foreground: Color::BLACK,
+		tertiary_container: colours::WALNUT_BROWN.tone(84.0),
+		tertiary_container_foreground: Color::BLACK,
+
+		disabled: GRAY.tone(84.0),
+		disabled_foreground: Color::BLACK,
+
+		destructive: RED.tone(36.0),
+		destructive_foreground: Color::WHITE,
+
+		success: GREEN.tone(36.0),
+		success_foreground: Color::WHITE,
+
+		warning: AMBER.tone(84.0),
+		warning_foreground: Color::BLACK,
+
+		default_font: None,
+		fonts: HashMap::new(),
+
+		transition: ColourTransition::default(),
+	}
+);
 
+/// Asks the default system theme source, blocking until it answers. UIBuilderPlugin detects the theme without blocking instead.
 impl Default for ThemeData
 {
 	fn default() -> Self
 	{
-		match dark_light::detect()
+		match default_system_theme_source().preference()
 		{
-			dark_light::Mode::Dark => themes::DARK.clone(),
-			dark_light::Mode::Light => themes::LIGHT.clone(),
-			dark_light::Mode::Default => themes::DARK.clone()
+			SystemThemePreference::Dark => themes::DARK.clone(),
+			SystemThemePreference::Light => themes::LIGHT.clone(),
+			SystemThemePreference::Unknown => themes::DARK.clone()
 		}
 	}
 }
@@ -138,4 +247,33 @@ mod tests
 		// Is it appropriate to test the transparent theme?
 		// themes::TRANSPARENT.is_accessible();
 	}
+
+	#[test]
+	fn high_contrast_themes_meet_enhanced_contrast()
+	{
+		use super::*;
+		assert!(themes::HIGH_CONTRAST_DARK.meets_contrast(CONTRAST_ENHANCED_RATIO));
+		assert!(themes::HIGH_CONTRAST_LIGHT.meets_contrast(CONTRAST_ENHANCED_RATIO));
+	}
+
+	#[test]
+	fn simulated_themes_stay_distinguishable()
+	{
+		use super::*;
+		// 3:1 is the WCAG minimum for large text and UI components, which should survive any colour vision deficiency.
+		for theme in [&*themes::DARK, &*themes::LIGHT]
+		{
+			for cvd in Cvd::ALL
+			{
+				assert!(theme.simulate(cvd).meets_contrast(3.0), "Theme role pairs are indistinguishable with {cvd:?}");
+			}
+		}
+		for theme in [&*themes::HIGH_CONTRAST_DARK, &*themes::HIGH_CONTRAST_LIGHT]
+		{
+			for cvd in Cvd::ALL
+			{
+				assert!(theme.simulate(cvd).meets_contrast(CONTRAST_ENHANCED_RATIO), "High contrast theme fails with {cvd:?}");
+			}
+		}
+	}
 }
@@ -0,0 +1,189 @@
+// Colour tweening. Widgets don't write their colours directly, they write a ColourTarget,
+// and the colours are interpolated towards it in Oklab.
+
+use bevy::prelude::*;
+
+use super::{CurrentThemeData, ShiftColour};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing
+{
+	Linear,
+	EaseIn,
+	#[default]
+	EaseOut,
+	EaseInOut,
+}
+
+impl Easing
+{
+	/// Maps linear progress (0.0..=1.0) to eased progress, using cubic curves.
+	pub fn apply(&self, t: f32) -> f32
+	{
+		let t = t.clamp(0.0, 1.0);
+		match self
+		{
+			Easing::Linear => t,
+			Easing::EaseIn => t * t * t,
+			Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+			Easing::EaseInOut =>
+				if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 },
+		}
+	}
+}
+
+/// How long colour changes take, and how they are eased.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColourTransition
+{
+	/// In seconds. Zero snaps to the target colour.
+	pub duration: f32,
+	pub easing: Easing,
+}
+
+impl ColourTransition
+{
+	pub const INSTANT: ColourTransition = ColourTransition { duration: 0.0, easing: Easing::Linear };
+}
+
+impl Default for ColourTransition
+{
+	fn default() -> Self
+	{
+		Self { duration: 0.15, easing: Easing::EaseOut }
+	}
+}
+
+/// The colours a widget should end up with. Colours that are None are left alone.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ColourTarget
+{
+	pub background: Option<Color>,
+	pub border: Option<Color>,
+	pub text: Option<Color>,
+	/// The tint of a UiImage, for images that follow the theme (see ImageTint).
+	pub image: Option<Color>,
+}
+
+/// The progress of the tween towards a ColourTarget. This is managed by `start_colour_tweens` and `animate_colours`.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ColourTween
+{
+	pub from: ColourTarget,
+	pub elapsed: f32,
+	pub finished: bool,
+}
+
+impl Default for ColourTween
+{
+	fn default() -> Self
+	{
+		Self { from: ColourTarget::default(), elapsed: 0.0, finished: true }
+	}
+}
+
+impl ColourTween
+{
+	/// How far along the tween is, from 0.0 to 1.0. Instant transitions are always finished.
+	pub fn progress(&self, transition: &ColourTransition) -> f32
+	{
+		if transition.duration <= 0.0 { 1.0 } else { (self.elapsed / transition.duration).min(1.0) }
+	}
+
+	/// The colour between `from` and `to` at the tween's current progress, eased.
+	pub fn step(&self, transition: &ColourTransition, from: Option<Color>, to: Color) -> Color
+	{
+		let progress = self.progress(transition);
+		if progress >= 1.0 || from == Some(to) { to } else { from.unwrap_or(to).mix(to, transition.easing.apply(progress)) }
+	}
+}
+
+/// Restarts the tween of every widget whose target changed, starting from the colours it currently has.
+pub fn start_colour_tweens<U: Component>
+(
+	mut query: Query<(&mut ColourTween, Option<&BackgroundColor>, Option<&BorderColor>, Option<&Text>, Option<&UiImage>), (Changed<ColourTarget>, With<U>)>,
+)
+{
+	for (mut tween, background_colour, border_colour, text, ui_image) in query.iter_mut()
+	{
+		*tween = ColourTween
+		{
+			from: ColourTarget
+			{
+				background: background_colour.map(|colour| colour.0),
+				border: border_colour.map(|colour| colour.0),
+				text: text.and_then(|text| text.sections.first()).map(|section| section.style.color),
+				image: ui_image.map(|ui_image| ui_image.color),
+			},
+			elapsed: 0.0,
+			finished: false,
+		};
+	}
+}
+
+pub fn animate_colours<U: Component>
+(
+	time: Res<Time>,
+	theme_data: Res<CurrentThemeData<U>>,
+	mut query: Query<(&ColourTarget, &mut ColourTween, Option<&mut BackgroundColor>, Option<&mut BorderColor>, Option<&mut Text>, Option<&mut UiImage>), With<U>>,
+)
+{
+	let transition = theme_data.0.transition;
+	for (target, mut tween, background_colour, border_colour, text, ui_image) in query.iter_mut()
+	{
+		if tween.finished
+			{ continue; }
+		tween.elapsed += time.delta_seconds();
+		let step = |from: Option<Color>, to: Color| tween.step(&transition, from, to);
+
+		if let (Some(to), Some(mut background_colour)) = (target.background, background_colour)
+			{ background_colour.0 = step(tween.from.background, to); }
+		if let (Some(to), Some(mut border_colour)) = (target.border, border_colour)
+			{ border_colour.0 = step(tween.from.border, to); }
+		if let (Some(to), Some(mut text)) = (target.text, text)
+		{
+			let colour = step(tween.from.text, to);
+			for section in text.sections.iter_mut()
+			{
+				section.style.color = colour;
+			}
+		}
+		if let (Some(to), Some(mut ui_image)) = (target.image, ui_image)
+			{ ui_image.color = step(tween.from.image, to); }
+		tween.finished = tween.progress(&transition) >= 1.0;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::{marker::PhantomData, time::Duration};
+	use super::*;
+	use crate::test::TestUI;
+
+	#[test]
+	fn colours_tween_towards_target()
+	{
+		let mut app = App::new();
+		app
+			.init_resource::<Time>()
+			.insert_resource(CurrentThemeData::<TestUI>(crate::theme::DARK.clone(), PhantomData))
+			.add_systems(Update, (start_colour_tweens::<TestUI>, animate_colours::<TestUI>).chain());
+		let entity = app.world_mut()
+			.spawn((TestUI, BackgroundColor(Color::BLACK), ColourTween::default()))
+			.id();
+		app.update();
+		app.world_mut().entity_mut(entity).insert(ColourTarget { background: Some(Color::WHITE), ..Default::default() });
+
+		let duration = crate::theme::DARK.transition.duration;
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(duration / 2.0));
+		app.update();
+		let halfway = app.world().get::<BackgroundColor>(entity).unwrap().0.to_srgba();
+		assert!(halfway.red > 0.0 && halfway.red < 1.0, "Colour should be between black and white, got {halfway:?}");
+
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(duration));
+		app.update();
+		assert_eq!(app.world().get::<BackgroundColor>(entity).unwrap().0, Color::WHITE);
+		assert!(app.world().get::<ColourTween>(entity).unwrap().finished);
+	}
+}
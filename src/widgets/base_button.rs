@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use bevy_alt_ui_navigation_lite::prelude::*;
 
 use super::*;
-use crate::{theme::{ThemeData, ThemeApplicator, CurrentTheme, ShiftColour}, prelude::CurrentThemeData};
+use crate::{theme::{ThemeData, ThemeApplicator, CurrentTheme, ThemePaint, ShiftColour, ColourTarget, ColourTween}, prelude::CurrentThemeData};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Component)]
 /// Indicates that this button should have effects applied to it when hovered over or pressed.
@@ -17,16 +17,15 @@ pub struct ActiveImage(pub UiImage);
 pub fn style_button_on_focus<U: Component + Default>
 
 (
-	mut button_query: Query<(&mut BackgroundColor, &CurrentTheme<U>, &Focusable, &mut UiImage, Option<&FocusedImage>, Option<&ActiveImage>), (With<AutoStyledButton>, Changed<Focusable>)>,
+	mut button_query: Query<(&mut ColourTarget, &ThemePaint<U>, &Focusable, &mut UiImage, Option<&FocusedImage>, Option<&ActiveImage>), (With<AutoStyledButton>, Changed<Focusable>)>,
 	theme_data: Res<CurrentThemeData<U>>,
 )
 {
 	let theme_data = &theme_data.0;
-	for (mut background_colour, current_theme, focus, mut ui_image, focused_image, active_image) in button_query.iter_mut()
+	for (mut colour_target, paint, focus, mut ui_image, focused_image, active_image) in button_query.iter_mut()
 
 	{
-		let current_theme = current_theme.0;
-		let current_background_colour = current_theme.get_background(&theme_data);
+		let current_background_colour = paint.paint_mode.get_background(paint.theme, theme_data);
 		match focus.state()
 		{
 			FocusState::Focused =>
@@ -37,7 +36,7 @@ pub fn style_button_on_focus<U: Component + Default>
 				}
 				else
 				{
-					*background_colour = current_background_colour.lighten(0.1).into();
+					colour_target.background = Some(current_background_colour.lighten(0.1));
 				}
 			},
 			FocusState::Active =>
@@ -48,29 +47,59 @@ pub fn style_button_on_focus<U: Component + Default>
 				}
 				else
 				{
-					*background_colour = current_background_colour.lighten(0.25).into();
+					colour_target.background = Some(current_background_colour.lighten(0.25));
 				}
 			},
-			_ => *background_colour = current_background_colour.into(),
+			_ => colour_target.background = Some(current_background_colour),
 		}
 	}
 }
 
+/// The background an auto styled button painted with `background` should have, the same colour style_button_on_focus and style_button_on_pressed give it.
+/// Buttons with focused/active images show those instead of changing colour.
+pub fn button_state_background(background: Color, focus: FocusState, interaction: Interaction, has_focused_image: bool, has_active_image: bool) -> Color
+{
+	match (interaction, focus)
+	{
+		(Interaction::Pressed, _) => background.darken(0.1),
+		(_, FocusState::Focused) if !has_focused_image => background.lighten(0.1),
+		(_, FocusState::Active) if !has_active_image => background.lighten(0.25),
+		_ => background,
+	}
+}
+
+/// Keeps the focused or pressed shade of auto styled buttons when repaint_on_theme_change repaints them.
+pub fn shade_repainted_buttons<U: Component>
+(
+	theme_data: Res<CurrentThemeData<U>>,
+	mut button_query: Query<(Ref<ThemePaint<U>>, &mut ColourTarget, &Focusable, &Interaction, Has<FocusedImage>, Has<ActiveImage>), With<AutoStyledButton>>,
+)
+{
+	for (paint, mut colour_target, focus, interaction, has_focused_image, has_active_image) in button_query.iter_mut()
+	{
+		if !crate::theme::needs_repaint(&theme_data, &paint)
+			{ continue; }
+		let background = paint.paint_mode.get_background(paint.theme, &theme_data.0);
+		let background = Some(button_state_background(background, focus.state(), *interaction, has_focused_image, has_active_image));
+		if colour_target.background != background
+			{ colour_target.background = background; }
+	}
+}
+
 pub fn style_button_on_pressed<U: Component + Default>
 
 (
-	mut button_query: Query<(&mut BackgroundColor, &CurrentTheme<U>, &Interaction, &mut Focusable), (With<AutoStyledButton>, Changed<Interaction>)>,
+	mut button_query: Query<(&mut ColourTarget, &ThemePaint<U>, &Interaction, &mut Focusable), (With<AutoStyledButton>, Changed<Interaction>)>,
 	theme_data: Res<CurrentThemeData<U>>,
 )
 {
 	let theme_data = &theme_data.0;
-	for (mut background_colour, current_theme, interaction, mut focus) in button_query.iter_mut()
+	for (mut colour_target, paint, interaction, mut focus) in button_query.iter_mut()
 	{
-		let current_theme = current_theme.0;
-		let current_background_colour = current_theme.get_background(&theme_data);
+		let current_background_colour = paint.paint_mode.get_background(paint.theme, theme_data);
 		match *interaction
 		{
-			Interaction::Pressed => *background_colour = current_background_colour.darken(0.1).into(),
+			Interaction::Pressed => colour_target.background = Some(current_background_colour.darken(0.1)),
 			_ => *focus = focus.clone(), // Other interactions are handled by style_button_on_focus, so tell it to update.
 		}
 	}
@@ -194,6 +223,7 @@ impl<U: Component + Default, M: Default> super::Widget for BaseButton<U, M>
 	}
 	fn with_colour(mut self, background: Color, foreground: Color) -> Self
 	{
+		crate::theme::warn_if_inaccessible(background, foreground);
 		self.theme = Theme::Custom(background, foreground);
 		self
 	}
@@ -282,24 +312,8 @@ impl<U: Component + Default, M: Default> ThemeApplicator for BaseButton<U, M>
 			_ => self.theme
 		};
 
-		self.button_bundle.background_color = match self.paint_mode
-		{
-			PaintMode::Background =>
-				theme.get_background(theme_data).into(),
-			PaintMode::BackgroundContainer =>
-				theme.get_background_container(theme_data).into(),
-			PaintMode::Invisible =>
-				Color::NONE.into(),
-		};
-		self.button_bundle.border_color = match self.paint_mode
-		{
-			PaintMode::Background =>
-				theme.get_background_container(theme_data).into(),
-			PaintMode::BackgroundContainer =>
-				theme.get_background(theme_data).into(),
-			PaintMode::Invisible =>
-				Color::NONE.into(),
-		};
+		self.button_bundle.background_color = self.paint_mode.get_background(theme, theme_data).into();
+		self.button_bundle.border_color = self.paint_mode.get_border(theme, theme_data).into();
 	}
 }
 
@@ -318,23 +332,29 @@ impl<U: Component + Default + std::any::Any, M: UIOptionalUniqueIdentifier> Widg
 			if parent_data.parent_ui_owner.is_none()
 			{
 				// If the parent UI Owner is None, then we need to add a new node to the tree.
-				ui_tree.new_node(M::default().type_id());
+				ui_tree.new_node(crate::UINode::of::<M>());
 				parent_data.parent_ui_owner = Some(U::default().type_id().into());
 			}
 			let parent_node_typeid = parent_data.parent_ui_owner.unwrap_or(U::default().type_id().into()).0;
 			let parent_node = ui_tree
 				.iter()
 				.filter(|node| !node.is_removed())
-				.find(|node| *node.get() == parent_node_typeid)
+				.find(|node| node.get().type_id == parent_node_typeid)
 				.expect("Parent node not found in the UI Tree.");
 			let parent_node = ui_tree.get_node_id(parent_node).expect("Parent node not found in the UI Tree.");
-			let new_node = ui_tree.new_node(M::default().type_id());
+			let new_node = ui_tree.new_node(crate::UINode::of::<M>());
 			parent_node.append(new_node, &mut ui_tree);
 			// Update the ParentData
 			parent_data.parent_ui_owner = crate::UIOwner(M::default().type_id()).into();
 		}
 		// Apply theming.
 		self.apply_theme(parent_data.resolve_theme(), theme);
+		let resolved_theme = match self.theme
+		{
+			Theme::Auto => parent_data.resolve_theme(),
+			_ => self.theme
+		};
+		let paint = ThemePaint::<U>::new(resolved_theme, self.paint_mode);
 
 		// Build children.
 		let new_parent_data = parent_data.from_current(self.theme);
@@ -349,6 +369,9 @@ impl<U: Component + Default + std::any::Any, M: UIOptionalUniqueIdentifier> Widg
 			.insert(U::default())
 			.insert(AutoStyledButton)
 			.insert(CurrentTheme(self.theme, std::marker::PhantomData::<U>))
+			.insert(paint.node_target(theme))
+			.insert(ColourTween::default())
+			.insert(paint)
 			.insert(Focusable::default())
 			.push_children(&children)
 			;
@@ -401,3 +424,35 @@ impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetB
 		Box::new(self)
 	}
 }
+
+#[cfg(test)]
+mod tests
+{
+	use bevy_alt_ui_navigation_lite::prelude::NavRequest;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	#[test]
+	fn repainted_buttons_keep_their_focus_shade()
+	{
+		fn build_button(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+		{
+			crate::widgets::TextButton::<TestUI>::new("Tab")
+				.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		}
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_button);
+		app.update();
+		let button = app.world_mut().query_filtered::<Entity, With<AutoStyledButton>>().single(app.world());
+		app.world_mut().send_event(NavRequest::FocusOn(button));
+		app.update();
+
+		// Like a tab becoming active as it's focused.
+		app.world_mut().get_mut::<ThemePaint<TestUI>>(button).unwrap().paint_mode = PaintMode::BackgroundContainer;
+		app.update();
+		let paint = app.world().get::<ThemePaint<TestUI>>(button).unwrap();
+		let background = paint.paint_mode.get_background(paint.theme, &app.world().resource::<CurrentThemeData<TestUI>>().0);
+		assert_eq!(app.world().get::<ColourTarget>(button).unwrap().background, Some(background.lighten(0.1)));
+	}
+}
@@ -23,7 +23,7 @@ pub fn toggle_checkbox
 
 pub fn handle_checkbox_toggle
 (
-	query: Query<(&CheckBoxState, Entity), Changed<CheckBoxState>>,
+	query: Query<(&CheckBoxState, Entity), (Changed<CheckBoxState>, Without<SwitchParts>)>, // Switches draw a thumb instead.
 	children_query: Query<&Children>,
 	mut text_query: Query<&mut Text>
 )
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use super::{ParentData, UIOptionalUniqueIdentifier, WidgetBuilder};
-use crate::theme::{Theme, ThemeApplicator, CurrentTheme, PaintMode};
+use crate::theme::{Theme, ThemeApplicator, CurrentTheme, PaintMode, ThemePaint, ColourTween};
 
 // A container is just a NodeBundle with extra steps. You should use other widgets (Column, Row, etc.) instead of this.
 pub struct Container<U, M = ()>
@@ -72,6 +72,7 @@ impl<U: Component + Default, M: UIOptionalUniqueIdentifier> super::Widget for Co
 	}
 	fn with_colour(mut self, background: Color, foreground: Color) -> Self
 	{
+		crate::theme::warn_if_inaccessible(background, foreground);
 		self.theme = Theme::Custom(background, foreground);
 		self
 	}
@@ -158,25 +159,8 @@ impl<U: Component + Default, M: UIOptionalUniqueIdentifier> ThemeApplicator for
 			_ => self.theme
 		};
 
-		match self.paint_mode
-		{
-			PaintMode::Background =>
-				self.node_bundle.background_color = theme.get_background(theme_data).into(),
-			PaintMode::BackgroundContainer =>
-				self.node_bundle.background_color = theme.get_background_container(theme_data).into(),
-			PaintMode::Invisible =>
-				self.node_bundle.background_color = Color::NONE.into(),
-		}
-
-		match self.paint_mode
-		{
-			PaintMode::Background =>
-				self.node_bundle.border_color = theme.get_background_container(theme_data).into(),
-			PaintMode::BackgroundContainer =>
-				self.node_bundle.border_color = theme.get_background(theme_data).into(),
-			PaintMode::Invisible =>
-				self.node_bundle.border_color = Color::NONE.into(),
-		}
+		self.node_bundle.background_color = self.paint_mode.get_background(theme, theme_data).into();
+		self.node_bundle.border_color = self.paint_mode.get_border(theme, theme_data).into();
 	}
 }
 
@@ -194,7 +178,7 @@ impl<U: Component + Default + std::any::Any, M: UIOptionalUniqueIdentifier> Widg
 			if parent_data.parent_ui_owner.is_none()
 			{
 				// If the parent UI Owner is None, then we need to add a new node to the tree.
-				ui_tree.new_node(U::default().type_id());
+				ui_tree.new_node(crate::UINode::of::<U>());
 				parent_data.parent_ui_owner = Some(U::default().type_id().into());
 			}
 
@@ -202,10 +186,10 @@ impl<U: Component + Default + std::any::Any, M: UIOptionalUniqueIdentifier> Widg
 			let parent_node = ui_tree
 				.iter()
 				.filter(|node| !node.is_removed())
-				.find(|node| *node.get() == parent_node_typeid)
+				.find(|node| node.get().type_id == parent_node_typeid)
 				.expect("Parent node not found in the UI Tree.");
 			let parent_node = ui_tree.get_node_id(parent_node).expect("Parent node not found in the UI Tree.");
-			let new_node = ui_tree.new_node(M::default().type_id());
+			let new_node = ui_tree.new_node(crate::UINode::of::<M>());
 			parent_node.append(new_node, &mut ui_tree);
 
 			// Update the ParentData
@@ -213,6 +197,12 @@ impl<U: Component + Default + std::any::Any, M: UIOptionalUniqueIdentifier> Widg
 		}
 
 		self.apply_theme(parent_data.resolve_theme(), theme_data);
+		let resolved_theme = match self.theme
+		{
+			Theme::Auto => parent_data.resolve_theme(),
+			_ => self.theme
+		};
+		let paint = ThemePaint::<U>::new(resolved_theme, self.paint_mode);
 
 		let new_parent_data = parent_data.from_current(self.theme);
 
@@ -247,6 +237,9 @@ impl<U: Component + Default + std::any::Any, M: UIOptionalUniqueIdentifier> Widg
 		this_container
 			.insert(U::default())
 			.insert(CurrentTheme(self.theme, std::marker::PhantomData::<U>))
+			.insert(paint.node_target(theme_data))
+			.insert(ColourTween::default())
+			.insert(paint)
 			.push_children(&children)
 			.id()
 	}
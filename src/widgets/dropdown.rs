@@ -1,30 +1,388 @@
 /*
-	This dropdown widget is going to be a bit more complicated than the others.
-	It will need to use Z indices to make sure that the dropdown is always on top of everything else.
-	It will also need to use a container to hold the dropdown items.
-	The dropdown items will be a list of buttons, and the buttons will need to be able to change the dropdown's value.
-	TODO: The dropdown will also need to be able to close itself when the user clicks outside of it.
+	The dropdown is a TextButton showing the current selection, and a popup list of TextButtons, one per option.
+	The popup is a top-level node (not a child of the button) with a global Z index, so it is always drawn on top of everything else.
+	It is registered as a sub-menu of the button, so keyboard/gamepad navigation stays inside it while it's open.
+	Opening and closing is done by flipping DropdownMenu::open, apply_dropdown_state then shows/hides the popup and moves focus.
 */
 
+use bevy_alt_ui_navigation_lite::prelude::*;
+
 use super::*;
+use crate::theme::ThemeData;
 
 #[derive(Component)]
 pub struct DropdownOption
 {
 	pub value: String,
+	pub index: usize,
+	/// The dropdown button this option belongs to.
+	pub dropdown: Entity,
 }
 
 #[derive(Component)]
 pub struct DropdownSelection
 {
 	pub value: String,
+	pub index: usize,
+}
+
+/// Lives on the dropdown button.
+#[derive(Component)]
+pub struct DropdownMenu
+{
+	pub popup: Entity,
+	pub options: Vec<Entity>,
+	pub open: bool,
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct DropdownSelectionChanged
+{
+	pub dropdown: Entity,
+	pub index: usize,
+	pub value: String,
+}
+
+/// Options start closed, so they must not be reachable by navigation.
+pub fn init_dropdown_options
+(
+	mut query: Query<&mut Focusable, Added<DropdownOption>>,
+)
+{
+	for mut focusable in query.iter_mut()
+	{
+		focusable.block();
+	}
+}
+
+pub fn open_dropdown_on_press
+(
+	mut dropdown_query: Query<(Ref<Interaction>, &mut DropdownMenu)>,
+	option_query: Query<&DropdownOption>,
+	mut nav_events: EventReader<NavEvent>,
+)
+{
+	for (interaction, mut menu) in dropdown_query.iter_mut()
+	{
+		if interaction.is_changed() && *interaction == Interaction::Pressed
+			{ menu.open = !menu.open; }
+	}
+	// Navigation can also move into the options (e.g. activating the button on a gamepad enters its sub-menu).
+	for event in nav_events.read()
+	{
+		let NavEvent::FocusChanged { to, .. } = event
+			else { continue; };
+		let Ok(option) = option_query.get(*to.first())
+			else { continue; };
+		if let Ok((_, mut menu)) = dropdown_query.get_mut(option.dropdown)
+		{
+			if !menu.open
+				{ menu.open = true; }
+		}
+	}
+}
+
+pub fn close_dropdown_on_cancel
+(
+	mut dropdown_query: Query<(&Interaction, &mut DropdownMenu)>,
+	option_query: Query<&Interaction, With<DropdownOption>>,
+	mut nav_requests: EventReader<NavRequest>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mouse_input: Res<ButtonInput<MouseButton>>,
+)
+{
+	let cancelled = keyboard_input.just_pressed(KeyCode::Escape)
+		|| nav_requests.read().any(|request| *request == NavRequest::Cancel);
+	let clicked = mouse_input.just_pressed(MouseButton::Left);
+	for (interaction, mut menu) in dropdown_query.iter_mut()
+	{
+		if !menu.open
+			{ continue; }
+		let clicked_outside = clicked
+			&& *interaction == Interaction::None
+			&& menu.options.iter().all(|option| option_query.get(*option).map_or(true, |interaction| *interaction == Interaction::None));
+		if cancelled || clicked_outside
+			{ menu.open = false; }
+	}
+}
+
+pub fn select_dropdown_option
+(
+	option_query: Query<(&Interaction, &DropdownOption), Changed<Interaction>>,
+	mut dropdown_query: Query<(&mut DropdownMenu, &mut DropdownSelection, &Children)>,
+	children_query: Query<&Children>,
+	mut text_query: Query<&mut Text>,
+	mut selection_writer: EventWriter<DropdownSelectionChanged>,
+)
+{
+	for (interaction, option) in option_query.iter()
+	{
+		if *interaction != Interaction::Pressed
+			{ continue; }
+		let Ok((mut menu, mut selection, children)) = dropdown_query.get_mut(option.dropdown)
+			else { continue; };
+		menu.open = false;
+		if selection.index == option.index
+			{ continue; }
+		selection.index = option.index;
+		selection.value = option.value.clone();
+
+		// The hierarchy is: BaseButton -> TextLabel Container -> TextBundle.
+		if let Ok(label) = children_query.get(children[0])
+		{
+			if let Ok(mut text) = text_query.get_mut(label[0])
+				{ text.sections[0].value = option.value.clone(); }
+		}
+
+		selection_writer.send(DropdownSelectionChanged
+		{
+			dropdown: option.dropdown,
+			index: option.index,
+			value: option.value.clone(),
+		});
+	}
+}
+
+pub fn apply_dropdown_state
+(
+	dropdown_query: Query<(Entity, Ref<DropdownMenu>, &DropdownSelection)>,
+	mut style_query: Query<&mut Style>,
+	mut focusable_query: Query<&mut Focusable>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	for (entity, menu, selection) in dropdown_query.iter()
+	{
+		if !menu.is_changed() || menu.is_added()
+			{ continue; }
+		if let Ok(mut style) = style_query.get_mut(menu.popup)
+		{
+			style.display = if menu.open { Display::Flex } else { Display::None };
+		}
+		let focus_was_inside = menu.options.iter().any(|option| focusable_query.get(*option).is_ok_and(|focusable| focusable.state() == FocusState::Focused));
+		for option in menu.options.iter()
+		{
+			let Ok(mut focusable) = focusable_query.get_mut(*option)
+				else { continue; };
+			if menu.open
+				{ focusable.unblock(); }
+			else
+				{ focusable.block(); }
+		}
+		if menu.open
+		{
+			if let Some(option) = menu.options.get(selection.index)
+				{ nav_writer.send(NavRequest::FocusOn(*option)); }
+		}
+		else if focus_was_inside
+		{
+			nav_writer.send(NavRequest::FocusOn(entity));
+		}
+	}
+}
+
+/// Keeps open popups directly below their button (or above it, if there is no room below).
+pub fn position_dropdown_popups
+(
+	dropdown_query: Query<(&DropdownMenu, &Node, &GlobalTransform)>,
+	mut style_query: Query<&mut Style>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+)
+{
+	let window_height = window_query.get_single().map(|window| window.height()).ok();
+	for (menu, node, transform) in dropdown_query.iter()
+	{
+		if !menu.open
+			{ continue; }
+		let Ok(mut style) = style_query.get_mut(menu.popup)
+			else { continue; };
+		let rect = node.logical_rect(transform);
+		let height = rect.height() * menu.options.len() as f32;
+		let top = match window_height
+		{
+			Some(window_height) if rect.max.y + height > window_height => (rect.min.y - height).max(0.0),
+			_ => rect.max.y,
+		};
+		style.left = Val::Px(rect.min.x);
+		style.top = Val::Px(top);
+		style.width = Val::Px(rect.width());
+		style.height = Val::Px(height);
+	}
 }
 
-pub struct Dropdown<U, M>
+pub struct Dropdown<U, M = ()>
 	where U: Component + Default, M: UIOptionalUniqueIdentifier
 {
 	/// The button that opens the dropdown and displays the current selection.
 	pub dropdown_base: TextButton<U, M>,
 	pub options: Vec<String>,
-	pub selection: String,
+	/// The index of the initially selected option.
+	pub selected: usize,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Dropdown<U, M>
+{
+	pub fn new(options: impl IntoIterator<Item = impl Into<String>>) -> Self
+	{
+		let options: Vec<String> = options.into_iter().map(Into::into).collect();
+		Self
+		{
+			dropdown_base: TextButton::new(options.first().cloned().unwrap_or_default()),
+			options,
+			selected: 0,
+		}
+	}
+
+	pub fn with_selected(mut self, selected: usize) -> Self
+		{ self.selected = selected; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Dropdown<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.dropdown_base = self.dropdown_base.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Dropdown<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let selected = self.selected.min(self.options.len().saturating_sub(1));
+		let selected_value = self.options.get(selected).cloned().unwrap_or_default();
+		self.dropdown_base.label.label.text.sections[0].value = selected_value.clone();
+
+		// Build the button.
+		let button_entity = self.dropdown_base.build(ui_tree, theme_data, parent_data, commands);
+
+		// Build the options, themed as if they were inside the button.
+		let option_parent_data = parent_data.from_current(self.dropdown_base.base_button.theme);
+		let option_height = Val::Percent(100.0 / self.options.len().max(1) as f32);
+		let options: Vec<Entity> = self.options
+			.iter()
+			.enumerate()
+			.map(|(index, value)|
+			{
+				let mut option = TextButton::<U, ()>::new(value.clone());
+				option.base_button.button_bundle.style.height = option_height;
+				let option_entity = option.build(ui_tree, theme_data, option_parent_data, commands);
+				commands.entity(option_entity)
+					.insert(DropdownOption { value: value.clone(), index, dropdown: button_entity });
+				option_entity
+			})
+			.collect();
+
+		// Build the popup as a top-level node.
+		let mut popup = Container::<U, ()>::new()
+			.with_direction(FlexDirection::Column)
+			.with_theme(self.dropdown_base.base_button.theme);
+		popup.node_bundle.style.position_type = PositionType::Absolute;
+		popup.node_bundle.style.display = Display::None;
+		popup.node_bundle.z_index = ZIndex::Global(OVERLAY_Z_INDEX);
+		for option in options.iter()
+		{
+			popup = popup.push(*option);
+		}
+		let popup_entity = popup.build(ui_tree, theme_data, parent_data, commands);
+
+		commands.entity(popup_entity)
+			.insert(MenuSetting::new().wrapping())
+			.insert(MenuBuilder::EntityParent(button_entity));
+		commands.entity(button_entity)
+			.insert(DropdownSelection { value: selected_value, index: selected })
+			.insert(DropdownMenu { popup: popup_entity, options, open: false });
+		button_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Dropdown<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_dropdown(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Dropdown::<TestUI>::new(["Low", "Medium", "High"])
+			.with_selected(1)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn selecting_an_option_updates_the_selection()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_dropdown);
+		app.update();
+		let (dropdown, popup, options) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &DropdownMenu)>();
+			let (entity, menu) = query.single(app.world());
+			(entity, menu.popup, menu.options.clone())
+		};
+		assert_eq!(options.len(), 3);
+		assert_eq!(app.world().get::<Style>(popup).unwrap().display, Display::None);
+		assert_eq!(app.world().get::<ZIndex>(popup), Some(&ZIndex::Global(OVERLAY_Z_INDEX)));
+
+		// Open it.
+		*app.world_mut().get_mut::<Interaction>(dropdown).unwrap() = Interaction::Pressed;
+		app.update();
+		assert!(app.world().get::<DropdownMenu>(dropdown).unwrap().open);
+		assert_eq!(app.world().get::<Style>(popup).unwrap().display, Display::Flex);
+
+		// Pick the last option.
+		*app.world_mut().get_mut::<Interaction>(dropdown).unwrap() = Interaction::None;
+		*app.world_mut().get_mut::<Interaction>(options[2]).unwrap() = Interaction::Pressed;
+		app.update();
+		let selection = app.world().get::<DropdownSelection>(dropdown).unwrap();
+		assert_eq!((selection.index, selection.value.as_str()), (2, "High"));
+		assert!(!app.world().get::<DropdownMenu>(dropdown).unwrap().open);
+		assert_eq!(app.world().get::<Style>(popup).unwrap().display, Display::None);
+		let events = app.world().resource::<Events<DropdownSelectionChanged>>();
+		let event = events.get_reader().read(events).last().cloned();
+		assert_eq!(event, Some(DropdownSelectionChanged { dropdown, index: 2, value: "High".to_string() }));
+	}
+
+	#[test]
+	fn escape_closes_the_dropdown()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_dropdown);
+		app.update();
+		let dropdown = app.world_mut().query_filtered::<Entity, With<DropdownMenu>>().single(app.world());
+		*app.world_mut().get_mut::<Interaction>(dropdown).unwrap() = Interaction::Pressed;
+		app.update();
+		assert!(app.world().get::<DropdownMenu>(dropdown).unwrap().open);
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Escape);
+		app.update();
+		assert!(!app.world().get::<DropdownMenu>(dropdown).unwrap().open);
+	}
 }
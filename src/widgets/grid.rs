@@ -0,0 +1,435 @@
+/*
+	A grid is a Container with Display::Grid. Every child is given an explicit position when the grid is built,
+	either the one set with at() or the next free cell, so the positions the layout uses are always known.
+	The positions live on the children in GridCell components, place_grid_cells copies them into the children's styles.
+	navigate_grids moves focus between cells with the arrow keys and the D-pad, overriding the navigation crate's choice inside a grid.
+	It leaves widgets that use the arrow keys themselves (text inputs, sliders) alone, and widgets nested deeper than a cell's first focusable.
+*/
+
+use std::collections::HashSet;
+
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy::input::gamepad::Gamepads;
+use bevy_alt_ui_navigation_lite::prelude::*;
+use bevy_alt_ui_navigation_lite::events::Direction;
+
+use super::*;
+use super::slider::{d_pad_input, SliderOrientation, SliderValue};
+use super::text_input::EditableText;
+use crate::theme::ThemeData;
+
+/// The position of a grid's child, rows and columns start at 0.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridCell
+{
+	pub grid: Entity,
+	pub row: usize,
+	pub column: usize,
+	pub row_span: usize,
+	pub column_span: usize,
+}
+
+/// How far apart two spans of tracks are, 0 if they overlap.
+fn span_distance(start: usize, span: usize, other_start: usize, other_span: usize) -> usize
+{
+	if other_start >= start + span
+		{ other_start + 1 - (start + span) }
+	else if start >= other_start + other_span
+		{ start + 1 - (other_start + other_span) }
+	else
+		{ 0 }
+}
+
+/// The nearest cell in `direction` from `from`, preferring cells in line with it.
+pub fn grid_neighbour(cells: &[GridCell], from: &GridCell, direction: Direction) -> Option<usize>
+{
+	cells
+		.iter()
+		.enumerate()
+		.filter_map(|(index, cell)|
+		{
+			let distance = match direction
+			{
+				Direction::East if cell.column >= from.column + from.column_span => cell.column - (from.column + from.column_span),
+				Direction::West if cell.column + cell.column_span <= from.column => from.column - (cell.column + cell.column_span),
+				Direction::South if cell.row >= from.row + from.row_span => cell.row - (from.row + from.row_span),
+				Direction::North if cell.row + cell.row_span <= from.row => from.row - (cell.row + cell.row_span),
+				_ => return None,
+			};
+			// Then prefer cells in line with this one, and aligned with its start.
+			let (offset, alignment) = match direction
+			{
+				Direction::East | Direction::West => (span_distance(from.row, from.row_span, cell.row, cell.row_span), from.row.abs_diff(cell.row)),
+				Direction::North | Direction::South => (span_distance(from.column, from.column_span, cell.column, cell.column_span), from.column.abs_diff(cell.column)),
+			};
+			Some((index, (distance, offset, alignment)))
+		})
+		.min_by_key(|(_, key)| *key)
+		.map(|(index, _)| index)
+}
+
+pub fn place_grid_cells
+(
+	mut cell_query: Query<(&GridCell, &mut Style), Changed<GridCell>>,
+)
+{
+	for (cell, mut style) in cell_query.iter_mut()
+	{
+		style.grid_row = GridPlacement::start_span(cell.row as i16 + 1, cell.row_span as u16);
+		style.grid_column = GridPlacement::start_span(cell.column as i16 + 1, cell.column_span as u16);
+	}
+}
+
+/// Like axis_input without WASD, which would move focus while typing into a cell's text input.
+fn grid_axis_input
+(
+	orientation: SliderOrientation,
+	keyboard_input: &ButtonInput<KeyCode>,
+	gamepad_buttons: &ButtonInput<GamepadButton>,
+	gamepads: &Gamepads,
+) -> f32
+{
+	let (increase_key, decrease_key) = match orientation
+	{
+		SliderOrientation::Horizontal => (KeyCode::ArrowRight, KeyCode::ArrowLeft),
+		SliderOrientation::Vertical => (KeyCode::ArrowUp, KeyCode::ArrowDown),
+	};
+	if keyboard_input.just_pressed(increase_key)
+		{ 1.0 }
+	else if keyboard_input.just_pressed(decrease_key)
+		{ -1.0 }
+	else
+		{ d_pad_input(orientation, gamepad_buttons, gamepads) }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn navigate_grids
+(
+	cell_query: Query<(Entity, &GridCell)>,
+	focus_query: Query<(Entity, &Focusable)>,
+	arrow_key_query: Query<(), Or<(With<EditableText>, With<SliderValue>)>>,
+	parent_query: Query<&Parent>,
+	children_query: Query<&Children>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	gamepads: Res<Gamepads>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let horizontal = grid_axis_input(SliderOrientation::Horizontal, &keyboard_input, &gamepad_buttons, &gamepads);
+	let vertical = grid_axis_input(SliderOrientation::Vertical, &keyboard_input, &gamepad_buttons, &gamepads);
+	let direction = if horizontal > 0.0
+		{ Direction::East }
+	else if horizontal < 0.0
+		{ Direction::West }
+	else if vertical > 0.0
+		{ Direction::North }
+	else if vertical < 0.0
+		{ Direction::South }
+	else
+	{
+		nav_events.clear();
+		return;
+	};
+
+	// The navigation crate reads the same input, so it may have just moved focus already.
+	let (from, to) = match nav_events.read().find_map(|event| match event
+	{
+		NavEvent::FocusChanged { to, from } => Some((*from.first(), Some(*to.first()))),
+		_ => None,
+	})
+	{
+		Some(change) => change,
+		None => match focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused)
+		{
+			Some((focused, _)) => (focused, None),
+			None => return,
+		},
+	};
+	if arrow_key_query.contains(from)
+		{ return; }
+	let Some(from_cell) = std::iter::once(from).chain(parent_query.iter_ancestors(from)).find_map(|entity| cell_query.get(entity).ok())
+		else { return; };
+	// Only the cell itself, or the widget a cell is entered through, moves between cells.
+	let cell_focus = std::iter::once(from_cell.0)
+		.chain(children_query.iter_descendants(from_cell.0))
+		.find(|entity| focus_query.contains(*entity));
+	if cell_focus != Some(from)
+		{ return; }
+
+	let (entities, cells): (Vec<Entity>, Vec<GridCell>) = cell_query
+		.iter()
+		.filter(|(entity, cell)| cell.grid == from_cell.1.grid && *entity != from_cell.0)
+		.map(|(entity, cell)| (entity, *cell))
+		.unzip();
+	let Some(index) = grid_neighbour(&cells, from_cell.1, direction)
+		else { return; };
+	// Cells that aren't focusable themselves (e.g. a Column of buttons) focus their first focusable descendant.
+	let target = std::iter::once(entities[index])
+		.chain(children_query.iter_descendants(entities[index]))
+		.find(|entity| focus_query.contains(*entity));
+	if let Some(target) = target
+	{
+		if to != Some(target)
+			{ nav_writer.send(NavRequest::FocusOn(target)); }
+	}
+}
+
+/// A child of a grid, with its position if it was given one.
+pub struct GridItem<U>
+	where U: Component + Default
+{
+	pub widget: Box<dyn WidgetBuilder<U>>,
+	pub position: Option<(usize, usize)>,
+	pub span: (usize, usize),
+}
+
+pub struct Grid<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub container: Container<U, M>,
+	/// The number of columns children are placed in, when they aren't given a position.
+	pub columns: usize,
+	pub items: Vec<GridItem<U>>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Grid<U, M>
+{
+	/// A grid of `columns` equally sized columns, and as many equally sized rows as are needed.
+	pub fn new(columns: usize) -> Self
+	{
+		let columns = columns.max(1);
+		let mut container = Container::new();
+		container.node_bundle.style.display = Display::Grid;
+		container.node_bundle.style.grid_template_columns = RepeatedGridTrack::flex(columns as u16, 1.0);
+		container.node_bundle.style.grid_auto_rows = vec![GridTrack::flex(1.0)];
+		Self
+		{
+			container,
+			columns,
+			items: Vec::new(),
+		}
+	}
+
+	/// Sets the column sizes, e.g. `vec![GridTrack::px(200.0).into(), RepeatedGridTrack::fr(3, 1.0)]`.
+	/// This should describe the same number of columns the grid was created with.
+	pub fn with_column_template(mut self, columns: Vec<RepeatedGridTrack>) -> Self
+		{ self.container.node_bundle.style.grid_template_columns = columns; self }
+
+	/// Sets the row sizes, rows past the template are equally sized.
+	pub fn with_row_template(mut self, rows: Vec<RepeatedGridTrack>) -> Self
+		{ self.container.node_bundle.style.grid_template_rows = rows; self }
+
+	pub fn with_gap(mut self, row_gap: Val, column_gap: Val) -> Self
+	{
+		self.container.node_bundle.style.row_gap = row_gap;
+		self.container.node_bundle.style.column_gap = column_gap;
+		self
+	}
+
+	/// Adds a child in the next free cell, use `at` and `with_span` to place it.
+	pub fn push(mut self, child: impl Into<Box<dyn WidgetBuilder<U>>>) -> Self
+	{
+		self.items.push(GridItem { widget: child.into(), position: None, span: (1, 1) });
+		self
+	}
+
+	/// Places the last pushed child at `row` and `column`, counting from 0.
+	pub fn at(mut self, row: usize, column: usize) -> Self
+	{
+		if let Some(item) = self.items.last_mut()
+			{ item.position = Some((row, column)); }
+		self
+	}
+
+	/// Makes the last pushed child span `rows` rows and `columns` columns.
+	pub fn with_span(mut self, rows: usize, columns: usize) -> Self
+	{
+		if let Some(item) = self.items.last_mut()
+			{ item.span = (rows.max(1), columns.max(1)); }
+		self
+	}
+
+	/// The position of every item, placed ones first, then the others in the next free cells, row by row.
+	pub fn positions(&self) -> Vec<(usize, usize)>
+	{
+		let mut occupied = HashSet::new();
+		let occupy = |occupied: &mut HashSet<(usize, usize)>, (row, column): (usize, usize), (rows, columns): (usize, usize)|
+		{
+			for row in row..row + rows
+			{
+				for column in column..column + columns
+					{ occupied.insert((row, column)); }
+			}
+		};
+		for item in self.items.iter()
+		{
+			if let Some(position) = item.position
+				{ occupy(&mut occupied, position, item.span); }
+		}
+		let mut cursor = 0;
+		self.items
+			.iter()
+			.map(|item|
+			{
+				if let Some(position) = item.position
+					{ return position; }
+				let columns = item.span.1.min(self.columns);
+				loop
+				{
+					let position = (cursor / self.columns, cursor % self.columns);
+					cursor += 1;
+					let fits = position.1 + columns <= self.columns
+						&& (position.0..position.0 + item.span.0).all(|row| (position.1..position.1 + columns).all(|column| !occupied.contains(&(row, column))));
+					if fits
+					{
+						occupy(&mut occupied, position, (item.span.0, columns));
+						return position;
+					}
+				}
+			})
+			.collect()
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Grid<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Grid<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let positions = self.positions();
+		let child_parent_data = parent_data.from_current(self.container.theme);
+		let children: Vec<(Entity, (usize, usize), (usize, usize))> = self.items
+			.iter_mut()
+			.zip(positions)
+			.map(|(item, position)| (item.widget.build(ui_tree, theme_data, child_parent_data, commands), position, item.span))
+			.collect();
+		for (child, _, _) in children.iter()
+		{
+			self.container.children.push((*child).into());
+		}
+		let grid_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		self.container.children.clear();
+
+		for (child, (row, column), (row_span, column_span)) in children
+		{
+			commands.entity(child).insert(GridCell { grid: grid_entity, row, column, row_span, column_span });
+		}
+		grid_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Grid<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, focused, press_key};
+
+	fn inventory() -> Grid<TestUI>
+	{
+		// A wide item at the top, then a column of two on the right, with auto placed items around them.
+		Grid::<TestUI>::new(3)
+			.push(TextButton::<TestUI>::new("Sword")).at(0, 0).with_span(1, 2)
+			.push(TextButton::<TestUI>::new("Shield")).at(1, 2).with_span(2, 1)
+			.push(TextButton::<TestUI>::new("Potion"))
+			.push(TextButton::<TestUI>::new("Arrow"))
+			.push(TextButton::<TestUI>::new("Bow"))
+	}
+
+	fn build_inventory(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		inventory().build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	fn build_form(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Grid::<TestUI>::new(2)
+			.push(TextInput::<TestUI>::new(None))
+			.push(TextButton::<TestUI>::new("Save"))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn children_fill_the_free_cells()
+	{
+		assert_eq!(inventory().positions(), [(0, 0), (1, 2), (0, 2), (1, 0), (1, 1)]);
+	}
+
+	#[test]
+	fn arrow_keys_move_between_cells()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_inventory);
+		app.update();
+		let cell_at = |app: &mut App, row: usize, column: usize|
+		{
+			let mut query = app.world_mut().query::<(Entity, &GridCell)>();
+			query.iter(app.world()).find(|(_, cell)| cell.row == row && cell.column == column).unwrap().0
+		};
+		let sword = cell_at(&mut app, 0, 0);
+		let shield = cell_at(&mut app, 1, 2);
+		let arrow = cell_at(&mut app, 1, 0);
+		assert_eq!(app.world().get::<Style>(shield).unwrap().grid_row, GridPlacement::start_span(2, 2));
+
+		app.world_mut().send_event(NavRequest::FocusOn(sword));
+		app.update();
+		press_key(&mut app, KeyCode::ArrowDown);
+		assert_eq!(focused(&mut app), Some(arrow));
+
+		press_key(&mut app, KeyCode::ArrowRight);
+		// Potion is in the row above, the bow is in line.
+		let bow = cell_at(&mut app, 1, 1);
+		assert_eq!(focused(&mut app), Some(bow));
+	}
+
+	#[test]
+	fn text_inputs_keep_their_keys()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_form);
+		app.update();
+		let input = app.world_mut().query_filtered::<Entity, With<EditableText>>().single(app.world());
+		app.world_mut().send_event(NavRequest::FocusOn(input));
+		app.update();
+		for key in [KeyCode::KeyD, KeyCode::ArrowRight]
+		{
+			press_key(&mut app, key);
+			assert_eq!(focused(&mut app), Some(input));
+		}
+	}
+}
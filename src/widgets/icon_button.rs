@@ -0,0 +1,302 @@
+/*
+	An icon button is a BaseButton holding an icon (an ImageBundle, optionally showing a texture atlas cell) and a TextLabel.
+	The icon is placed before, after or above the label, with a gap between them, and is tinted with the label's foreground colour, tweening to the new one alongside the label on a theme change.
+	Icon-only buttons have no label, so they carry an AccessibleName that keep_accessible_names puts back whenever Bevy recomputes the button's accessibility node.
+	Like FocusedImage/ActiveImage on buttons, the icon can be swapped while the button is focused or active.
+*/
+
+use bevy::a11y::{accesskit::{NodeBuilder, Role}, AccessibilityNode};
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use super::base_button::BaseButton;
+use crate::theme::{ThemeData, ThemePaint};
+
+/// Where the icon goes, relative to the label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IconPlacement
+{
+	#[default]
+	Leading,
+	Trailing,
+	Top,
+}
+
+/// Lives on the icon button.
+#[derive(Component)]
+pub struct IconButtonParts
+{
+	pub icon: Entity,
+	pub label: Option<Entity>,
+}
+
+/// Lives on an icon button's icon.
+#[derive(Component)]
+pub struct IconImages
+{
+	pub normal: UiImage,
+	pub focused: Option<UiImage>,
+	pub active: Option<UiImage>,
+}
+
+/// The name screen readers announce for a button without a label.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct AccessibleName(pub String);
+
+pub fn swap_icons_on_focus
+(
+	button_query: Query<(&Focusable, &IconButtonParts), Changed<Focusable>>,
+	mut icon_query: Query<(&mut UiImage, &IconImages)>,
+)
+{
+	for (focus, parts) in button_query.iter()
+	{
+		let Ok((mut ui_image, images)) = icon_query.get_mut(parts.icon)
+			else { continue; };
+		let image = match focus.state()
+		{
+			FocusState::Focused => images.focused.as_ref(),
+			FocusState::Active => images.active.as_ref(),
+			_ => None,
+		}.unwrap_or(&images.normal);
+		// Only the texture is swapped, the tint is kept.
+		if ui_image.texture != image.texture
+			{ ui_image.texture = image.texture.clone(); }
+	}
+}
+
+/// Bevy names buttons after their text children, which icon-only buttons don't have.
+pub fn keep_accessible_names
+(
+	mut query: Query<(&AccessibleName, &mut AccessibilityNode), Changed<AccessibilityNode>>,
+)
+{
+	for (name, mut node) in query.iter_mut()
+	{
+		if node.name() != Some(name.0.as_str())
+			{ node.set_name(name.0.clone()); }
+	}
+}
+
+pub struct IconButton<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub base_button: BaseButton<U, M>,
+	pub icon: UiImage,
+	pub focused_icon: Option<UiImage>,
+	pub active_icon: Option<UiImage>,
+	/// Shows a single cell of the icon image.
+	pub atlas: Option<TextureAtlas>,
+	/// None for icon-only buttons.
+	pub label: Option<TextLabel<U, ()>>,
+	pub accessible_name: String,
+	pub placement: IconPlacement,
+	/// The gap between the icon and the label.
+	pub spacing: Val,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> IconButton<U, M>
+{
+	pub fn new(icon: impl Into<UiImage>, text: impl Into<String>) -> Self
+	{
+		let text = text.into();
+		Self
+		{
+			base_button: BaseButton::new()
+				.with_auto_style(true),
+			icon: icon.into(),
+			focused_icon: None,
+			active_icon: None,
+			atlas: None,
+			label: Some(TextLabel::new(text.clone())
+				.with_paint_mode(PaintMode::Invisible)),
+			accessible_name: text,
+			placement: IconPlacement::default(),
+			spacing: Val::Percent(2.0),
+		}
+	}
+
+	/// A button showing only an icon, `accessible_name` describes it to screen readers.
+	pub fn icon_only(icon: impl Into<UiImage>, accessible_name: impl Into<String>) -> Self
+	{
+		let mut button = Self::new(icon, accessible_name);
+		button.label = None;
+		button
+	}
+
+	pub fn with_atlas(mut self, atlas: TextureAtlas) -> Self
+		{ self.atlas = Some(atlas); self }
+
+	pub fn with_placement(mut self, placement: IconPlacement) -> Self
+		{ self.placement = placement; self }
+
+	pub fn with_spacing(mut self, spacing: Val) -> Self
+		{ self.spacing = spacing; self }
+
+	pub fn with_focused_icon(mut self, icon: impl Into<UiImage>) -> Self
+		{ self.focused_icon = Some(icon.into()); self }
+
+	pub fn with_active_icon(mut self, icon: impl Into<UiImage>) -> Self
+		{ self.active_icon = Some(icon.into()); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for IconButton<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.base_button = self.base_button.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.base_button = self.base_button.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.base_button = self.base_button.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.base_button = self.base_button.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.base_button = self.base_button.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.base_button = self.base_button.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.base_button = self.base_button.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.base_button = self.base_button.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.base_button = self.base_button.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.base_button = self.base_button.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.base_button = self.base_button.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.base_button = self.base_button.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for IconButton<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let theme = match self.base_button.theme
+		{
+			Theme::Auto => parent_data.resolve_theme(),
+			theme => theme,
+		};
+		let child_parent_data = parent_data.from_current(self.base_button.theme);
+
+		// Tint the icon like the label's text.
+		let paint = ThemePaint::<U>::new(theme, PaintMode::Invisible);
+		let tint = paint.paint_mode.get_foreground(theme, theme_data);
+		let mut ui_image = self.icon.clone();
+		ui_image.color = tint;
+		let icon_size = match (&self.label, self.placement)
+		{
+			(None, _) => Val::Percent(70.0),
+			(Some(_), IconPlacement::Top) => Val::Percent(50.0),
+			(Some(_), _) => Val::Percent(60.0),
+		};
+		let mut icon = commands.spawn(ImageBundle
+		{
+			style: Style
+			{
+				height: icon_size,
+				aspect_ratio: Some(1.0),
+				flex_shrink: 0.0,
+				..Default::default()
+			},
+			image: ui_image,
+			..Default::default()
+		});
+		icon
+			.insert(U::default())
+			.insert(image_tint_bundle(tint))
+			.insert(paint)
+			.insert(IconImages
+			{
+				normal: self.icon.clone(),
+				focused: self.focused_icon.clone(),
+				active: self.active_icon.clone(),
+			});
+		if let Some(atlas) = &self.atlas
+			{ icon.insert(atlas.clone()); }
+		let icon_entity = icon.id();
+
+		let label_entity = self.label.as_mut().map(|label| label.build(ui_tree, theme_data, child_parent_data, commands));
+
+		let style = &mut self.base_button.button_bundle.style;
+		let (builder_direction, builder_column_gap, builder_row_gap) = (style.flex_direction, style.column_gap, style.row_gap);
+		style.flex_direction = if self.placement == IconPlacement::Top { FlexDirection::Column } else { FlexDirection::Row };
+		style.column_gap = self.spacing;
+		style.row_gap = self.spacing;
+		let button_entity = self.base_button.build(ui_tree, theme_data, parent_data, commands);
+		let style = &mut self.base_button.button_bundle.style;
+		(style.flex_direction, style.column_gap, style.row_gap) = (builder_direction, builder_column_gap, builder_row_gap);
+
+		let children = match (label_entity, self.placement)
+		{
+			(Some(label_entity), IconPlacement::Trailing) => vec![label_entity, icon_entity],
+			(Some(label_entity), _) => vec![icon_entity, label_entity],
+			(None, _) => vec![icon_entity],
+		};
+		let mut button = commands.entity(button_entity);
+		button
+			.push_children(&children)
+			.insert(IconButtonParts { icon: icon_entity, label: label_entity });
+		if label_entity.is_none()
+		{
+			let mut node = NodeBuilder::new(Role::Button);
+			node.set_name(self.accessible_name.clone());
+			button
+				.insert(AccessibilityNode(node))
+				.insert(AccessibleName(self.accessible_name.clone()));
+		}
+		button_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for IconButton<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	const ICON: Handle<Image> = Handle::weak_from_u128(1);
+	const FOCUSED_ICON: Handle<Image> = Handle::weak_from_u128(2);
+
+	fn build_buttons(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Row::<TestUI>::new()
+			.push(IconButton::<TestUI>::new(ICON, "Settings")
+				.with_placement(IconPlacement::Trailing))
+			.push(IconButton::<TestUI>::icon_only(ICON, "Close")
+				.with_focused_icon(FOCUSED_ICON))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn icons_are_placed_tinted_and_swapped()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_buttons);
+		app.update();
+		let mut query = app.world_mut().query::<(Entity, &IconButtonParts)>();
+		let buttons: Vec<(Entity, Entity, Option<Entity>)> = query.iter(app.world()).map(|(entity, parts)| (entity, parts.icon, parts.label)).collect();
+		let (labelled, labelled_icon, label) = *buttons.iter().find(|(_, _, label)| label.is_some()).unwrap();
+		let (icon_only, icon, _) = *buttons.iter().find(|(_, _, label)| label.is_none()).unwrap();
+
+		// Trailing icons come after the label.
+		assert_eq!(**app.world().get::<Children>(labelled).unwrap(), [label.unwrap(), labelled_icon]);
+		assert_eq!(app.world().get::<UiImage>(labelled_icon).unwrap().color, crate::theme::DARK.base_foreground);
+		assert_eq!(app.world().get::<AccessibilityNode>(icon_only).unwrap().name(), Some("Close"));
+
+		app.world_mut().send_event(NavRequest::FocusOn(icon_only));
+		app.update();
+		assert_eq!(app.world().get::<UiImage>(icon).unwrap().texture, FOCUSED_ICON);
+		app.world_mut().send_event(NavRequest::FocusOn(labelled));
+		app.update();
+		assert_eq!(app.world().get::<UiImage>(icon).unwrap().texture, ICON);
+	}
+}
@@ -0,0 +1,324 @@
+/*
+	An image widget is a Container (clipping, invisible by default) holding an ImageBundle.
+	The container takes the space given by the layout, fit_images then sizes the image inside it according to its ImageFit once the image has loaded.
+	Children are pushed into the image itself, so a nine-sliced image can frame other widgets.
+	Tinted images take the foreground colour of their theme, when the theme changes tint_images tweens them to the new one like every other colour.
+*/
+
+use super::*;
+use crate::theme::{ThemeData, ThemePaint, CurrentThemeData, ColourTarget, ColourTween};
+
+/// How an image is sized inside its widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFit
+{
+	/// As large as possible while showing the whole image.
+	#[default]
+	Contain,
+	/// As small as possible while covering the whole widget, the rest is clipped.
+	Cover,
+	/// Fills the widget, ignoring the image's proportions.
+	Stretch,
+	/// The image's own size, in logical pixels.
+	None,
+}
+
+/// Lives on the image widget's container.
+#[derive(Component)]
+pub struct ImageParts
+{
+	pub image: Entity,
+	pub fit: ImageFit,
+	/// Whether to give the widget the image's aspect ratio once it has loaded.
+	pub auto_aspect_ratio: bool,
+}
+
+/// Marks images that are tinted with their theme's foreground colour.
+#[derive(Component)]
+pub struct ImageTint;
+
+/// The size of an image of `image` size fitted into `container`.
+pub fn fit_size(fit: ImageFit, image: Vec2, container: Vec2) -> Vec2
+{
+	let scale = container / image;
+	match fit
+	{
+		ImageFit::Contain => image * scale.min_element(),
+		ImageFit::Cover => image * scale.max_element(),
+		ImageFit::Stretch => container,
+		ImageFit::None => image,
+	}
+}
+
+pub fn fit_images
+(
+	mut commands: Commands,
+	parts_query: Query<(Entity, &ImageParts, &Node, Has<AspectRatio>)>,
+	image_query: Query<&UiImage>,
+	images: Res<Assets<Image>>,
+	mut style_query: Query<&mut Style>,
+	mut aspect_ratio_writer: EventWriter<AspectRatioEvent>,
+)
+{
+	for (entity, parts, node, has_aspect_ratio) in parts_query.iter()
+	{
+		let Some(image_size) = image_query.get(parts.image).ok().and_then(|ui_image| images.get(&ui_image.texture)).map(|image| image.size_f32())
+			else { continue; };
+		if image_size.x <= 0.0 || image_size.y <= 0.0
+			{ continue; }
+		if parts.auto_aspect_ratio && !has_aspect_ratio
+		{
+			commands.entity(entity).insert(AspectRatio(image_size.x / image_size.y));
+			aspect_ratio_writer.send(AspectRatioEvent);
+		}
+
+		let (width, height) = match parts.fit
+		{
+			ImageFit::Stretch => (Val::Percent(100.0), Val::Percent(100.0)),
+			fit =>
+			{
+				// Nodes that haven't been laid out yet have nothing to fit into.
+				if fit != ImageFit::None && (node.size().x == 0.0 || node.size().y == 0.0)
+					{ continue; }
+				let size = fit_size(fit, image_size, node.size());
+				(Val::Px(size.x), Val::Px(size.y))
+			},
+		};
+		let Ok(mut style) = style_query.get_mut(parts.image)
+			else { continue; };
+		if style.width != width || style.height != height
+		{
+			style.width = width;
+			style.height = height;
+		}
+	}
+}
+
+pub fn tint_images<U: Component>
+(
+	theme_data: Res<CurrentThemeData<U>>,
+	mut image_query: Query<(Ref<ThemePaint<U>>, &mut ColourTarget), With<ImageTint>>,
+)
+{
+	for (paint, mut target) in image_query.iter_mut()
+	{
+		if !crate::theme::needs_repaint(&theme_data, &paint)
+			{ continue; }
+		// Only the tint, not the node colours repaint_on_theme_change gave the image.
+		let tint = ColourTarget { image: Some(paint.paint_mode.get_foreground(paint.theme, &theme_data.0)), ..Default::default() };
+		if *target != tint
+			{ *target = tint; }
+	}
+}
+
+/// The components a tinted image needs to follow the theme, tinted with `tint`.
+pub fn image_tint_bundle(tint: Color) -> (ImageTint, ColourTarget, ColourTween)
+{
+	(ImageTint, ColourTarget { image: Some(tint), ..Default::default() }, ColourTween::default())
+}
+
+pub struct ImageWidget<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub container: Container<U, M>,
+	pub image: UiImage,
+	pub fit: ImageFit,
+	pub scale_mode: Option<ImageScaleMode>,
+	pub tint: bool,
+	pub auto_aspect_ratio: bool,
+	pub children: Vec<Box<dyn WidgetBuilder<U>>>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> ImageWidget<U, M>
+{
+	pub fn new(image: impl Into<UiImage>) -> Self
+	{
+		Self
+		{
+			container: Container::new()
+				.with_paint_mode(PaintMode::Invisible),
+			image: image.into(),
+			fit: ImageFit::default(),
+			scale_mode: None,
+			tint: false,
+			auto_aspect_ratio: false,
+			children: Vec::new(),
+		}
+	}
+
+	pub fn with_fit(mut self, fit: ImageFit) -> Self
+		{ self.fit = fit; self }
+
+	/// Tints the image with its theme's foreground colour, for monochrome icons and logos.
+	pub fn with_tint(mut self, tint: bool) -> Self
+		{ self.tint = tint; self }
+
+	/// Gives the widget the image's aspect ratio once it has loaded.
+	pub fn with_auto_aspect_ratio(mut self, auto_aspect_ratio: bool) -> Self
+		{ self.auto_aspect_ratio = auto_aspect_ratio; self }
+
+	pub fn with_scale_mode(mut self, scale_mode: ImageScaleMode) -> Self
+		{ self.scale_mode = Some(scale_mode); self }
+
+	/// Nine-slices the image, keeping `border` pixels of each edge unscaled, and stretches it over the widget.
+	pub fn with_nine_slice(self, border: f32) -> Self
+	{
+		self.with_fit(ImageFit::Stretch)
+			.with_scale_mode(ImageScaleMode::Sliced(TextureSlicer
+			{
+				border: BorderRect::square(border),
+				center_scale_mode: SliceScaleMode::Stretch,
+				sides_scale_mode: SliceScaleMode::Stretch,
+				max_corner_scale: 1.0,
+			}))
+	}
+
+	pub fn push(mut self, child: impl Into<Box<dyn WidgetBuilder<U>>>) -> Self
+	{
+		self.children.push(child.into());
+		self
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for ImageWidget<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for ImageWidget<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let theme = match self.container.theme
+		{
+			Theme::Auto => parent_data.resolve_theme(),
+			theme => theme,
+		};
+		let paint = ThemePaint::<U>::new(theme, self.container.paint_mode);
+		let mut ui_image = self.image.clone();
+		if self.tint
+			{ ui_image.color = paint.paint_mode.get_foreground(theme, theme_data); }
+
+		let child_parent_data = parent_data.from_current(self.container.theme);
+		let children: Vec<Entity> = self.children.iter_mut().map(|child| child.build(ui_tree, theme_data, child_parent_data, commands)).collect();
+
+		let mut image = commands.spawn(ImageBundle
+		{
+			style: Style
+			{
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_shrink: 0.0,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..Default::default()
+			},
+			image: ui_image,
+			..Default::default()
+		});
+		image
+			.insert(U::default())
+			.push_children(&children);
+		if let Some(scale_mode) = &self.scale_mode
+			{ image.insert(scale_mode.clone()); }
+		if self.tint
+			{ image.insert(image_tint_bundle(paint.paint_mode.get_foreground(theme, theme_data))).insert(paint); }
+		let image_entity = image.id();
+
+		self.container.children.push(image_entity.into());
+		let container_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		self.container.children.clear();
+
+		commands.entity(container_entity)
+			.insert(ImageParts { image: image_entity, fit: self.fit, auto_aspect_ratio: self.auto_aspect_ratio });
+		container_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for ImageWidget<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use bevy::render::{render_asset::RenderAssetUsages, render_resource::{Extent3d, TextureDimension, TextureFormat}};
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_logo(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>, mut images: ResMut<Assets<Image>>)
+	{
+		let size = Extent3d { width: 200, height: 100, depth_or_array_layers: 1 };
+		let logo = images.add(Image::new_fill(size, TextureDimension::D2, &[255, 255, 255, 255], TextureFormat::Rgba8UnormSrgb, RenderAssetUsages::default()));
+		ImageWidget::<TestUI>::new(logo)
+			.with_tint(true)
+			.with_auto_aspect_ratio(true)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn images_fit_their_widget()
+	{
+		let image = Vec2::new(200.0, 100.0);
+		let container = Vec2::new(100.0, 100.0);
+		assert_eq!(fit_size(ImageFit::Contain, image, container), Vec2::new(100.0, 50.0));
+		assert_eq!(fit_size(ImageFit::Cover, image, container), Vec2::new(200.0, 100.0));
+		assert_eq!(fit_size(ImageFit::Stretch, image, container), container);
+		assert_eq!(fit_size(ImageFit::None, image, container), image);
+	}
+
+	#[test]
+	fn loaded_images_set_the_aspect_ratio_and_follow_the_theme()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_logo);
+		app.update();
+		app.update();
+		let (widget, image) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &ImageParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.image)
+		};
+		assert_eq!(app.world().get::<AspectRatio>(widget).map(|aspect_ratio| aspect_ratio.0), Some(2.0));
+		assert_eq!(app.world().get::<UiImage>(image).unwrap().color, crate::theme::DARK.base_foreground);
+
+		// The tint is tweened like the rest of the UI rather than jumping to the new theme.
+		app.world_mut().resource_mut::<CurrentThemeData<TestUI>>().0 = crate::theme::LIGHT.clone();
+		app.update();
+		assert_ne!(app.world().get::<UiImage>(image).unwrap().color, crate::theme::LIGHT.base_foreground);
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(crate::theme::LIGHT.transition.duration));
+		app.update();
+		assert_eq!(app.world().get::<UiImage>(image).unwrap().color, crate::theme::LIGHT.base_foreground);
+	}
+}
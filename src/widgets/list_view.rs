@@ -0,0 +1,331 @@
+/*
+	A list view is a ScrollView whose content is as tall as all of its rows, but only holds the rows that are visible (plus a buffer).
+	Rows have a fixed height, and are built into "slots": absolutely positioned containers that are moved to a new index when they scroll out of view.
+	update_list_views runs every frame, frees the slots that left the visible range, and builds the newly visible rows into them.
+	Only the slot containers are reused, a freed slot's row is despawned and the new row is built from the source.
+	The slot holding the focused widget is never freed while scrolling, so keyboard/gamepad focus is never lost.
+	When the source changes the focused row is rebuilt too (even out of view), and focus moves to the rebuilt row.
+*/
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::{ThemeData, CurrentThemeData};
+
+/// How many rows are kept built above and below the visible ones.
+const DEFAULT_BUFFER_ROWS: usize = 4;
+
+/// What the list view displays. Change the count or replace the builder to rebuild the visible rows.
+#[derive(Component)]
+pub struct ListViewSource<U: Component + Default>
+{
+	pub count: usize,
+	pub build: Arc<dyn Fn(usize) -> Box<dyn WidgetBuilder<U>> + Send + Sync>,
+}
+
+impl<U: Component + Default> ListViewSource<U>
+{
+	pub fn new<T: WidgetBuilder<U> + 'static>(count: usize, build: impl Fn(usize) -> T + Send + Sync + 'static) -> Self
+	{
+		Self
+		{
+			count,
+			build: Arc::new(move |index| Box::new(build(index))),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListRowSlot
+{
+	pub entity: Entity,
+	/// The row this slot is showing, None if the slot is free (and hidden).
+	pub index: Option<usize>,
+}
+
+/// The row slots of a list view, lives on the list view.
+#[derive(Component)]
+pub struct ListViewRows
+{
+	pub row_height: f32,
+	pub buffer: usize,
+	pub slots: Vec<ListRowSlot>,
+	/// Rows are built at runtime, with the theme the list view was built with.
+	pub parent_data: ParentData,
+}
+
+/// The rows that should be built, for the given scroll offset and viewport height (both in logical pixels).
+pub fn visible_rows(offset: f32, viewport_height: f32, row_height: f32, buffer: usize, count: usize) -> Range<usize>
+{
+	if row_height <= 0.0
+		{ return 0..0; }
+	let first = (offset / row_height).floor().max(0.0) as usize;
+	// Before the first layout, the viewport has no size, build at least one row.
+	let visible = ((viewport_height / row_height).ceil() as usize).max(1);
+	let start = first.saturating_sub(buffer).min(count);
+	let end = (first + visible + buffer).min(count);
+	start..end
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_list_views<U: Component + Default>
+(
+	mut commands: Commands,
+	mut list_query: Query<(Ref<ListViewSource<U>>, &mut ListViewRows, &ScrollOffset, &ScrollViewParts)>,
+	node_query: Query<&Node>,
+	mut style_query: Query<&mut Style>,
+	focus_query: Query<(Entity, &Focusable)>,
+	parent_query: Query<&Parent>,
+	mut ui_tree: ResMut<crate::UIHierarchy<U>>,
+	theme_data: Res<CurrentThemeData<U>>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let focused = focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused).map(|(entity, _)| entity);
+	for (source, mut rows, scroll, parts) in list_query.iter_mut()
+	{
+		let rows = &mut *rows;
+		let viewport_height = node_query.get(parts.viewport).map_or(0.0, |node| node.size().y);
+		let range = visible_rows(scroll.offset, viewport_height, rows.row_height, rows.buffer, source.count);
+		let rebuild = source.is_changed();
+		if rebuild
+		{
+			if let Ok(mut style) = style_query.get_mut(parts.content)
+				{ style.height = Val::Px(source.count as f32 * rows.row_height); }
+		}
+
+		// Free the slots that left the visible range, except the focused one. Everything is freed when the source changes.
+		let focused_slot = focused.and_then(|focused| parent_query.iter_ancestors(focused).find(|ancestor| rows.slots.iter().any(|slot| slot.entity == *ancestor)));
+		let mut refocus = None;
+		for slot in rows.slots.iter_mut()
+		{
+			let Some(index) = slot.index
+				else { continue; };
+			let is_focused = focused_slot == Some(slot.entity);
+			if !rebuild && (range.contains(&index) || is_focused)
+				{ continue; }
+			if is_focused && source.count > 0
+				{ refocus = Some(index.min(source.count - 1)); }
+			commands.entity(slot.entity).despawn_descendants();
+			slot.index = None;
+		}
+
+		// Build the missing rows, reusing free slots before spawning new ones. A focused row that was rebuilt out of view is kept, like it is while scrolling.
+		let missing: Vec<usize> = range.clone().chain(refocus.filter(|index| !range.contains(index)))
+			.filter(|index| !rows.slots.iter().any(|slot| slot.index == Some(*index)))
+			.collect();
+		for index in missing
+		{
+			let top = Val::Px(index as f32 * rows.row_height);
+			let slot_entity = match rows.slots.iter_mut().find(|slot| slot.index.is_none())
+			{
+				Some(slot) =>
+				{
+					slot.index = Some(index);
+					if let Ok(mut style) = style_query.get_mut(slot.entity)
+					{
+						style.top = top;
+						style.display = Display::Flex;
+					}
+					slot.entity
+				},
+				None =>
+				{
+					let mut slot = Container::<U, ()>::new()
+						.with_paint_mode(PaintMode::Invisible);
+					slot.node_bundle.style.position_type = PositionType::Absolute;
+					slot.node_bundle.style.top = top;
+					slot.node_bundle.style.height = Val::Px(rows.row_height);
+					let slot_entity = slot.build(&mut ui_tree, &theme_data.0, rows.parent_data, &mut commands);
+					commands.entity(parts.content).add_child(slot_entity);
+					rows.slots.push(ListRowSlot { entity: slot_entity, index: Some(index) });
+					slot_entity
+				},
+			};
+			let item = (source.build)(index).build(&mut ui_tree, &theme_data.0, rows.parent_data, &mut commands);
+			commands.entity(slot_entity).add_child(item);
+			// The focused row was rebuilt, so move focus to its replacement. Rows are expected to be focusable (buttons, etc.).
+			if refocus == Some(index)
+				{ nav_writer.send(NavRequest::FocusOn(item)); }
+		}
+
+		for slot in rows.slots.iter().filter(|slot| slot.index.is_none())
+		{
+			if let Ok(mut style) = style_query.get_mut(slot.entity)
+			{
+				if style.display != Display::None
+					{ style.display = Display::None; }
+			}
+		}
+	}
+}
+
+pub struct ListView<U, T>
+	where U: Component + Default, T: WidgetBuilder<U> + 'static
+{
+	pub scroll_view: ScrollView<U, ()>,
+	pub count: usize,
+	/// In logical pixels, every row has the same height.
+	pub row_height: f32,
+	pub buffer: usize,
+	pub build_item: Arc<dyn Fn(usize) -> T + Send + Sync>,
+}
+
+impl<U: Component + Default, T: WidgetBuilder<U> + 'static> ListView<U, T>
+{
+	/// `build_item` is called with the index of each row, when that row scrolls into view.
+	pub fn new(count: usize, build_item: impl Fn(usize) -> T + Send + Sync + 'static) -> Self
+	{
+		Self
+		{
+			scroll_view: ScrollView::new(),
+			count,
+			row_height: 40.0,
+			buffer: DEFAULT_BUFFER_ROWS,
+			build_item: Arc::new(build_item),
+		}
+	}
+
+	pub fn with_row_height(mut self, row_height: f32) -> Self
+		{ self.row_height = row_height; self }
+
+	pub fn with_buffer(mut self, buffer: usize) -> Self
+		{ self.buffer = buffer; self }
+}
+
+impl<U: Component + Default, T: WidgetBuilder<U> + 'static> Widget for ListView<U, T>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.scroll_view = self.scroll_view.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.scroll_view = self.scroll_view.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.scroll_view = self.scroll_view.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.scroll_view = self.scroll_view.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.scroll_view = self.scroll_view.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.scroll_view = self.scroll_view.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.scroll_view = self.scroll_view.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.scroll_view = self.scroll_view.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.scroll_view = self.scroll_view.with_theme(theme); self }
+}
+
+impl<U: Component + Default, T: WidgetBuilder<U> + 'static> WidgetBuilder<U> for ListView<U, T>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		// The rows are built by update_list_views, on the next frame.
+		self.scroll_view.content.node_bundle.style.height = Val::Px(self.count as f32 * self.row_height);
+		let list_entity = self.scroll_view.build(ui_tree, theme_data, parent_data, commands);
+		let build_item = self.build_item.clone();
+		commands.entity(list_entity)
+			.insert(ListViewSource::<U>::new(self.count, move |index| build_item(index)))
+			.insert(ListViewRows
+			{
+				row_height: self.row_height,
+				buffer: self.buffer,
+				slots: Vec::new(),
+				parent_data: parent_data.from_current(self.scroll_view.container.theme),
+			});
+		list_entity
+	}
+}
+
+impl<U: Component + Default, T: WidgetBuilder<U> + 'static> Into<Box<dyn WidgetBuilder<U>>> for ListView<U, T>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, focused};
+
+	fn build_list_view(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		ListView::new(20_000, |index| TextButton::<TestUI>::new(format!("Item {index}")))
+			.with_buffer(2)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn visible_rows_include_the_buffer()
+	{
+		assert_eq!(visible_rows(0.0, 200.0, 40.0, 2, 100), 0..7);
+		assert_eq!(visible_rows(400.0, 200.0, 40.0, 2, 100), 8..17);
+		assert_eq!(visible_rows(3900.0, 200.0, 40.0, 2, 100), 95..100);
+		assert_eq!(visible_rows(0.0, 200.0, 40.0, 2, 3), 0..3);
+	}
+
+	#[test]
+	fn only_visible_rows_are_built()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_list_view);
+		app.update();
+		app.update();
+		let list = app.world_mut().query_filtered::<Entity, With<ListViewRows>>().single(app.world());
+		let built_rows = |app: &mut App| app.world_mut().query_filtered::<(), With<Focusable>>().iter(app.world()).count();
+		// Without a layout a single row is visible, plus the buffer below it.
+		assert_eq!(built_rows(&mut app), 3);
+
+		// Shrinking the source rebuilds the rows, reusing the slots.
+		app.world_mut().get_mut::<ListViewSource<TestUI>>(list).unwrap().count = 1;
+		app.update();
+		assert_eq!(built_rows(&mut app), 1);
+		let rows = app.world().get::<ListViewRows>(list).unwrap();
+		assert_eq!(rows.slots.len(), 3);
+		assert_eq!(rows.slots.iter().filter(|slot| slot.index.is_some()).count(), 1);
+	}
+
+	#[test]
+	fn focus_follows_a_rebuilt_row_out_of_view()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_list_view);
+		app.update();
+		app.update();
+		let list = app.world_mut().query_filtered::<Entity, With<ListViewRows>>().single(app.world());
+		let slot_index = |app: &mut App, entity: Entity|
+		{
+			let slot = app.world().get::<Parent>(entity).unwrap().get();
+			app.world().get::<ListViewRows>(list).unwrap().slots.iter().find(|row_slot| row_slot.entity == slot).unwrap().index
+		};
+		let first_row = app.world().get::<ListViewRows>(list).unwrap().slots[0].entity;
+		let first_row = app.world().get::<Children>(first_row).unwrap()[0];
+		app.world_mut().send_event(NavRequest::FocusOn(first_row));
+		app.update();
+		assert_eq!(focused(&mut app), Some(first_row));
+
+		// There is no layout to scroll with, so move the focused slot out of view by hand, as scrolling down would leave it.
+		app.world_mut().get_mut::<ListViewRows>(list).unwrap().slots[0].index = Some(10);
+		app.update();
+		assert_eq!(slot_index(&mut app, first_row), Some(10));
+
+		// Changing the source rebuilds the focused row, out of view, and moves focus to it.
+		app.world_mut().get_mut::<ListViewSource<TestUI>>(list).unwrap().count = 100;
+		app.update();
+		app.update();
+		let refocused = focused(&mut app).expect("Focus was lost when the source changed.");
+		assert_ne!(refocused, first_row);
+		assert_eq!(slot_index(&mut app, refocused), Some(10));
+	}
+}
@@ -0,0 +1,1036 @@
+/*
+	Menus for tool UIs: a MenuBar of buttons that each open a menu, and a ContextMenu opened by right-clicking a widget (or the menu key while it's focused).
+	A Menu describes its entries (items, submenus and separators), each menu is built as a hidden top-level popup Container with a global Z index,
+	holding one BaseButton per entry with TextLabels for the check mark, the text, the accelerator and the submenu arrow.
+	Popups are registered as sub-menus of whatever opens them, so navigation stays inside the innermost open menu.
+	A submenu opens when its item is pressed or on Right, and closes on Left, Escape or the gamepad back button. Clicking anywhere else closes every menu.
+	Activating an item (or pressing its accelerator, even with the menus closed) sends a MenuAction and closes the menus.
+	Menu bar accelerators work anywhere, a context menu's only while its target is focused or the menu is open.
+*/
+
+use bevy::ui::RelativeCursorPosition;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use super::base_button::BaseButton;
+use crate::theme::ThemeData;
+
+const MENU_ITEM_HEIGHT: f32 = 28.0;
+const MENU_BAR_HEIGHT: f32 = 32.0;
+const MENU_MIN_WIDTH: f32 = 180.0;
+
+/// A keyboard shortcut for a menu item, shown next to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accelerator
+{
+	pub key: KeyCode,
+	pub ctrl: bool,
+	pub shift: bool,
+	pub alt: bool,
+}
+
+impl Accelerator
+{
+	pub fn new(key: KeyCode) -> Self
+	{
+		Self { key, ctrl: false, shift: false, alt: false }
+	}
+
+	pub fn ctrl(key: KeyCode) -> Self
+	{
+		Self { ctrl: true, ..Self::new(key) }
+	}
+
+	pub fn with_shift(mut self) -> Self
+		{ self.shift = true; self }
+
+	pub fn with_alt(mut self) -> Self
+		{ self.alt = true; self }
+
+	/// The text shown next to the item, e.g. "Ctrl+Shift+S".
+	pub fn label(&self) -> String
+	{
+		let key = format!("{:?}", self.key);
+		let key = key.strip_prefix("Key")
+			.or_else(|| key.strip_prefix("Digit"))
+			.unwrap_or(&key);
+		let mut label = String::new();
+		for (held, modifier) in [(self.ctrl, "Ctrl+"), (self.shift, "Shift+"), (self.alt, "Alt+")]
+		{
+			if held
+				{ label.push_str(modifier); }
+		}
+		label.push_str(key);
+		label
+	}
+
+	/// Whether the key was just pressed with exactly these modifiers held.
+	pub fn just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>) -> bool
+	{
+		keyboard_input.just_pressed(self.key)
+			&& keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) == self.ctrl
+			&& keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) == self.shift
+			&& keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) == self.alt
+	}
+}
+
+#[derive(Clone)]
+pub struct MenuItem
+{
+	pub text: String,
+	/// Sent with MenuAction when the item is activated, the text by default.
+	pub action: String,
+	pub accelerator: Option<Accelerator>,
+	/// Some for checkable items.
+	pub checked: Option<bool>,
+	pub disabled: bool,
+}
+
+impl MenuItem
+{
+	pub fn new(text: impl Into<String>) -> Self
+	{
+		let text = text.into();
+		Self
+		{
+			action: text.clone(),
+			text,
+			accelerator: None,
+			checked: None,
+			disabled: false,
+		}
+	}
+
+	pub fn with_action(mut self, action: impl Into<String>) -> Self
+		{ self.action = action.into(); self }
+
+	pub fn with_accelerator(mut self, accelerator: Accelerator) -> Self
+		{ self.accelerator = Some(accelerator); self }
+
+	/// Makes the item checkable, activating it toggles the check mark.
+	pub fn with_checked(mut self, checked: bool) -> Self
+		{ self.checked = Some(checked); self }
+
+	pub fn with_disabled(mut self, disabled: bool) -> Self
+		{ self.disabled = disabled; self }
+}
+
+pub enum MenuEntry
+{
+	Item(MenuItem),
+	Submenu(String, Menu),
+	Separator,
+}
+
+/// The entries of a menu, see MenuBar and ContextMenu.
+#[derive(Default)]
+pub struct Menu
+{
+	pub entries: Vec<MenuEntry>,
+}
+
+impl Menu
+{
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+	pub fn push(mut self, item: MenuItem) -> Self
+		{ self.entries.push(MenuEntry::Item(item)); self }
+
+	pub fn push_submenu(mut self, text: impl Into<String>, menu: Menu) -> Self
+		{ self.entries.push(MenuEntry::Submenu(text.into(), menu)); self }
+
+	pub fn push_separator(mut self) -> Self
+		{ self.entries.push(MenuEntry::Separator); self }
+}
+
+/// Lives on a menu's popup.
+#[derive(Component, Debug)]
+pub struct MenuPopup
+{
+	/// The menu bar button, submenu item or context menu target that opens this menu.
+	pub opener: Entity,
+	/// The popup of the menu this one is a submenu of.
+	pub parent: Option<Entity>,
+	pub items: Vec<Entity>,
+	pub open: bool,
+	/// Where a context menu was opened, in logical pixels. Other menus are placed next to their opener.
+	pub position: Option<Vec2>,
+}
+
+/// Lives on menu bar buttons and submenu items.
+#[derive(Component, Debug)]
+pub struct MenuOpener
+{
+	pub popup: Entity,
+}
+
+/// Lives on a widget with a context menu.
+#[derive(Component, Debug)]
+pub struct ContextMenuTarget
+{
+	pub popup: Entity,
+}
+
+/// Lives on a menu item, change `checked` to check or uncheck it from code.
+#[derive(Component, Debug)]
+pub struct MenuItemState
+{
+	/// The popup the item is in.
+	pub popup: Entity,
+	pub action: String,
+	pub accelerator: Option<Accelerator>,
+	pub checked: Option<bool>,
+	pub disabled: bool,
+	/// The label showing the check mark.
+	pub check_label: Option<Entity>,
+}
+
+/// Lives on a menu bar.
+#[derive(Component, Debug)]
+pub struct MenuBarParts
+{
+	/// The button of each menu.
+	pub menus: Vec<Entity>,
+}
+
+/// Sent when a menu item is activated.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct MenuAction
+{
+	pub item: Entity,
+	pub action: String,
+	/// The new state of checkable items.
+	pub checked: Option<bool>,
+}
+
+/// The popup and the popups it is a submenu of, innermost first.
+fn popup_chain(popup: Entity, parent_of: impl Fn(Entity) -> Option<Option<Entity>>) -> Vec<Entity>
+{
+	let mut chain = vec![popup];
+	while let Some(Some(parent)) = parent_of(*chain.last().unwrap())
+	{
+		if chain.contains(&parent)
+			{ break; }
+		chain.push(parent);
+	}
+	chain
+}
+
+/// Items start closed, so they must not be reachable by navigation.
+pub fn init_menu_items
+(
+	mut item_query: Query<&mut Focusable, Added<MenuItemState>>,
+	mut commands: Commands,
+	context_query: Query<(Entity, &ContextMenuTarget), (Added<ContextMenuTarget>, With<Focusable>)>,
+)
+{
+	for mut focusable in item_query.iter_mut()
+	{
+		focusable.block();
+	}
+	// Context menus of focusable widgets are sub-menus of them, so cancelling goes back to the widget.
+	for (target, context_menu) in context_query.iter()
+	{
+		commands.entity(context_menu.popup)
+			.insert(MenuSetting::new().wrapping())
+			.insert(MenuBuilder::EntityParent(target));
+	}
+}
+
+/// Closes the innermost menu on Escape or the gamepad back button, a submenu on Left, and every menu on clicks outside of them.
+pub fn close_menus
+(
+	mut popup_query: Query<(Entity, &mut MenuPopup)>,
+	item_query: Query<(&Focusable, &MenuItemState)>,
+	interaction_query: Query<&Interaction, Or<(With<MenuItemState>, With<MenuOpener>)>>,
+	mut nav_requests: EventReader<NavRequest>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mouse_input: Res<ButtonInput<MouseButton>>,
+)
+{
+	let cancelled = keyboard_input.just_pressed(KeyCode::Escape)
+		|| nav_requests.read().any(|request| *request == NavRequest::Cancel);
+	let clicked = mouse_input.any_just_pressed([MouseButton::Left, MouseButton::Right]);
+	let mut closing = Vec::new();
+	if clicked && interaction_query.iter().all(|interaction| *interaction == Interaction::None)
+	{
+		closing.extend(popup_query.iter().filter(|(_, popup)| popup.open).map(|(entity, _)| entity));
+	}
+	else if cancelled
+	{
+		let innermost = popup_query.iter()
+			.filter(|(_, popup)| popup.open)
+			.max_by_key(|(entity, _)| popup_chain(*entity, |popup| popup_query.get(popup).ok().map(|(_, popup)| popup.parent)).len());
+		closing.extend(innermost.map(|(entity, _)| entity));
+	}
+	else if keyboard_input.just_pressed(KeyCode::ArrowLeft)
+	{
+		let focused_item = item_query.iter().find(|(focusable, _)| focusable.state() == FocusState::Focused);
+		if let Some((_, item)) = focused_item
+		{
+			if popup_query.get(item.popup).is_ok_and(|(_, popup)| popup.parent.is_some())
+				{ closing.push(item.popup); }
+		}
+	}
+	for popup in closing
+	{
+		if let Ok((_, mut popup)) = popup_query.get_mut(popup)
+			{ popup.open = false; }
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn open_menus
+(
+	opener_query: Query<(Entity, Ref<Interaction>, &MenuOpener)>,
+	context_query: Query<(Entity, &ContextMenuTarget, Option<&RelativeCursorPosition>)>,
+	item_query: Query<&MenuItemState>,
+	bar_query: Query<&MenuBarParts>,
+	focusable_query: Query<(Entity, &Focusable)>,
+	mut popup_query: Query<(Entity, &mut MenuPopup)>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mouse_input: Res<ButtonInput<MouseButton>>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let focused = focusable_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused).map(|(entity, _)| entity);
+	let mut opening: Vec<(Entity, Option<Vec2>)> = Vec::new();
+	for (entity, interaction, opener) in opener_query.iter()
+	{
+		if !interaction.is_changed() || *interaction != Interaction::Pressed
+			{ continue; }
+		match item_query.get(entity)
+		{
+			Ok(item) if item.disabled => {},
+			Ok(_) => opening.push((opener.popup, None)),
+			// Menu bar buttons toggle their menu.
+			Err(_) => match popup_query.get_mut(opener.popup)
+			{
+				Ok((_, mut popup)) if popup.open => popup.open = false,
+				_ => opening.push((opener.popup, None)),
+			},
+		}
+	}
+
+	// Right opens the focused submenu, or moves along the menu bar.
+	let left = keyboard_input.just_pressed(KeyCode::ArrowLeft);
+	let right = keyboard_input.just_pressed(KeyCode::ArrowRight);
+	let focused_item = focused.and_then(|focused| item_query.get(focused).ok().map(|item| (focused, item)));
+	if let (Some((focused, item)), true) = (focused_item, left || right)
+	{
+		let submenu = opener_query.get(focused).ok().map(|(_, _, opener)| opener.popup);
+		match submenu
+		{
+			Some(submenu) if right =>
+			{
+				if !item.disabled
+					{ opening.push((submenu, None)); }
+			},
+			_ =>
+			{
+				let bar_menu = popup_query.get(item.popup).ok()
+					.filter(|(_, popup)| popup.parent.is_none())
+					.and_then(|(_, popup)| bar_query.iter().find_map(|bar| bar.menus.iter().position(|menu| *menu == popup.opener).map(|index| (bar, index))));
+				if let Some((bar, index)) = bar_menu
+				{
+					let next = (if right { index + 1 } else { index + bar.menus.len() - 1 }) % bar.menus.len();
+					nav_writer.send(NavRequest::FocusOn(bar.menus[next]));
+				}
+			},
+		}
+	}
+
+	// Right-clicking a widget with a context menu, or pressing the menu key (or Shift+F10) while it's focused.
+	let cursor = window_query.get_single().ok().and_then(|window| window.cursor_position());
+	let menu_key = keyboard_input.just_pressed(KeyCode::ContextMenu)
+		|| (keyboard_input.just_pressed(KeyCode::F10) && keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]));
+	for (entity, context_menu, cursor_position) in context_query.iter()
+	{
+		if mouse_input.just_pressed(MouseButton::Right) && cursor_position.is_some_and(|cursor_position| cursor_position.mouse_over())
+			{ opening.push((context_menu.popup, Some(cursor.unwrap_or_default()))); }
+		else if menu_key && focused == Some(entity)
+			{ opening.push((context_menu.popup, None)); }
+	}
+
+	// Navigation can also move into a menu (e.g. activating a submenu item on a gamepad enters its sub-menu),
+	// and focusing another menu bar button while a menu is open switches to its menu.
+	let is_open = |popup: Entity| popup_query.get(popup).is_ok_and(|(_, popup)| popup.open);
+	let mut focus_target = None;
+	for event in nav_events.read()
+	{
+		let NavEvent::FocusChanged { to, .. } = event
+			else { continue; };
+		let to = *to.first();
+		focus_target = Some(to);
+		if let Ok(item) = item_query.get(to)
+			{ opening.push((item.popup, None)); }
+		let Ok((_, _, opener)) = opener_query.get(to)
+			else { continue; };
+		let bar_menu_open = bar_query.iter()
+			.find(|bar| bar.menus.contains(&to))
+			.is_some_and(|bar| bar.menus.iter().any(|menu| opener_query.get(*menu).is_ok_and(|(_, _, opener)| is_open(opener.popup))));
+		if bar_menu_open
+			{ opening.push((opener.popup, None)); }
+	}
+
+	let parent_of = |popup: Entity| popup_query.get(popup).ok().map(|(_, popup)| popup.parent);
+	let opening: Vec<(Vec<Entity>, Option<Vec2>)> = opening.into_iter().map(|(popup, position)| (popup_chain(popup, parent_of), position)).collect();
+	let mut keep: Vec<Entity> = opening.iter().flat_map(|(chain, _)| chain.iter().copied()).collect();
+	// Focusing a menu entry closes the menus it isn't in (e.g. the submenu of the previously focused item), as does opening a menu.
+	let focus_popup = focus_target.and_then(|to| item_query.get(to).ok()).map(|item| item.popup);
+	let own_popup = focus_target.and_then(|to| opener_query.get(to).ok()).map(|(_, _, opener)| opener.popup);
+	keep.extend(focus_popup.into_iter().flat_map(|popup| popup_chain(popup, parent_of)));
+	keep.extend(own_popup);
+	if !opening.is_empty() || focus_popup.is_some() || own_popup.is_some()
+	{
+		for (entity, mut popup) in popup_query.iter_mut()
+		{
+			if popup.open && !keep.contains(&entity)
+				{ popup.open = false; }
+		}
+	}
+
+	for (chain, position) in opening
+	{
+		for (index, entity) in chain.into_iter().enumerate()
+		{
+			let Ok((_, mut popup)) = popup_query.get_mut(entity)
+				else { continue; };
+			// Context menus reopened by navigation stay where they are.
+			if index == 0 && (!popup.open || position.is_some()) && popup.position != position
+				{ popup.position = position; }
+			if !popup.open
+				{ popup.open = true; }
+		}
+	}
+}
+
+/// Activates pressed items and items whose accelerator was pressed.
+pub fn activate_menu_items
+(
+	mut item_query: Query<(Entity, Ref<Interaction>, &mut MenuItemState), Without<MenuOpener>>,
+	mut popup_query: Query<&mut MenuPopup>,
+	focus_query: Query<(Entity, &Focusable)>,
+	text_input_query: Query<(), With<EditableText>>,
+	context_query: Query<(), With<ContextMenuTarget>>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut action_writer: EventWriter<MenuAction>,
+)
+{
+	let focused = focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused).map(|(entity, _)| entity);
+	let typing = focused.is_some_and(|focused| text_input_query.contains(focused));
+	// A context menu's shortcuts belong to its target, so they only apply while the target is focused or the menu is open.
+	let in_scope = |item: &MenuItemState|
+	{
+		let chain = popup_chain(item.popup, |popup| popup_query.get(popup).ok().map(|popup| popup.parent));
+		let Ok(root) = popup_query.get(*chain.last().unwrap())
+			else { return true; };
+		!context_query.contains(root.opener)
+			|| focused == Some(root.opener)
+			|| chain.iter().any(|popup| popup_query.get(*popup).is_ok_and(|popup| popup.open))
+	};
+	let mut activated = false;
+	for (entity, interaction, mut item) in item_query.iter_mut()
+	{
+		if item.disabled
+			{ continue; }
+		let pressed = interaction.is_changed() && *interaction == Interaction::Pressed;
+		// Unmodified shortcuts are left to the text input being typed in.
+		let accelerated = item.accelerator.is_some_and(|accelerator| accelerator.just_pressed(&keyboard_input) && !(typing && !accelerator.ctrl && !accelerator.alt))
+			&& in_scope(&item);
+		if !pressed && !accelerated
+			{ continue; }
+		if let Some(checked) = item.checked
+			{ item.checked = Some(!checked); }
+		action_writer.send(MenuAction { item: entity, action: item.action.clone(), checked: item.checked });
+		activated = true;
+	}
+	if !activated
+		{ return; }
+	for mut popup in popup_query.iter_mut()
+	{
+		if popup.open
+			{ popup.open = false; }
+	}
+}
+
+pub fn update_menu_checks
+(
+	item_query: Query<&MenuItemState, Changed<MenuItemState>>,
+	children_query: Query<&Children>,
+	mut text_query: Query<&mut Text>,
+)
+{
+	for item in item_query.iter()
+	{
+		let (Some(checked), Some(check_label)) = (item.checked, item.check_label)
+			else { continue; };
+		// The hierarchy is: TextLabel Container -> TextBundle.
+		let Some(mut text) = children_query.get(check_label).ok().and_then(|children| text_query.get_mut(children[0]).ok())
+			else { continue; };
+		let mark = if checked { "X" } else { "" };
+		if text.sections[0].value != mark
+			{ text.sections[0].value = mark.to_string(); }
+	}
+}
+
+/// Shows and hides popups, and moves the focus into opened menus and out of closed ones.
+pub fn apply_menu_state
+(
+	popup_query: Query<(Entity, Ref<MenuPopup>)>,
+	item_query: Query<&MenuItemState>,
+	mut style_query: Query<&mut Style>,
+	mut focusable_query: Query<(Entity, &mut Focusable)>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let parent_of = |popup: Entity| popup_query.get(popup).ok().map(|(_, popup)| popup.parent);
+	// The popups the focus is in.
+	let focused_chain = focusable_query.iter()
+		.find(|(_, focusable)| focusable.state() == FocusState::Focused)
+		.and_then(|(focused, _)| item_query.get(focused).ok())
+		.map_or(Vec::new(), |item| popup_chain(item.popup, parent_of));
+	for (entity, popup) in popup_query.iter()
+	{
+		if !popup.is_changed() || popup.is_added()
+			{ continue; }
+		if let Ok(mut style) = style_query.get_mut(entity)
+			{ style.display = if popup.open { Display::Flex } else { Display::None }; }
+		for item in popup.items.iter()
+		{
+			let disabled = item_query.get(*item).map_or(true, |item| item.disabled);
+			let Ok((_, mut focusable)) = focusable_query.get_mut(*item)
+				else { continue; };
+			let reachable = popup.open && !disabled;
+			let blocked = focusable.state() == FocusState::Blocked;
+			if reachable && blocked
+				{ focusable.unblock(); }
+			else if !reachable && !blocked
+				{ focusable.block(); }
+		}
+		let focus_inside = focused_chain.contains(&entity);
+		if popup.open && !focus_inside
+		{
+			let first_enabled = popup.items.iter().find(|item| item_query.get(**item).is_ok_and(|item| !item.disabled));
+			if let Some(first_enabled) = first_enabled
+				{ nav_writer.send(NavRequest::FocusOn(*first_enabled)); }
+		}
+		else if !popup.open && focus_inside
+		{
+			// Go back to the first opener that is still reachable.
+			let mut opener = popup.opener;
+			while let Some((_, opener_popup)) = item_query.get(opener).ok().and_then(|item| popup_query.get(item.popup).ok()).filter(|(_, popup)| !popup.open)
+				{ opener = opener_popup.opener; }
+			if focusable_query.contains(opener)
+				{ nav_writer.send(NavRequest::FocusOn(opener)); }
+		}
+	}
+}
+
+/// Places open menus below their menu bar button, beside their submenu item, or where their context menu was opened, inside the window.
+pub fn position_menu_popups
+(
+	mut popup_query: Query<(&MenuPopup, &Node, &mut Style)>,
+	opener_query: Query<(&Node, &GlobalTransform)>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+)
+{
+	let window_size = window_query.get_single().map_or(Vec2::INFINITY, |window| window.size());
+	for (popup, node, mut style) in popup_query.iter_mut()
+	{
+		if !popup.open
+			{ continue; }
+		let size = node.size();
+		let opener = opener_query.get(popup.opener).ok().map(|(node, transform)| node.logical_rect(transform));
+		let position = match (popup.position, popup.parent, opener)
+		{
+			(Some(position), _, _) => position,
+			(None, None, Some(opener)) => Vec2::new(opener.min.x, opener.max.y),
+			(None, Some(_), Some(opener)) if opener.max.x + size.x > window_size.x => Vec2::new(opener.min.x - size.x, opener.min.y),
+			(None, Some(_), Some(opener)) => Vec2::new(opener.max.x, opener.min.y),
+			(None, _, None) => Vec2::ZERO,
+		};
+		let position = position.min(window_size - size).max(Vec2::ZERO);
+		style.left = Val::Px(position.x);
+		style.top = Val::Px(position.y);
+	}
+}
+
+fn menu_label<U: Component + Default>(text: impl Into<String>, theme: Theme) -> TextLabel<U, ()>
+{
+	let mut label = TextLabel::new(text)
+		.with_paint_mode(PaintMode::Invisible)
+		.with_theme(theme)
+		.with_text_size(BASE_TEXT_SIZE);
+	label.container.node_bundle.style.width = Val::Auto;
+	label.container.node_bundle.style.height = Val::Auto;
+	label.label.text.justify = JustifyText::Left;
+	label
+}
+
+/// Builds `menu` as a hidden top-level popup opened by `opener`, and its submenus.
+#[allow(clippy::too_many_arguments)]
+fn build_menu_popup<U: Component + Default>
+(
+	menu: &mut Menu,
+	opener: Entity,
+	parent: Option<Entity>,
+	depth: i32,
+	ui_tree: &mut crate::UIHierarchy<U>,
+	theme_data: &ThemeData,
+	parent_data: ParentData,
+	commands: &mut Commands,
+) -> Entity
+{
+	let mut popup = Container::<U, ()>::new()
+		.with_direction(FlexDirection::Column)
+		.with_paint_mode(PaintMode::BackgroundContainer)
+		.with_padding(UiRect::all(Val::Px(4.0)));
+	let style = &mut popup.node_bundle.style;
+	style.position_type = PositionType::Absolute;
+	style.display = Display::None;
+	style.width = Val::Auto;
+	style.height = Val::Auto;
+	style.min_width = Val::Px(MENU_MIN_WIDTH);
+	style.align_items = AlignItems::Stretch;
+	popup.node_bundle.z_index = ZIndex::Global(OVERLAY_Z_INDEX + depth);
+	let popup_entity = popup.build(ui_tree, theme_data, parent_data, commands);
+	let item_parent_data = parent_data.from_current(popup.theme);
+
+	let mut children = Vec::new();
+	let mut items = Vec::new();
+	for entry in menu.entries.iter_mut()
+	{
+		let (item, submenu) = match entry
+		{
+			MenuEntry::Separator =>
+			{
+				let mut separator = Container::<U, ()>::new()
+					.with_paint_mode(PaintMode::Foreground)
+					.with_margin(UiRect::vertical(Val::Px(4.0)));
+				separator.node_bundle.style.height = Val::Px(1.0);
+				children.push(separator.build(ui_tree, theme_data, item_parent_data, commands));
+				continue;
+			},
+			MenuEntry::Item(item) => (item.clone(), None),
+			MenuEntry::Submenu(text, submenu) => (MenuItem::new(text.clone()), Some(submenu)),
+		};
+		// Disabled items are greyed out, and can't be focused.
+		let label_theme = if item.disabled
+			{ Theme::Custom(theme_data.disabled, theme_data.disabled_foreground) }
+		else
+			{ Theme::Auto };
+		let mut button = BaseButton::<U, ()>::new()
+			.with_auto_style(!item.disabled)
+			.with_direction(FlexDirection::Row)
+			.with_padding(UiRect::horizontal(Val::Px(8.0)));
+		if item.disabled
+			{ button = button.with_theme(label_theme); }
+		let style = &mut button.button_bundle.style;
+		style.height = Val::Px(MENU_ITEM_HEIGHT);
+		style.justify_content = JustifyContent::FlexStart;
+		style.column_gap = Val::Px(8.0);
+		let check_label = item.checked.map(|checked|
+		{
+			let mut check = menu_label::<U>(if checked { "X" } else { "" }, label_theme);
+			check.container.node_bundle.style.width = Val::Px(12.0);
+			check.build(ui_tree, theme_data, item_parent_data, commands)
+		});
+		let mut text = menu_label::<U>(item.text.clone(), label_theme);
+		text.container.node_bundle.style.flex_grow = 1.0;
+		let mut labels: Vec<Entity> = check_label.into_iter().collect();
+		labels.push(text.build(ui_tree, theme_data, item_parent_data, commands));
+		if let Some(accelerator) = item.accelerator
+			{ labels.push(menu_label::<U>(accelerator.label(), label_theme).build(ui_tree, theme_data, item_parent_data, commands)); }
+		if submenu.is_some()
+			{ labels.push(menu_label::<U>(">", label_theme).build(ui_tree, theme_data, item_parent_data, commands)); }
+		for label in labels
+			{ button = button.push(label); }
+		let item_entity = button.build(ui_tree, theme_data, item_parent_data, commands);
+		commands.entity(item_entity)
+			.insert(MenuItemState
+			{
+				popup: popup_entity,
+				action: item.action.clone(),
+				accelerator: item.accelerator,
+				checked: item.checked,
+				disabled: item.disabled,
+				check_label,
+			});
+		if let Some(submenu) = submenu
+		{
+			let submenu_popup = build_menu_popup(submenu, item_entity, Some(popup_entity), depth + 1, ui_tree, theme_data, parent_data, commands);
+			commands.entity(item_entity).insert(MenuOpener { popup: submenu_popup });
+		}
+		children.push(item_entity);
+		items.push(item_entity);
+	}
+
+	let mut popup_commands = commands.entity(popup_entity);
+	popup_commands
+		.push_children(&children)
+		.insert(MenuPopup { opener, parent, items, open: false, position: None });
+	// Top-level menus are registered by what builds them, context menus only once it's known if their target is focusable.
+	if parent.is_some()
+	{
+		popup_commands
+			.insert(MenuSetting::new().wrapping())
+			.insert(MenuBuilder::EntityParent(opener));
+	}
+	popup_entity
+}
+
+/// A row of buttons that each open a menu.
+pub struct MenuBar<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub row: Row<U, M>,
+	pub menus: Vec<(String, Menu)>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> MenuBar<U, M>
+{
+	pub fn new() -> Self
+	{
+		let mut row = Row::new()
+			.with_paint_mode(PaintMode::BackgroundContainer);
+		let style = &mut row.container.node_bundle.style;
+		style.height = Val::Px(MENU_BAR_HEIGHT);
+		style.justify_content = JustifyContent::FlexStart;
+		Self
+		{
+			row,
+			menus: Vec::new(),
+		}
+	}
+
+	pub fn push_menu(mut self, text: impl Into<String>, menu: Menu) -> Self
+		{ self.menus.push((text.into(), menu)); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for MenuBar<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.row = self.row.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.row = self.row.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.row = self.row.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.row = self.row.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.row = self.row.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.row = self.row.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.row = self.row.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.row = self.row.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.row = self.row.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.row = self.row.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.row = self.row.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.row = self.row.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for MenuBar<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		// Build the buttons, themed as if they were inside the bar.
+		let button_parent_data = parent_data.from_current(self.row.container.theme);
+		let buttons: Vec<Entity> = self.menus
+			.iter()
+			.map(|(text, _)|
+			{
+				let mut button = TextButton::<U, ()>::new(text.clone())
+					.with_paint_mode(PaintMode::BackgroundContainer)
+					.with_padding(UiRect::horizontal(Val::Px(12.0)));
+				button.base_button.button_bundle.style.width = Val::Auto;
+				button.label = button.label.with_text_size(BASE_TEXT_SIZE);
+				button.label.container.node_bundle.style.width = Val::Auto;
+				button.label.container.node_bundle.style.height = Val::Auto;
+				button.build(ui_tree, theme_data, button_parent_data, commands)
+			})
+			.collect();
+		for button in buttons.iter()
+			{ self.row.container.children.push((*button).into()); }
+		let bar_entity = self.row.build(ui_tree, theme_data, parent_data, commands);
+		self.row.container.children.clear();
+
+		// Build the menus as top-level nodes.
+		for ((_, menu), button) in self.menus.iter_mut().zip(buttons.iter())
+		{
+			let popup = build_menu_popup(menu, *button, None, 0, ui_tree, theme_data, parent_data, commands);
+			commands.entity(popup)
+				.insert(MenuSetting::new().wrapping())
+				.insert(MenuBuilder::EntityParent(*button));
+			commands.entity(*button).insert(MenuOpener { popup });
+		}
+		commands.entity(bar_entity).insert(MenuBarParts { menus: buttons });
+		bar_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for MenuBar<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+/// A widget with a context menu, see WithContextMenu::with_context_menu.
+pub struct ContextMenu<U, W>
+	where U: Component + Default, W: WidgetBuilder<U>
+{
+	pub widget: W,
+	pub menu: Menu,
+	phantom: std::marker::PhantomData<U>,
+}
+
+pub trait WithContextMenu<U>
+	where U: Component + Default, Self: WidgetBuilder<U> + Sized
+{
+	/// Opens `menu` where the widget is right-clicked, or below it when the menu key is pressed while it's focused.
+	fn with_context_menu(self, menu: Menu) -> ContextMenu<U, Self>;
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U>> WithContextMenu<U> for W
+{
+	fn with_context_menu(self, menu: Menu) -> ContextMenu<U, Self>
+	{
+		ContextMenu { widget: self, menu, phantom: std::marker::PhantomData }
+	}
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U> + Widget> Widget for ContextMenu<U, W>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.widget = self.widget.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.widget = self.widget.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.widget = self.widget.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.widget = self.widget.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.widget = self.widget.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.widget = self.widget.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.widget = self.widget.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.widget = self.widget.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.widget = self.widget.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.widget = self.widget.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.widget = self.widget.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.widget = self.widget.with_theme(theme); self }
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U>> WidgetBuilder<U> for ContextMenu<U, W>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let widget_entity = self.widget.build(ui_tree, theme_data, parent_data, commands);
+		let popup_entity = build_menu_popup(&mut self.menu, widget_entity, None, 0, ui_tree, theme_data, parent_data, commands);
+		commands.entity(widget_entity)
+			.insert(RelativeCursorPosition::default())
+			.insert(ContextMenuTarget { popup: popup_entity });
+		widget_entity
+	}
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U> + 'static> Into<Box<dyn WidgetBuilder<U>>> for ContextMenu<U, W>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, focused, press_key, press};
+
+	fn build_menus(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		let file = Menu::new()
+			.push(MenuItem::new("New").with_accelerator(Accelerator::ctrl(KeyCode::KeyN)))
+			.push_submenu("Recent", Menu::new().push(MenuItem::new("level.ron")))
+			.push_separator()
+			.push(MenuItem::new("Autosave").with_checked(false))
+			.push(MenuItem::new("Export").with_disabled(true));
+		MenuBar::<TestUI>::new()
+			.push_menu("File", file)
+			.push_menu("Edit", Menu::new().push(MenuItem::new("Undo")))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		TextButton::<TestUI>::new("Entity")
+			.with_context_menu(Menu::new().push(MenuItem::new("Delete").with_accelerator(Accelerator::new(KeyCode::Delete))))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	fn item(app: &mut App, action: &str) -> Entity
+	{
+		app.world_mut().query::<(Entity, &MenuItemState)>().iter(app.world())
+			.find(|(_, item)| item.action == action)
+			.map(|(entity, _)| entity)
+			.unwrap()
+	}
+
+	fn is_open(app: &App, popup: Entity) -> bool
+	{
+		app.world().get::<MenuPopup>(popup).unwrap().open
+	}
+
+	fn actions(app: &App) -> Vec<MenuAction>
+	{
+		let events = app.world().resource::<Events<MenuAction>>();
+		events.get_reader().read(events).cloned().collect()
+	}
+
+	#[test]
+	fn menu_bars_open_submenus_and_activate_items()
+	{
+		assert_eq!(Accelerator::ctrl(KeyCode::KeyS).with_shift().label(), "Ctrl+Shift+S");
+
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_menus);
+		app.update();
+		let file = app.world_mut().query::<&MenuBarParts>().single(app.world()).menus[0];
+		let file_popup = app.world().get::<MenuOpener>(file).unwrap().popup;
+		assert_eq!(app.world().get::<ZIndex>(file_popup), Some(&ZIndex::Global(OVERLAY_Z_INDEX)));
+
+		// Pressing the bar button opens its menu and focuses the first item.
+		press(&mut app, file);
+		assert!(is_open(&app, file_popup));
+		assert_eq!(app.world().get::<Style>(file_popup).unwrap().display, Display::Flex);
+		assert_eq!(focused(&mut app), Some(item(&mut app, "New")));
+
+		// Right opens the submenu, Left closes it again.
+		let recent = item(&mut app, "Recent");
+		let recent_popup = app.world().get::<MenuOpener>(recent).unwrap().popup;
+		app.world_mut().send_event(NavRequest::FocusOn(recent));
+		app.update();
+		press_key(&mut app, KeyCode::ArrowRight);
+		app.update();
+		assert!(is_open(&app, recent_popup));
+		assert_eq!(focused(&mut app), Some(item(&mut app, "level.ron")));
+		press_key(&mut app, KeyCode::ArrowLeft);
+		app.update();
+		assert!(!is_open(&app, recent_popup));
+		assert!(is_open(&app, file_popup));
+		assert_eq!(focused(&mut app), Some(recent));
+
+		// Disabled items can't be focused or activated.
+		let export = item(&mut app, "Export");
+		assert_eq!(app.world().get::<Focusable>(export).unwrap().state(), FocusState::Blocked);
+		*app.world_mut().get_mut::<Interaction>(export).unwrap() = Interaction::Pressed;
+		app.update();
+		assert!(actions(&app).is_empty());
+
+		// Checkable items toggle, and activating an item closes the menus.
+		let autosave = item(&mut app, "Autosave");
+		*app.world_mut().get_mut::<Interaction>(autosave).unwrap() = Interaction::Pressed;
+		app.update();
+		assert_eq!(actions(&app), vec![MenuAction { item: autosave, action: "Autosave".to_string(), checked: Some(true) }]);
+		assert!(!is_open(&app, file_popup));
+		app.update();
+		assert_eq!(focused(&mut app), Some(file));
+
+		// Accelerators work with the menus closed.
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ControlLeft);
+		press_key(&mut app, KeyCode::KeyN);
+		assert!(actions(&app).iter().any(|action| action.action == "New"));
+	}
+
+	#[test]
+	fn context_menus_open_on_right_click_and_close_on_escape()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_menus);
+		app.update();
+		let (target, popup) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &ContextMenuTarget)>();
+			let (entity, context_menu) = query.single(app.world());
+			(entity, context_menu.popup)
+		};
+		app.world_mut().send_event(NavRequest::FocusOn(target));
+		app.update();
+
+		*app.world_mut().get_mut::<RelativeCursorPosition>(target).unwrap() = RelativeCursorPosition
+		{
+			normalized_visible_node_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+			normalized: Some(Vec2::splat(0.5)),
+		};
+		app.world_mut().resource_mut::<ButtonInput<MouseButton>>().press(MouseButton::Right);
+		app.update();
+		app.world_mut().resource_mut::<ButtonInput<MouseButton>>().reset_all();
+		app.update();
+		assert!(is_open(&app, popup));
+		assert_eq!(focused(&mut app), Some(item(&mut app, "Delete")));
+
+		press_key(&mut app, KeyCode::Escape);
+		app.update();
+		assert!(!is_open(&app, popup));
+		assert_eq!(focused(&mut app), Some(target));
+	}
+
+	fn build_context_targets(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		for name in ["Tree", "Rock"]
+		{
+			TextButton::<TestUI>::new(name)
+				.with_context_menu(Menu::new().push(MenuItem::new("Delete").with_accelerator(Accelerator::new(KeyCode::Delete))))
+				.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		}
+		TextButton::<TestUI>::new("Elsewhere")
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn context_menu_accelerators_only_apply_to_their_target()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_context_targets);
+		app.update();
+		let targets: Vec<(Entity, Entity)> = app.world_mut().query::<(Entity, &ContextMenuTarget)>().iter(app.world())
+			.map(|(entity, context_menu)| (entity, context_menu.popup))
+			.collect();
+		let elsewhere = app.world_mut().query_filtered::<Entity, (With<Focusable>, Without<ContextMenuTarget>, Without<MenuItemState>)>().single(app.world());
+		let popups_of = |app: &App| actions(app).iter().map(|action| app.world().get::<MenuItemState>(action.item).unwrap().popup).collect::<Vec<_>>();
+
+		// Only the focused target's item is activated.
+		app.world_mut().send_event(NavRequest::FocusOn(targets[1].0));
+		app.update();
+		press_key(&mut app, KeyCode::Delete);
+		assert_eq!(popups_of(&app), vec![targets[1].1]);
+		app.update();
+
+		// Nothing happens while neither target is focused and their menus are closed.
+		app.world_mut().send_event(NavRequest::FocusOn(elsewhere));
+		app.update();
+		press_key(&mut app, KeyCode::Delete);
+		assert!(actions(&app).is_empty());
+		app.update();
+
+		// An open menu's shortcuts apply too.
+		app.world_mut().get_mut::<MenuPopup>(targets[0].1).unwrap().open = true;
+		app.update();
+		press_key(&mut app, KeyCode::Delete);
+		assert_eq!(popups_of(&app), vec![targets[0].1]);
+	}
+}
@@ -19,9 +19,19 @@ pub use row::*;
 
 pub const BASE_TEXT_SIZE: f32 = 16f32;
 
+/// The global Z index of popups and overlays (dropdown lists, etc.), so they are drawn above the rest of the UI.
+pub const OVERLAY_Z_INDEX: i32 = 1000;
+/// The global Z index of the first open modal, nested modals are drawn one above the other, and below popups so dropdowns work inside dialogs.
+pub const MODAL_Z_INDEX: i32 = 500;
+/// The global Z index of toasts, which are drawn above everything else.
+pub const TOAST_Z_INDEX: i32 = 2000;
+
 pub mod text_label;
 pub use text_label::*;
 
+pub mod rich_text;
+pub use rich_text::*;
+
 pub mod text_input;
 pub use text_input::*;
 
@@ -37,6 +47,60 @@ pub use checkbox::*;
 pub mod dropdown;
 pub use dropdown::*;
 
+pub mod slider;
+pub use slider::*;
+
+pub mod radio;
+pub use radio::*;
+
+pub mod switch;
+pub use switch::*;
+
+pub mod progress;
+pub use progress::*;
+
+pub mod scroll_view;
+pub use scroll_view::*;
+
+pub mod list_view;
+pub use list_view::*;
+
+pub mod tabs;
+pub use tabs::*;
+
+pub mod modal;
+pub use modal::*;
+
+pub mod tooltip;
+pub use tooltip::*;
+
+pub mod image_widget;
+pub use image_widget::*;
+
+pub mod icon_button;
+pub use icon_button::*;
+
+pub mod grid;
+pub use grid::*;
+
+pub mod number_input;
+pub use number_input::*;
+
+pub mod text_area;
+pub use text_area::*;
+
+pub mod toast;
+pub use toast::*;
+
+pub mod menu;
+pub use menu::*;
+
+pub mod tree_view;
+pub use tree_view::*;
+
+pub mod table;
+pub use table::*;
+
 use crate::{theme::{PaintMode, Theme}, UIOwner};
 
 // pub fn compute_val(val: Val, parent_size: f32) -> f32
@@ -94,6 +158,9 @@ pub fn ensure_aspect_ratio
 	for (AspectRatio(aspect_ratio), mut style, node) in query.iter_mut()
 	{
 		let size = node.size();
+		// Nodes that haven't been laid out yet have no size to keep a ratio of.
+		if size.x == 0f32 || size.y == 0f32
+			{ continue; }
 		use std::cmp::Ordering;
 		match size.x.partial_cmp(&size.y).unwrap()
 		{
@@ -208,11 +275,25 @@ impl<U: Component + Default> WidgetBuilder<U> for Entity
 	}
 }
 
+/// Allows already built entities to be pushed into containers.
+impl<U: Component + Default> From<Entity> for Box<dyn WidgetBuilder<U>>
+{
+	fn from(entity: Entity) -> Self
+	{
+		Box::new(entity)
+	}
+}
+
 pub trait Widget
 	where Self: Sized
 {
 	fn with_paint_mode(self, paint_mode: PaintMode) -> Self;
 	fn with_colour(self, background: Color, foreground: Color) -> Self;
+	/// Like `with_colour`, but picks a readable foreground (and the container colours) from the background.
+	fn with_background(self, background: Color) -> Self
+	{
+		self.with_theme(Theme::CustomBackground(background))
+	}
 	fn with_border(self, border: UiRect) -> Self;
 	/// Note a few important things:
 	///
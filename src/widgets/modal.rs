@@ -0,0 +1,436 @@
+/*
+	A modal is a full-screen scrim (a translucent Container with a global Z index) holding a centred dialog Container.
+	It is meant to be built as a top-level node at runtime, e.g. from a system reacting to a button press, and despawns itself when closed.
+	When it opens, trap_focus_in_modals blocks every focusable outside the dialog, makes the dialog a wrapping sub-menu of the previously focused entity and focuses into it.
+	Closing (Escape, the gamepad back button, a CloseModal event or a ConfirmDialog button) unblocks what was blocked and focuses the previously focused entity again.
+*/
+
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::ThemeData;
+
+const SCRIM_COLOUR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// Lives on the modal's scrim.
+#[derive(Component)]
+pub struct ModalParts
+{
+	pub dialog: Entity,
+	pub initial_focus: Option<Entity>,
+}
+
+/// Added to the scrim once the modal has opened, so it can be undone when it closes.
+#[derive(Component)]
+pub struct ModalFocusTrap
+{
+	/// The entity that was focused when the modal opened.
+	pub restore_focus: Option<Entity>,
+	/// The focusables blocked by this modal.
+	pub blocked: Vec<Entity>,
+	/// How many modals were already open below this one.
+	pub layer: usize,
+}
+
+/// Lives on the modal of a ConfirmDialog.
+#[derive(Component)]
+pub struct ConfirmDialogParts
+{
+	pub confirm: Entity,
+	pub cancel: Entity,
+}
+
+/// Lives on the buttons of a ConfirmDialog.
+#[derive(Component)]
+pub struct ConfirmDialogButton
+{
+	pub modal: Entity,
+	pub confirmed: bool,
+}
+
+/// Send this to close a modal from code.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CloseModal
+{
+	pub modal: Entity,
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModalClosed
+{
+	pub modal: Entity,
+}
+
+/// Sent when a ConfirmDialog closes, closing it without pressing either button counts as cancelling.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfirmDialogResult
+{
+	pub dialog: Entity,
+	pub confirmed: bool,
+}
+
+pub fn trap_focus_in_modals
+(
+	mut commands: Commands,
+	mut new_modal_query: Query<(Entity, &ModalParts, &mut ZIndex), Without<ModalFocusTrap>>,
+	open_modal_query: Query<(), With<ModalFocusTrap>>,
+	mut focusable_query: Query<(Entity, &mut Focusable)>,
+	parent_query: Query<&Parent>,
+	children_query: Query<&Children>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let mut layer = open_modal_query.iter().count();
+	for (entity, parts, mut z_index) in new_modal_query.iter_mut()
+	{
+		let in_dialog = |focusable: Entity| parent_query.iter_ancestors(focusable).any(|ancestor| ancestor == parts.dialog);
+		let restore_focus = focusable_query
+			.iter()
+			.find(|(focusable, state)| state.state() == FocusState::Focused && !in_dialog(*focusable))
+			.map(|(focusable, _)| focusable);
+		let mut blocked = Vec::new();
+		for (focusable, mut state) in focusable_query.iter_mut()
+		{
+			if Some(focusable) == restore_focus || in_dialog(focusable) || state.state() == FocusState::Blocked
+				{ continue; }
+			if state.block()
+				{ blocked.push(focusable); }
+		}
+
+		// Cancelling inside the dialog then goes back to where the focus was.
+		if let Some(restore_focus) = restore_focus
+		{
+			commands.entity(parts.dialog)
+				.insert(MenuSetting::new().wrapping())
+				.insert(MenuBuilder::EntityParent(restore_focus));
+		}
+		let initial_focus = parts.initial_focus
+			.or_else(|| children_query.iter_descendants(parts.dialog).find(|descendant| focusable_query.contains(*descendant)));
+		if let Some(initial_focus) = initial_focus
+			{ nav_writer.send(NavRequest::FocusOn(initial_focus)); }
+
+		*z_index = ZIndex::Global(MODAL_Z_INDEX + layer as i32);
+		commands.entity(entity).insert(ModalFocusTrap { restore_focus, blocked, layer });
+		layer += 1;
+	}
+}
+
+/// Closes the topmost modal on Escape or the gamepad back button, and any modal a CloseModal event or ConfirmDialog button asks for.
+#[allow(clippy::too_many_arguments)]
+pub fn close_modals
+(
+	mut commands: Commands,
+	modal_query: Query<(Entity, &ModalFocusTrap, Option<&ConfirmDialogParts>)>,
+	button_query: Query<(&Interaction, &ConfirmDialogButton), Changed<Interaction>>,
+	dropdown_query: Query<&DropdownMenu>,
+	menu_query: Query<&MenuPopup>,
+	mut focusable_query: Query<&mut Focusable>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	// Reads the cancel requests, then sends the focus restoring ones.
+	mut nav_requests: ParamSet<(EventReader<NavRequest>, EventWriter<NavRequest>)>,
+	mut close_reader: EventReader<CloseModal>,
+	mut closed_writer: EventWriter<ModalClosed>,
+	mut result_writer: EventWriter<ConfirmDialogResult>,
+)
+{
+	let mut closing: Vec<(Entity, bool)> = Vec::new();
+	let cancelled = keyboard_input.just_pressed(KeyCode::Escape)
+		|| nav_requests.p0().read().any(|request| *request == NavRequest::Cancel);
+	// An open dropdown or menu is closed first.
+	if cancelled && !dropdown_query.iter().any(|menu| menu.open) && !menu_query.iter().any(|popup| popup.open)
+	{
+		if let Some((modal, _, _)) = modal_query.iter().max_by_key(|(_, trap, _)| trap.layer)
+			{ closing.push((modal, false)); }
+	}
+	for (interaction, button) in button_query.iter()
+	{
+		if *interaction == Interaction::Pressed
+			{ closing.push((button.modal, button.confirmed)); }
+	}
+	closing.extend(close_reader.read().map(|event| (event.modal, false)));
+
+	let mut closed = Vec::new();
+	for (modal, confirmed) in closing
+	{
+		if closed.contains(&modal)
+			{ continue; }
+		let Ok((_, trap, confirm_parts)) = modal_query.get(modal)
+			else { continue; };
+		for blocked in trap.blocked.iter()
+		{
+			if let Ok(mut focusable) = focusable_query.get_mut(*blocked)
+				{ focusable.unblock(); }
+		}
+		if let Some(restore_focus) = trap.restore_focus.filter(|entity| focusable_query.contains(*entity))
+			{ nav_requests.p1().send(NavRequest::FocusOn(restore_focus)); }
+		if confirm_parts.is_some()
+			{ result_writer.send(ConfirmDialogResult { dialog: modal, confirmed }); }
+		closed_writer.send(ModalClosed { modal });
+		commands.entity(modal).despawn_recursive();
+		closed.push(modal);
+	}
+}
+
+pub struct Modal<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	/// The translucent full-screen node, it blocks the mouse from reaching the UI below.
+	pub scrim: Container<U, M>,
+	pub dialog: Container<U, ()>,
+	/// The entity to focus when the modal opens, defaults to the first focusable in the dialog.
+	pub initial_focus: Option<Entity>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Modal<U, M>
+{
+	pub fn new() -> Self
+	{
+		let mut scrim = Container::new()
+			.with_paint_mode(PaintMode::Background)
+			.with_theme(Theme::CustomBackground(SCRIM_COLOUR));
+		scrim.node_bundle.style.position_type = PositionType::Absolute;
+		scrim.node_bundle.style.left = Val::Px(0.0);
+		scrim.node_bundle.style.top = Val::Px(0.0);
+		scrim.node_bundle.focus_policy = bevy::ui::FocusPolicy::Block;
+		scrim.node_bundle.z_index = ZIndex::Global(MODAL_Z_INDEX);
+		let mut dialog = Container::new()
+			.with_direction(FlexDirection::Column)
+			.with_paint_mode(PaintMode::Background)
+			.with_padding(UiRect::all(Val::Percent(1.0)));
+		dialog.node_bundle.style.width = Val::Percent(50.0);
+		dialog.node_bundle.style.height = Val::Percent(50.0);
+		dialog.node_bundle.focus_policy = bevy::ui::FocusPolicy::Block;
+		Self
+		{
+			scrim,
+			dialog,
+			initial_focus: None,
+		}
+	}
+
+	pub fn push(mut self, child: impl Into<Box<dyn WidgetBuilder<U>>>) -> Self
+		{ self.dialog = self.dialog.push(child); self }
+
+	/// Sets the size of the dialog, it's half of the window in both directions by default.
+	pub fn with_size(mut self, width: Val, height: Val) -> Self
+	{
+		self.dialog.node_bundle.style.width = width;
+		self.dialog.node_bundle.style.height = height;
+		self
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Modal<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.dialog = self.dialog.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.dialog = self.dialog.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.dialog = self.dialog.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.dialog = self.dialog.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.dialog = self.dialog.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.dialog = self.dialog.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.dialog = self.dialog.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.dialog = self.dialog.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.dialog = self.dialog.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.dialog = self.dialog.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.dialog = self.dialog.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.dialog = self.dialog.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Modal<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		// The dialog is themed as if the scrim wasn't there.
+		let dialog_entity = self.dialog.build(ui_tree, theme_data, parent_data, commands);
+		self.scrim.children.push(dialog_entity.into());
+		let scrim_entity = self.scrim.build(ui_tree, theme_data, parent_data, commands);
+		self.scrim.children.clear();
+
+		commands.entity(scrim_entity)
+			.insert(ModalParts { dialog: dialog_entity, initial_focus: self.initial_focus });
+		scrim_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Modal<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+/// A modal with a message and two buttons, closing it sends a ConfirmDialogResult.
+pub struct ConfirmDialog<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub modal: Modal<U, M>,
+	pub message: String,
+	pub confirm_text: String,
+	pub cancel_text: String,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> ConfirmDialog<U, M>
+{
+	pub fn new(message: impl Into<String>) -> Self
+	{
+		Self
+		{
+			modal: Modal::new()
+				.with_size(Val::Percent(40.0), Val::Percent(25.0)),
+			message: message.into(),
+			confirm_text: "OK".into(),
+			cancel_text: "Cancel".into(),
+		}
+	}
+
+	pub fn with_confirm_text(mut self, text: impl Into<String>) -> Self
+		{ self.confirm_text = text.into(); self }
+
+	pub fn with_cancel_text(mut self, text: impl Into<String>) -> Self
+		{ self.cancel_text = text.into(); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for ConfirmDialog<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.modal = self.modal.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.modal = self.modal.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.modal = self.modal.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.modal = self.modal.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.modal = self.modal.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.modal = self.modal.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.modal = self.modal.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.modal = self.modal.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.modal = self.modal.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.modal = self.modal.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.modal = self.modal.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.modal = self.modal.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for ConfirmDialog<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let dialog_parent_data = parent_data.from_current(self.modal.dialog.theme);
+		let message_entity = TextLabel::<U, ()>::new(self.message.clone())
+			.with_paint_mode(PaintMode::Invisible)
+			.with_fill_portion(0.6)
+			.build(ui_tree, theme_data, dialog_parent_data, commands);
+		let cancel_entity = TextButton::<U, ()>::new(self.cancel_text.clone())
+			.with_margin(UiRect::all(Val::Percent(2.0)))
+			.build(ui_tree, theme_data, dialog_parent_data, commands);
+		let confirm_entity = TextButton::<U, ()>::new(self.confirm_text.clone())
+			.with_theme(Theme::Primary)
+			.with_margin(UiRect::all(Val::Percent(2.0)))
+			.build(ui_tree, theme_data, dialog_parent_data, commands);
+		let buttons_entity = Row::<U, ()>::new()
+			.with_paint_mode(PaintMode::Invisible)
+			.with_fill_portion(0.4)
+			.push(cancel_entity)
+			.push(confirm_entity)
+			.build(ui_tree, theme_data, dialog_parent_data, commands);
+
+		// Focus the harmless choice first.
+		self.modal.initial_focus = Some(cancel_entity);
+		self.modal.dialog.children.push(message_entity.into());
+		self.modal.dialog.children.push(buttons_entity.into());
+		let modal_entity = self.modal.build(ui_tree, theme_data, parent_data, commands);
+		self.modal.dialog.children.clear();
+		self.modal.initial_focus = None;
+
+		commands.entity(cancel_entity).insert(ConfirmDialogButton { modal: modal_entity, confirmed: false });
+		commands.entity(confirm_entity).insert(ConfirmDialogButton { modal: modal_entity, confirmed: true });
+		commands.entity(modal_entity).insert(ConfirmDialogParts { confirm: confirm_entity, cancel: cancel_entity });
+		modal_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for ConfirmDialog<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use bevy::ecs::system::RunSystemOnce;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_button(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		TextButton::<TestUI>::new("Quit")
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	fn build_dialog(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		ConfirmDialog::<TestUI>::new("Are you sure you want to quit?")
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn focus_is_trapped_and_restored()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_button);
+		app.update();
+		let quit_button =
+		{
+			let mut query = app.world_mut().query_filtered::<Entity, With<Focusable>>();
+			query.single(app.world())
+		};
+		app.world_mut().send_event(NavRequest::FocusOn(quit_button));
+		app.update();
+
+		app.world_mut().run_system_once(build_dialog);
+		app.update();
+		app.update();
+		let (modal, cancel) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &ConfirmDialogParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.cancel)
+		};
+		assert_eq!(app.world().get::<Focusable>(cancel).unwrap().state(), FocusState::Focused);
+		assert_ne!(app.world().get::<Focusable>(quit_button).unwrap().state(), FocusState::Focused);
+		assert_eq!(app.world().get::<ModalFocusTrap>(modal).unwrap().restore_focus, Some(quit_button));
+
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Escape);
+		app.update();
+		let results: Vec<ConfirmDialogResult> = app.world_mut().resource_mut::<Events<ConfirmDialogResult>>().drain().collect();
+		assert_eq!(results, [ConfirmDialogResult { dialog: modal, confirmed: false }]);
+		assert!(app.world().get_entity(modal).is_none());
+		app.update();
+		assert_eq!(app.world().get::<Focusable>(quit_button).unwrap().state(), FocusState::Focused);
+	}
+}
@@ -0,0 +1,510 @@
+/*
+	A number input is a Row holding a decrement TextButton, a TextInput that only accepts characters numbers are made of, and an increment TextButton.
+	The typed value lives in NumberValue<T> on the row. Text that parses to a number inside the range updates it as it is typed,
+	anything else is left alone while the input is focused, and replaced by the last valid value once it isn't.
+	The buttons step the value when pressed and repeat while held, the mouse wheel steps it while hovered, and the d-pad steps it while the input is focused.
+	The systems are generic over the number type, UIEventsPlugin adds them for the common ones, add number_input_systems::<T>() for any other.
+*/
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use bevy::ecs::schedule::SystemConfigs;
+use bevy::input::gamepad::Gamepads;
+use bevy::input::mouse::MouseWheel;
+use bevy::ui::RelativeCursorPosition;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::ThemeData;
+
+/// How long a button must be held before it starts repeating, in seconds.
+const HOLD_DELAY: f32 = 0.4;
+/// How often a held button steps the value, in seconds.
+const HOLD_REPEAT: f32 = 0.08;
+
+/// The number types a NumberInput can edit.
+pub trait Num: Copy + PartialOrd + FromStr + Display + Send + Sync + 'static
+{
+	const ONE: Self;
+	const SIGNED: bool;
+	const INTEGER: bool;
+	/// Adds, saturating at the type's bounds.
+	fn step_up(self, step: Self) -> Self;
+	/// Subtracts, saturating at the type's bounds.
+	fn step_down(self, step: Self) -> Self;
+}
+
+macro_rules! impl_num_integer
+{
+	($signed:expr; $($number:ty),*) =>
+	{
+		$(
+			impl Num for $number
+			{
+				const ONE: Self = 1;
+				const SIGNED: bool = $signed;
+				const INTEGER: bool = true;
+				fn step_up(self, step: Self) -> Self { self.saturating_add(step) }
+				fn step_down(self, step: Self) -> Self { self.saturating_sub(step) }
+			}
+		)*
+	};
+}
+
+impl_num_integer!(true; i8, i16, i32, i64, isize);
+impl_num_integer!(false; u8, u16, u32, u64, usize);
+
+macro_rules! impl_num_float
+{
+	($($number:ty),*) =>
+	{
+		$(
+			impl Num for $number
+			{
+				const ONE: Self = 1.0;
+				const SIGNED: bool = true;
+				const INTEGER: bool = false;
+				fn step_up(self, step: Self) -> Self { (self + step).round_to(decimal_places(&self).max(decimal_places(&step))) }
+				fn step_down(self, step: Self) -> Self { (self - step).round_to(decimal_places(&self).max(decimal_places(&step))) }
+			}
+
+			impl RoundFloat for $number
+			{
+				fn round_to(self, places: i32) -> Self
+				{
+					let scale = (10.0 as $number).powi(places);
+					let rounded = (self * scale).round() / scale;
+					if rounded.is_finite() { rounded } else { self }
+				}
+			}
+		)*
+	};
+}
+
+/// Steps like 0.1 aren't exact in binary, so float steps are rounded to the decimal places of the value and step.
+/// Otherwise the error adds up, and the input ends up showing text like "0.70000005".
+trait RoundFloat
+{
+	fn round_to(self, places: i32) -> Self;
+}
+
+/// The decimal places of the shortest text that reads back as this number, at most 12.
+fn decimal_places(number: &impl Display) -> i32
+{
+	number.to_string().split_once('.').map_or(0, |(_, decimals)| decimals.len().min(12) as i32)
+}
+
+impl_num_float!(f32, f64);
+
+/// Lives on the number input's row.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct NumberValue<T: Num>
+{
+	pub value: T,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct NumberRange<T: Num>
+{
+	pub min: Option<T>,
+	pub max: Option<T>,
+	pub step: T,
+}
+
+impl<T: Num> NumberRange<T>
+{
+	pub fn clamp(&self, value: T) -> T
+	{
+		match (self.min, self.max)
+		{
+			(Some(min), _) if value < min => min,
+			(_, Some(max)) if value > max => max,
+			_ => value,
+		}
+	}
+
+	/// Parses `text`, if it is a number inside the range.
+	pub fn parse(&self, text: &str) -> Option<T>
+	{
+		text.trim().parse::<T>().ok()
+			.filter(|value| self.clamp(*value) == *value)
+	}
+
+	/// The value after `steps` steps, negative steps go down.
+	pub fn step(&self, value: T, steps: i32) -> T
+	{
+		let mut value = value;
+		for _ in 0..steps.unsigned_abs()
+		{
+			value = if steps > 0 { value.step_up(self.step) } else { value.step_down(self.step) };
+		}
+		self.clamp(value)
+	}
+}
+
+/// Lives on the number input's row.
+#[derive(Component)]
+pub struct NumberInputParts
+{
+	pub text_input: Entity,
+	pub decrement: Entity,
+	pub increment: Entity,
+}
+
+/// Lives on a number input's increment and decrement buttons.
+#[derive(Component)]
+pub struct NumberStepButton
+{
+	pub number_input: Entity,
+	pub increase: bool,
+	/// How long the button has been held with the mouse for.
+	pub held_for: f32,
+}
+
+/// Lives on a number input's text input.
+#[derive(Component)]
+pub struct NumberInputText
+{
+	pub number_input: Entity,
+}
+
+/// The characters numbers of type T can be typed with.
+pub fn accepts_number_character<T: Num>(character: char) -> bool
+{
+	character.is_ascii_digit()
+		|| (T::SIGNED && character == '-')
+		|| (!T::INTEGER && character == '.')
+}
+
+/// How many times a button held for `held_for` seconds has repeated.
+fn repeat_count(held_for: f32) -> i32
+{
+	if held_for < HOLD_DELAY
+		{ 0 }
+	else
+		{ ((held_for - HOLD_DELAY) / HOLD_REPEAT) as i32 + 1 }
+}
+
+/// The systems that make number inputs editing T work.
+pub fn number_input_systems<T: Num>() -> SystemConfigs
+{
+	(
+		// Typed text is read before stepping, so the text written for the previous value can't undo a step.
+		read_number_text::<T>,
+		step_number_inputs::<T>,
+		write_number_text::<T>,
+	)
+		.chain()
+		.after(NavRequestSystem)
+		.after(super::text_input::handle_text_input)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn step_number_inputs<T: Num>
+(
+	mut number_query: Query<(&NumberInputParts, &NumberRange<T>, &mut NumberValue<T>, &RelativeCursorPosition)>,
+	mut button_query: Query<(Ref<Interaction>, &mut NumberStepButton)>,
+	focus_query: Query<&Focusable>,
+	mouse_input: Res<ButtonInput<MouseButton>>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	gamepads: Res<Gamepads>,
+	time: Res<Time>,
+	mut wheel_reader: EventReader<MouseWheel>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let wheel: f32 = wheel_reader.read().map(|wheel| wheel.y).sum();
+	// The navigation crate reads the same d-pad presses, so it may have just moved focus away from the input.
+	let left_input = nav_events.read().find_map(|event| match event
+	{
+		NavEvent::FocusChanged { to, from } if to.first() != from.first() => Some(*from.first()),
+		_ => None,
+	});
+
+	for (parts, range, mut value, cursor) in number_query.iter_mut()
+	{
+		let mut steps = 0;
+		for button in [parts.decrement, parts.increment]
+		{
+			let Ok((interaction, mut step_button)) = button_query.get_mut(button)
+				else { continue; };
+			let presses = if interaction.is_changed() && *interaction == Interaction::Pressed
+			{
+				step_button.held_for = 0.0;
+				1
+			}
+			// Keyboard presses also set Interaction::Pressed, only the mouse repeats.
+			else if *interaction == Interaction::Pressed && mouse_input.pressed(MouseButton::Left)
+			{
+				let before = repeat_count(step_button.held_for);
+				step_button.held_for += time.delta_seconds();
+				repeat_count(step_button.held_for) - before
+			}
+			else
+			{
+				step_button.held_for = 0.0;
+				0
+			};
+			steps += if step_button.increase { presses } else { -presses };
+		}
+
+		if wheel != 0.0 && cursor.mouse_over()
+			{ steps += wheel.signum() as i32; }
+
+		let was_focused = left_input == Some(parts.text_input);
+		let focused = focus_query.get(parts.text_input).is_ok_and(|focusable| focusable.state() == FocusState::Focused);
+		if focused || was_focused
+		{
+			let direction = super::slider::d_pad_input(super::slider::SliderOrientation::Horizontal, &gamepad_buttons, &gamepads);
+			if direction != 0.0
+			{
+				steps += direction as i32;
+				// Left and right adjust the value, so take the focus back.
+				if was_focused
+					{ nav_writer.send(NavRequest::FocusOn(parts.text_input)); }
+			}
+		}
+
+		if steps == 0
+			{ continue; }
+		let new_value = range.step(value.value, steps);
+		if new_value != value.value
+			{ value.value = new_value; }
+	}
+}
+
+/// Updates the value as valid numbers are typed.
+pub fn read_number_text<T: Num>
+(
+	text_query: Query<(&EditableText, &NumberInputText), Changed<EditableText>>,
+	mut number_query: Query<(&NumberRange<T>, &mut NumberValue<T>)>,
+)
+{
+	for (text, input) in text_query.iter()
+	{
+		let Ok((range, mut value)) = number_query.get_mut(input.number_input)
+			else { continue; };
+		if let Some(new_value) = range.parse(&text.text)
+		{
+			if new_value != value.value
+				{ value.value = new_value; }
+		}
+	}
+}
+
+/// Shows the value when it changes, and puts the last valid value back when the input loses focus with invalid text.
+pub fn write_number_text<T: Num>
+(
+	number_query: Query<(Ref<NumberValue<T>>, &NumberRange<T>, &NumberInputParts)>,
+	mut text_query: Query<(&mut EditableText, &mut EditCursor, &Focusable)>,
+)
+{
+	for (value, range, parts) in number_query.iter()
+	{
+		let Ok((mut text, mut cursor, focusable)) = text_query.get_mut(parts.text_input)
+			else { continue; };
+		if range.parse(&text.text) == Some(value.value)
+			{ continue; }
+		// Leave whatever is being typed alone until the input loses focus.
+		if !value.is_changed() && focusable.state() == FocusState::Focused
+			{ continue; }
+		text.text = value.value.to_string();
+		cursor.position = text.text.len();
+	}
+}
+
+pub struct NumberInput<U, M = (), T = f32>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier, T: Num
+{
+	pub row: Row<U, M>,
+	pub text_input: TextInput<U, ()>,
+	pub decrement: TextButton<U, ()>,
+	pub increment: TextButton<U, ()>,
+	pub value: T,
+	pub range: NumberRange<T>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier, T: Num> NumberInput<U, M, T>
+{
+	pub fn new(value: T) -> Self
+	{
+		Self
+		{
+			row: Row::new()
+				.with_paint_mode(PaintMode::Invisible),
+			text_input: TextInput::new(None)
+				.with_filter(accepts_number_character::<T>)
+				.with_fill_portion(0.6),
+			decrement: TextButton::new("-")
+				.with_fill_portion(0.2),
+			increment: TextButton::new("+")
+				.with_fill_portion(0.2),
+			value,
+			range: NumberRange { min: None, max: None, step: T::ONE },
+		}
+	}
+
+	pub fn with_range(mut self, min: T, max: T) -> Self
+	{
+		self.range.min = Some(min);
+		self.range.max = Some(max);
+		self
+	}
+
+	pub fn with_min(mut self, min: T) -> Self
+		{ self.range.min = Some(min); self }
+
+	pub fn with_max(mut self, max: T) -> Self
+		{ self.range.max = Some(max); self }
+
+	/// How much the buttons, the mouse wheel and the d-pad change the value by.
+	pub fn with_step(mut self, step: T) -> Self
+		{ self.range.step = step; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier, T: Num> Widget for NumberInput<U, M, T>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.row = self.row.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.row = self.row.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.row = self.row.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.row = self.row.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.row = self.row.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.row = self.row.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.row = self.row.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.row = self.row.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.row = self.row.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.row = self.row.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.row = self.row.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.row = self.row.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier, T: Num> WidgetBuilder<U> for NumberInput<U, M, T>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let child_parent_data = parent_data.from_current(self.row.container.theme);
+		let decrement_entity = self.decrement.build(ui_tree, theme_data, child_parent_data, commands);
+		let text_input_entity = self.text_input.build(ui_tree, theme_data, child_parent_data, commands);
+		let increment_entity = self.increment.build(ui_tree, theme_data, child_parent_data, commands);
+
+		let container = &mut self.row.container;
+		container.children.push(decrement_entity.into());
+		container.children.push(text_input_entity.into());
+		container.children.push(increment_entity.into());
+		let number_input_entity = self.row.build(ui_tree, theme_data, parent_data, commands);
+		self.row.container.children.clear();
+
+		commands.entity(decrement_entity)
+			.insert(NumberStepButton { number_input: number_input_entity, increase: false, held_for: 0.0 });
+		commands.entity(increment_entity)
+			.insert(NumberStepButton { number_input: number_input_entity, increase: true, held_for: 0.0 });
+		// write_number_text fills in the text.
+		commands.entity(text_input_entity)
+			.insert(NumberInputText { number_input: number_input_entity });
+		commands.entity(number_input_entity)
+			.insert(RelativeCursorPosition::default())
+			.insert(NumberValue { value: self.range.clamp(self.value) })
+			.insert(self.range)
+			.insert(NumberInputParts { text_input: text_input_entity, decrement: decrement_entity, increment: increment_entity });
+		number_input_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier, T: Num> Into<Box<dyn WidgetBuilder<U>>> for NumberInput<U, M, T>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_number_input(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		NumberInput::<TestUI, (), i32>::new(5)
+			.with_range(0, 20)
+			.with_step(2)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn float_steps_do_not_accumulate_error()
+	{
+		let range = NumberRange { min: None, max: None, step: 0.1f32 };
+		assert_eq!(range.step(0.0, 7).to_string(), "0.7");
+		assert_eq!(range.step(0.7, -9).to_string(), "-0.2");
+		// Typed values keep their own precision.
+		assert_eq!(range.step(0.25, 1).to_string(), "0.35");
+		assert_eq!(NumberRange { min: None, max: None, step: 0.1f64 }.step(0.0, 3).to_string(), "0.3");
+	}
+
+	#[test]
+	fn numbers_step_parse_and_restore_on_blur()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_number_input);
+		app.update();
+		let (number_input, text_input, decrement, increment) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &NumberInputParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.text_input, parts.decrement, parts.increment)
+		};
+		let value = |app: &App| app.world().get::<NumberValue<i32>>(number_input).unwrap().value;
+		let text = |app: &App| app.world().get::<EditableText>(text_input).unwrap().text.clone();
+		app.update();
+		assert_eq!(text(&app), "5");
+
+		*app.world_mut().get_mut::<Interaction>(increment).unwrap() = Interaction::Pressed;
+		app.update();
+		assert_eq!((value(&app), text(&app)), (7, "7".to_string()));
+		*app.world_mut().get_mut::<Interaction>(increment).unwrap() = Interaction::None;
+
+		// Holding the mouse on a button repeats it.
+		app.world_mut().resource_mut::<ButtonInput<MouseButton>>().press(MouseButton::Left);
+		*app.world_mut().get_mut::<Interaction>(decrement).unwrap() = Interaction::Pressed;
+		app.update();
+		assert_eq!(value(&app), 5);
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(HOLD_DELAY + HOLD_REPEAT * 1.5));
+		app.update();
+		assert_eq!(value(&app), 1);
+		app.world_mut().resource_mut::<ButtonInput<MouseButton>>().release(MouseButton::Left);
+		*app.world_mut().get_mut::<Interaction>(decrement).unwrap() = Interaction::None;
+
+		// Typed numbers update the value, out of range ones are replaced once the input loses focus.
+		app.world_mut().send_event(NavRequest::FocusOn(text_input));
+		app.update();
+		app.world_mut().get_mut::<EditableText>(text_input).unwrap().text = "12".to_string();
+		app.update();
+		assert_eq!(value(&app), 12);
+		app.world_mut().get_mut::<EditableText>(text_input).unwrap().text = "120".to_string();
+		app.update();
+		assert_eq!((value(&app), text(&app)), (12, "120".to_string()));
+		app.world_mut().send_event(NavRequest::FocusOn(increment));
+		app.update();
+		assert_eq!(text(&app), "12");
+		assert!(!accepts_number_character::<u32>('-'));
+		assert!(accepts_number_character::<f32>('.'));
+	}
+}
@@ -0,0 +1,487 @@
+/*
+	Progress bars and meters are Containers (the track) with absolutely positioned fills inside them.
+	Both read their value from Progress, update_progress_bars and update_meters resize the fills when it changes.
+	An indeterminate progress bar ignores its value, and sweeps a short fill across the track instead.
+	A meter splits the track into segments, and can leave a "recent damage" trail behind when its value drops, which catches up after a moment.
+*/
+
+use std::sync::Arc;
+
+use bevy::ecs::removal_detection::RemovedComponents;
+
+use super::*;
+use crate::theme::ThemeData;
+
+/// How long one sweep of an indeterminate progress bar takes, in seconds.
+const INDETERMINATE_PERIOD: f32 = 1.5;
+/// The width of an indeterminate progress bar's fill, as a percentage of the track.
+const INDETERMINATE_WIDTH: f32 = 30.0;
+/// How long a meter's trail stays in place after the value drops, in seconds.
+const TRAIL_HOLD_SECONDS: f32 = 0.5;
+/// How fast a meter's trail catches up with the value, in fractions of the whole meter per second.
+const TRAIL_CATCH_UP_PER_SECOND: f32 = 0.5;
+
+/// The value of a progress bar or meter, from 0.0 to 1.0.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Progress(pub f32);
+
+/// Makes a progress bar sweep instead of showing its value. Remove it to show the value again.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProgressIndeterminate
+{
+	/// How far through the current sweep the fill is, from 0.0 to 1.0.
+	pub phase: f32,
+}
+
+/// Turns the value into the text of the progress bar's label.
+#[derive(Component, Clone)]
+pub struct ProgressLabelFormat(pub Arc<dyn Fn(f32) -> String + Send + Sync>);
+
+/// The entities that make up a progress bar, lives on the track.
+#[derive(Component)]
+pub struct ProgressBarParts
+{
+	pub fill: Entity,
+	pub label: Option<Entity>,
+}
+
+/// The entities that make up a meter, lives on the meter.
+#[derive(Component)]
+pub struct MeterParts
+{
+	/// One fill per segment.
+	pub fills: Vec<Entity>,
+	/// One trail per segment, empty if the meter has no trail.
+	pub trails: Vec<Entity>,
+}
+
+/// The "recent damage" trail of a meter, which lags behind the value when it drops.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct MeterTrail
+{
+	pub value: f32,
+	/// Seconds left before the trail starts catching up.
+	pub hold: f32,
+}
+
+pub fn update_progress_bars
+(
+	bar_query: Query<(Entity, Ref<Progress>, &ProgressBarParts, Option<&ProgressLabelFormat>), Without<ProgressIndeterminate>>,
+	mut no_longer_indeterminate: RemovedComponents<ProgressIndeterminate>,
+	mut style_query: Query<&mut Style>,
+	children_query: Query<&Children>,
+	mut text_query: Query<&mut Text>,
+)
+{
+	let no_longer_indeterminate: Vec<Entity> = no_longer_indeterminate.read().collect();
+	for (entity, progress, parts, label_format) in bar_query.iter()
+	{
+		if !progress.is_changed() && !no_longer_indeterminate.contains(&entity)
+			{ continue; }
+		let value = progress.0.clamp(0.0, 1.0);
+		if let Ok(mut style) = style_query.get_mut(parts.fill)
+		{
+			style.left = Val::Percent(0.0);
+			style.width = Val::Percent(value * 100.0);
+		}
+
+		// The hierarchy is: TextLabel Container -> TextBundle.
+		let (Some(label), Some(label_format)) = (parts.label, label_format)
+			else { continue; };
+		let Ok(children) = children_query.get(label)
+			else { continue; };
+		if let Ok(mut text) = text_query.get_mut(children[0])
+			{ text.sections[0].value = (label_format.0)(value); }
+	}
+}
+
+pub fn animate_indeterminate_progress
+(
+	time: Res<Time>,
+	mut bar_query: Query<(&mut ProgressIndeterminate, &ProgressBarParts)>,
+	mut style_query: Query<&mut Style>,
+)
+{
+	for (mut indeterminate, parts) in bar_query.iter_mut()
+	{
+		indeterminate.phase = (indeterminate.phase + time.delta_seconds() / INDETERMINATE_PERIOD).fract();
+		if let Ok(mut style) = style_query.get_mut(parts.fill)
+		{
+			// Sweep from just outside the left of the track to just outside the right, the track clips the rest.
+			style.left = Val::Percent(-INDETERMINATE_WIDTH + (100.0 + INDETERMINATE_WIDTH) * indeterminate.phase);
+			style.width = Val::Percent(INDETERMINATE_WIDTH);
+		}
+	}
+}
+
+/// How much of a segment is filled, when the whole meter is filled up to `value`.
+fn segment_fill(value: f32, segment: usize, segments: usize) -> f32
+{
+	(value * segments as f32 - segment as f32).clamp(0.0, 1.0)
+}
+
+pub fn update_meters
+(
+	time: Res<Time>,
+	mut meter_query: Query<(Ref<Progress>, &MeterParts, Option<&mut MeterTrail>)>,
+	mut style_query: Query<&mut Style>,
+)
+{
+	for (progress, parts, trail) in meter_query.iter_mut()
+	{
+		let value = progress.0.clamp(0.0, 1.0);
+		let segments = parts.fills.len();
+		if progress.is_changed()
+		{
+			for (segment, fill) in parts.fills.iter().enumerate()
+			{
+				if let Ok(mut style) = style_query.get_mut(*fill)
+					{ style.width = Val::Percent(segment_fill(value, segment, segments) * 100.0); }
+			}
+		}
+
+		let Some(mut trail) = trail
+			else { continue; };
+		if value >= trail.value
+		{
+			// Healing doesn't leave a trail.
+			if trail.value != value
+				{ *trail = MeterTrail { value, hold: 0.0 }; }
+		}
+		else if progress.is_changed()
+		{
+			trail.hold = TRAIL_HOLD_SECONDS;
+		}
+		else if trail.hold > 0.0
+		{
+			trail.hold -= time.delta_seconds();
+		}
+		else
+		{
+			trail.value = (trail.value - TRAIL_CATCH_UP_PER_SECOND * time.delta_seconds()).max(value);
+		}
+		if !trail.is_changed()
+			{ continue; }
+		for (segment, trail_fill) in parts.trails.iter().enumerate()
+		{
+			if let Ok(mut style) = style_query.get_mut(*trail_fill)
+				{ style.width = Val::Percent(segment_fill(trail.value, segment, segments) * 100.0); }
+		}
+	}
+}
+
+/// A fill that starts at the left of its track.
+fn fill_container<U: Component + Default>(paint_mode: PaintMode, theme: Theme, width: f32) -> Container<U, ()>
+{
+	let mut fill = Container::<U, ()>::new()
+		.with_paint_mode(paint_mode)
+		.with_theme(theme);
+	fill.node_bundle.style = Style
+	{
+		position_type: PositionType::Absolute,
+		left: Val::Percent(0.0),
+		top: Val::Percent(0.0),
+		width: Val::Percent(width),
+		height: Val::Percent(100.0),
+		..Default::default()
+	};
+	fill
+}
+
+pub struct ProgressBar<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	/// The track, the fill is drawn inside it.
+	pub container: Container<U, M>,
+	/// The initial value.
+	pub progress: f32,
+	pub indeterminate: bool,
+	/// If this is set, a label showing the value is drawn over the bar.
+	pub label_format: Option<ProgressLabelFormat>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> ProgressBar<U, M>
+{
+	pub fn new(progress: f32) -> Self
+	{
+		Self
+		{
+			container: Container::new(),
+			progress,
+			indeterminate: false,
+			label_format: None,
+		}
+	}
+
+	/// Sweeps a fill across the bar instead of showing the value, for tasks of unknown length.
+	pub fn indeterminate(mut self) -> Self
+		{ self.indeterminate = true; self }
+
+	/// Shows the value as a percentage (e.g. "45%") over the bar.
+	pub fn with_percentage_label(self) -> Self
+	{
+		self.with_label_format(|progress| format!("{:.0}%", progress * 100.0))
+	}
+
+	/// Shows the value over the bar, formatted with the given function.
+	pub fn with_label_format(mut self, format: impl Fn(f32) -> String + Send + Sync + 'static) -> Self
+	{
+		self.label_format = Some(ProgressLabelFormat(Arc::new(format)));
+		self
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for ProgressBar<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for ProgressBar<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let progress = self.progress.clamp(0.0, 1.0);
+		let child_parent_data = parent_data.from_current(self.container.theme);
+
+		let fill_width = if self.indeterminate { INDETERMINATE_WIDTH } else { progress * 100.0 };
+		let fill_entity = fill_container::<U>(PaintMode::Background, Theme::Auto, fill_width)
+			.build(ui_tree, theme_data, child_parent_data, commands);
+
+		let label_entity = self.label_format.as_ref().map(|label_format|
+		{
+			let mut label = TextLabel::<U, ()>::new((label_format.0)(progress))
+				.with_paint_mode(PaintMode::Invisible);
+			label.container.node_bundle.style.position_type = PositionType::Absolute;
+			label.build(ui_tree, theme_data, child_parent_data, commands)
+		});
+
+		let track_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		let mut track = commands.entity(track_entity);
+		track
+			.push_children(&[fill_entity])
+			.insert(Progress(progress))
+			.insert(ProgressBarParts { fill: fill_entity, label: label_entity });
+		if let (Some(label_entity), Some(label_format)) = (label_entity, &self.label_format)
+		{
+			track
+				.push_children(&[label_entity])
+				.insert(label_format.clone());
+		}
+		if self.indeterminate
+			{ track.insert(ProgressIndeterminate::default()); }
+		track_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for ProgressBar<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+pub struct Meter<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	/// Holds the segments in a row.
+	pub container: Container<U, M>,
+	/// The initial value.
+	pub progress: f32,
+	pub segments: usize,
+	/// Leaves a trail, painted with the destructive role, behind when the value drops.
+	pub trail: bool,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Meter<U, M>
+{
+	pub fn new(progress: f32, segments: usize) -> Self
+	{
+		Self
+		{
+			container: Container::new()
+				.with_direction(FlexDirection::Row)
+				.with_paint_mode(PaintMode::Invisible),
+			progress,
+			segments: segments.max(1),
+			trail: true,
+		}
+	}
+
+	pub fn with_trail(mut self, trail: bool) -> Self
+		{ self.trail = trail; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Meter<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Meter<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let progress = self.progress.clamp(0.0, 1.0);
+		let child_parent_data = parent_data.from_current(self.container.theme);
+		let mut fills = Vec::new();
+		let mut trails = Vec::new();
+		for segment in 0..self.segments
+		{
+			let width = segment_fill(progress, segment, self.segments) * 100.0;
+			let mut segment_track = Container::<U, ()>::new()
+				.with_fill_portion(1.0 / self.segments as f32)
+				.with_margin(crate::theme::dimensions::SMALL);
+			if self.trail
+			{
+				let trail_entity = fill_container::<U>(PaintMode::Background, Theme::Destructive, width)
+					.build(ui_tree, theme_data, child_parent_data, commands);
+				segment_track = segment_track.push(trail_entity);
+				trails.push(trail_entity);
+			}
+			// The fill is pushed last, so it is drawn over the trail.
+			let fill_entity = fill_container::<U>(PaintMode::Background, Theme::Auto, width)
+				.build(ui_tree, theme_data, child_parent_data, commands);
+			segment_track = segment_track.push(fill_entity);
+			fills.push(fill_entity);
+			self.container.children.push(segment_track.into());
+		}
+
+		let meter_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		let mut meter = commands.entity(meter_entity);
+		meter
+			.insert(Progress(progress))
+			.insert(MeterParts { fills, trails });
+		if self.trail
+			{ meter.insert(MeterTrail { value: progress, hold: 0.0 }); }
+		meter_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Meter<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn width(app: &App, entity: Entity) -> f32
+	{
+		let Val::Percent(width) = app.world().get::<Style>(entity).unwrap().width
+			else { panic!("Fills should be sized with percentages."); };
+		width
+	}
+
+	#[test]
+	fn progress_bar_fill_and_label_follow_the_value()
+	{
+		fn build_progress_bar(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+		{
+			ProgressBar::<TestUI>::new(0.1)
+				.with_percentage_label()
+				.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		}
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_progress_bar);
+		app.update();
+		let (bar, fill, label) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &ProgressBarParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.fill, parts.label.unwrap())
+		};
+		app.world_mut().get_mut::<Progress>(bar).unwrap().0 = 0.45;
+		app.update();
+		assert!((width(&app, fill) - 45.0).abs() < 0.01);
+		let text = app.world().get::<Children>(label).unwrap()[0];
+		assert_eq!(app.world().get::<Text>(text).unwrap().sections[0].value, "45%");
+	}
+
+	#[test]
+	fn meter_trail_catches_up_after_damage()
+	{
+		fn build_meter(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+		{
+			Meter::<TestUI>::new(1.0, 4)
+				.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		}
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_meter);
+		app.update();
+		let (meter, fills, trails) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &MeterParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.fills.clone(), parts.trails.clone())
+		};
+
+		// Take 40% damage, the third segment is left 40% full.
+		app.world_mut().get_mut::<Progress>(meter).unwrap().0 = 0.6;
+		app.update();
+		let fill_widths: Vec<f32> = fills.iter().map(|fill| width(&app, *fill)).collect();
+		assert!((fill_widths[2] - 40.0).abs() < 0.01 && fill_widths[3] == 0.0, "Unexpected fills {fill_widths:?}");
+		assert_eq!(width(&app, trails[3]), 100.0);
+
+		// The trail holds, then catches up.
+		for _ in 0..4
+		{
+			app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(TRAIL_HOLD_SECONDS));
+			app.update();
+		}
+		assert_eq!(app.world().get::<MeterTrail>(meter).unwrap().value, 0.6);
+		assert_eq!(width(&app, trails[3]), 0.0);
+	}
+}
@@ -0,0 +1,308 @@
+/*
+	A radio group is a Container (Row or Column) of options, each option is a BaseButton holding an indicator and a TextLabel.
+	The indicator is themed like a CheckBox (a bordered container, but round), with a round dot inside it that is only shown when selected.
+	The group always has exactly one option selected, arrow keys along the group's direction move the selection (and the focus), wrapping around.
+*/
+
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use super::base_button::BaseButton;
+use crate::theme::ThemeData;
+
+/// Lives on each option's button.
+#[derive(Component)]
+pub struct RadioOption
+{
+	pub value: String,
+	pub index: usize,
+	/// The group this option belongs to.
+	pub group: Entity,
+}
+
+/// Lives on the group, and always holds a valid option.
+#[derive(Component)]
+pub struct RadioSelection
+{
+	pub value: String,
+	pub index: usize,
+}
+
+/// The entities that make up a radio group, lives on the group.
+#[derive(Component)]
+pub struct RadioGroupOptions
+{
+	pub options: Vec<Entity>,
+	/// The dot shown inside each option's indicator when it is selected.
+	pub dots: Vec<Entity>,
+	pub direction: FlexDirection,
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct RadioSelectionChanged
+{
+	pub group: Entity,
+	pub index: usize,
+	pub value: String,
+}
+
+fn select_radio(group: Entity, option: &RadioOption, selection: &mut RadioSelection, selection_writer: &mut EventWriter<RadioSelectionChanged>)
+{
+	if selection.index == option.index
+		{ return; }
+	selection.index = option.index;
+	selection.value = option.value.clone();
+	selection_writer.send(RadioSelectionChanged { group, index: option.index, value: option.value.clone() });
+}
+
+pub fn select_radio_option
+(
+	option_query: Query<(&Interaction, &RadioOption), Changed<Interaction>>,
+	mut group_query: Query<&mut RadioSelection>,
+	mut selection_writer: EventWriter<RadioSelectionChanged>,
+)
+{
+	for (interaction, option) in option_query.iter()
+	{
+		if *interaction != Interaction::Pressed
+			{ continue; }
+		if let Ok(mut selection) = group_query.get_mut(option.group)
+			{ select_radio(option.group, option, &mut selection, &mut selection_writer); }
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn navigate_radio_group
+(
+	option_query: Query<(Entity, &Focusable, &RadioOption)>,
+	mut group_query: Query<(&RadioGroupOptions, &mut RadioSelection)>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	gamepads: Res<bevy::input::gamepad::Gamepads>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+	mut selection_writer: EventWriter<RadioSelectionChanged>,
+)
+{
+	// The navigation crate reads the same input, so it may have just moved focus away from the option.
+	let left_option = nav_events.read().find_map(|event| match event
+	{
+		NavEvent::FocusChanged { to, from } if to.first() != from.first() && option_query.contains(*from.first()) => Some(*from.first()),
+		_ => None,
+	});
+	let focused_option = option_query
+		.iter()
+		.find(|(entity, focusable, _)| focusable.state() == FocusState::Focused || left_option == Some(*entity));
+	let Some((_, _, option)) = focused_option
+		else { return; };
+	let Ok((group, mut selection)) = group_query.get_mut(option.group)
+		else { return; };
+
+	let (orientation, forwards) = match group.direction
+	{
+		FlexDirection::Row => (SliderOrientation::Horizontal, 1.0),
+		FlexDirection::RowReverse => (SliderOrientation::Horizontal, -1.0),
+		FlexDirection::Column => (SliderOrientation::Vertical, -1.0),
+		FlexDirection::ColumnReverse => (SliderOrientation::Vertical, 1.0),
+	};
+	let direction = slider::axis_input(orientation, &keyboard_input, &gamepad_buttons, &gamepads, None) * forwards;
+	if direction == 0.0 || group.options.is_empty()
+		{ return; }
+	let count = group.options.len();
+	let index = if direction > 0.0 { (option.index + 1) % count } else { (option.index + count - 1) % count };
+	let target = group.options[index];
+	if let Ok((_, _, target_option)) = option_query.get(target)
+		{ select_radio(option.group, target_option, &mut selection, &mut selection_writer); }
+	nav_writer.send(NavRequest::FocusOn(target));
+}
+
+pub fn apply_radio_selection
+(
+	group_query: Query<(&RadioGroupOptions, &RadioSelection), Changed<RadioSelection>>,
+	mut style_query: Query<&mut Style>,
+)
+{
+	for (group, selection) in group_query.iter()
+	{
+		for (index, dot) in group.dots.iter().enumerate()
+		{
+			if let Ok(mut style) = style_query.get_mut(*dot)
+			{
+				style.display = if index == selection.index { Display::Flex } else { Display::None };
+			}
+		}
+	}
+}
+
+pub struct RadioGroup<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub container: Container<U, M>,
+	pub options: Vec<String>,
+	/// The index of the initially selected option.
+	pub selected: usize,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> RadioGroup<U, M>
+{
+	/// The options are laid out in a column, use `with_direction(FlexDirection::Row)` for a row.
+	pub fn new(options: impl IntoIterator<Item = impl Into<String>>) -> Self
+	{
+		Self
+		{
+			container: Container::new()
+				.with_direction(FlexDirection::Column)
+				.with_paint_mode(PaintMode::Invisible),
+			options: options.into_iter().map(Into::into).collect(),
+			selected: 0,
+		}
+	}
+
+	pub fn with_selected(mut self, selected: usize) -> Self
+		{ self.selected = selected; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for RadioGroup<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for RadioGroup<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let selected = self.selected.min(self.options.len().saturating_sub(1));
+		let option_parent_data = parent_data.from_current(self.container.theme);
+		let mut options = Vec::new();
+		let mut dots = Vec::new();
+		for (index, value) in self.options.iter().enumerate()
+		{
+			let mut dot = Container::<U, ()>::new()
+				.with_paint_mode(PaintMode::Foreground)
+				.with_size(Val::Percent(50.0), Val::Percent(50.0));
+			dot.node_bundle.border_radius = BorderRadius::MAX;
+			dot.node_bundle.style.display = if index == selected { Display::Flex } else { Display::None };
+			let dot_entity = dot.build(ui_tree, theme_data, option_parent_data, commands);
+
+			let mut indicator = Container::<U, ()>::new()
+				.with_aspect_ratio(1f32)
+				.with_border(crate::theme::dimensions::LARGE)
+				.push(dot_entity);
+			indicator.node_bundle.border_radius = BorderRadius::MAX;
+
+			let option_entity = BaseButton::<U, ()>::new()
+				.with_auto_style(true)
+				.with_direction(FlexDirection::Row)
+				.push(indicator)
+				.push(TextLabel::<U, ()>::new(value.clone())
+					.with_paint_mode(PaintMode::Invisible)
+					.with_fill_portion(0.8))
+				.build(ui_tree, theme_data, option_parent_data, commands);
+			self.container.children.push(option_entity.into());
+			options.push(option_entity);
+			dots.push(dot_entity);
+		}
+
+		let group_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		for (index, (option, value)) in options.iter().zip(self.options.iter()).enumerate()
+		{
+			commands.entity(*option)
+				.insert(RadioOption { value: value.clone(), index, group: group_entity });
+		}
+		commands.entity(group_entity)
+			.insert(RadioSelection { value: self.options.get(selected).cloned().unwrap_or_default(), index: selected })
+			.insert(RadioGroupOptions { options, dots, direction: self.container.node_bundle.style.flex_direction });
+		group_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for RadioGroup<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_radio_group(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		RadioGroup::<TestUI>::new(["Easy", "Normal", "Hard"])
+			.with_selected(1)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn exactly_one_option_is_selected()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_radio_group);
+		app.update();
+		let (group, options, dots) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &RadioGroupOptions)>();
+			let (entity, group) = query.single(app.world());
+			(entity, group.options.clone(), group.dots.clone())
+		};
+		let shown_dots = |app: &App| dots.iter().map(|dot| app.world().get::<Style>(*dot).unwrap().display == Display::Flex).collect::<Vec<_>>();
+		assert_eq!(shown_dots(&app), [false, true, false]);
+
+		*app.world_mut().get_mut::<Interaction>(options[2]).unwrap() = Interaction::Pressed;
+		app.update();
+		let selection = app.world().get::<RadioSelection>(group).unwrap();
+		assert_eq!((selection.index, selection.value.as_str()), (2, "Hard"));
+		assert_eq!(shown_dots(&app), [false, false, true]);
+		let events = app.world().resource::<Events<RadioSelectionChanged>>();
+		let event = events.get_reader().read(events).last().cloned();
+		assert_eq!(event, Some(RadioSelectionChanged { group, index: 2, value: "Hard".to_string() }));
+	}
+
+	#[test]
+	fn arrow_keys_move_the_selection_and_wrap()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_radio_group);
+		app.update();
+		let (group, options) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &RadioGroupOptions)>();
+			let (entity, group) = query.single(app.world());
+			(entity, group.options.clone())
+		};
+		app.world_mut().send_event(NavRequest::FocusOn(options[2]));
+		app.update();
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ArrowDown);
+		app.update();
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().reset_all();
+		app.update();
+		assert_eq!(app.world().get::<RadioSelection>(group).unwrap().index, 0);
+		assert_eq!(app.world().get::<Focusable>(options[0]).unwrap().state(), FocusState::Focused);
+	}
+}
@@ -0,0 +1,270 @@
+/*
+	Rich text is a TextLabel whose text is split into sections by lightweight markup, see TextLabel::from_markup.
+	Tags nest and are closed by name: [b]bold[/b], [i]italic[/i], [color=primary]a theme colour or #rrggbb[/color], [size=1.5]relative size[/size], [font=name]a theme font[/font].
+	Bold and italic need "bold", "italic" and "bold_italic" fonts in ThemeData::fonts, without them the regular font is used (with a warning).
+	Unknown tags are kept as text, and "[[" is a literal "[".
+	The RichText component on the label's text remembers each section's span, so sizes stay relative when resize_text rescales the label,
+	and coloured sections keep their colour when animate_colours recolours the rest: colour_rich_text tweens them to their colour in the new theme alongside the label.
+*/
+
+use bevy::prelude::*;
+
+use crate::theme::{ThemeData, CurrentThemeData, ColourTarget, ColourTween};
+
+/// How a section of rich text differs from the label's own text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichSpan
+{
+	pub bold: bool,
+	pub italic: bool,
+	/// A theme colour name or hex colour, see ThemeData::colour_by_name.
+	pub colour: Option<String>,
+	/// A multiple of the label's text size.
+	pub size: f32,
+	/// The name of a font in ThemeData::fonts.
+	pub font: Option<String>,
+}
+
+impl Default for RichSpan
+{
+	fn default() -> Self
+	{
+		Self { bold: false, italic: false, colour: None, size: 1.0, font: None }
+	}
+}
+
+impl RichSpan
+{
+	/// The font of the section. Bold and italic sections use the theme's "bold", "italic" and "bold_italic" fonts, and keep the label's font if it has none.
+	pub fn font(&self, theme_data: &ThemeData) -> Option<Handle<Font>>
+	{
+		let name = match (&self.font, self.bold, self.italic)
+		{
+			(Some(font), _, _) => font.as_str(),
+			(None, true, true) => "bold_italic",
+			(None, true, false) => "bold",
+			(None, false, true) => "italic",
+			(None, false, false) => return None,
+		};
+		let font = theme_data.fonts.get(name).cloned();
+		if font.is_none() && self.font.is_none()
+			{ warn_once!("Rich text uses [b] or [i], but the theme has no \"{name}\" font, so it is drawn in the regular font. Add \"bold\", \"italic\" and \"bold_italic\" to ThemeData::fonts."); }
+		font
+	}
+
+	pub fn colour(&self, theme_data: &ThemeData) -> Option<Color>
+	{
+		self.colour.as_deref().and_then(|colour| theme_data.colour_by_name(colour))
+	}
+}
+
+/// Lives on the text of a label built from markup, with one span per section.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct RichText
+{
+	pub spans: Vec<RichSpan>,
+	/// The colour of each section when the label's colour tween last started, what sections with their own colour are tweened from.
+	pub from_colours: Vec<Color>,
+}
+
+impl RichText
+{
+	/// The size multiplier of a section.
+	pub fn size(&self, section: usize) -> f32
+	{
+		self.spans.get(section).map_or(1.0, |span| span.size)
+	}
+
+	pub fn has_own_colour(&self, section: usize) -> bool
+	{
+		self.spans.get(section).is_some_and(|span| span.colour.is_some())
+	}
+
+	/// The colour of a section, if it has its own.
+	pub fn colour(&self, section: usize, theme_data: &ThemeData) -> Option<Color>
+	{
+		self.spans.get(section).and_then(|span| span.colour(theme_data))
+	}
+
+	/// Applies the spans to `text`, whose sections are already in the label's size, font and colour.
+	pub fn apply(&self, text: &mut Text, theme_data: &ThemeData)
+	{
+		for (section, span) in text.sections.iter_mut().zip(self.spans.iter())
+		{
+			section.style.font_size *= span.size;
+			if let Some(font) = span.font(theme_data)
+				{ section.style.font = font; }
+			if let Some(colour) = span.colour(theme_data)
+				{ section.style.color = colour; }
+		}
+	}
+}
+
+/// Tweens the label's colour from its first section without a colour of its own, rather than from the first section,
+/// and remembers where the other sections start from.
+pub fn start_rich_text_tweens<U: Component>
+(
+	mut text_query: Query<(&mut RichText, &Text, &mut ColourTween), (Changed<ColourTarget>, With<U>)>,
+)
+{
+	for (mut rich_text, text, mut tween) in text_query.iter_mut()
+	{
+		rich_text.from_colours = text.sections.iter().map(|section| section.style.color).collect();
+		let from = text.sections.iter().enumerate()
+			.find(|(index, _)| !rich_text.has_own_colour(*index))
+			.map(|(_, section)| section.style.color);
+		if from.is_some()
+			{ tween.from.text = from; }
+	}
+}
+
+/// Gives sections with their own colour back their colour after animate_colours gave every section the label's,
+/// tweening them to their colour in the current theme along with the label.
+pub fn colour_rich_text<U: Component>
+(
+	theme_data: Res<CurrentThemeData<U>>,
+	mut text_query: Query<(&RichText, &ColourTween, &mut Text), (Changed<ColourTween>, With<U>)>,
+)
+{
+	let transition = theme_data.0.transition;
+	for (rich_text, tween, mut text) in text_query.iter_mut()
+	{
+		for (index, section) in text.sections.iter_mut().enumerate()
+		{
+			if let Some(colour) = rich_text.colour(index, &theme_data.0)
+				{ section.style.color = tween.step(&transition, rich_text.from_colours.get(index).copied(), colour); }
+		}
+	}
+}
+
+/// Splits `markup` into the text of each section and how it is styled.
+pub fn parse_markup(markup: &str) -> Vec<(String, RichSpan)>
+{
+	let mut sections = Vec::new();
+	let mut span = RichSpan::default();
+	// The tags still open, with the span to go back to once each is closed.
+	let mut open: Vec<(&str, RichSpan)> = Vec::new();
+	let mut text = String::new();
+	let mut rest = markup;
+	while let Some(start) = rest.find('[')
+	{
+		text.push_str(&rest[..start]);
+		rest = &rest[start..];
+		if rest.starts_with("[[")
+		{
+			text.push('[');
+			rest = &rest[2..];
+			continue;
+		}
+		let Some(end) = rest.find(']')
+			else { break; };
+		let tag = &rest[1..end];
+		let (name, value) = tag.split_once('=').map_or((tag, None), |(name, value)| (name.trim(), Some(value.trim())));
+		let mut next = span.clone();
+		let known = match name.strip_prefix('/')
+		{
+			Some(closed) =>
+			{
+				let closed = if closed == "colour" { "color" } else { closed };
+				match open.iter().rposition(|(open_name, _)| *open_name == closed)
+				{
+					Some(index) =>
+					{
+						next = open[index].1.clone();
+						open.truncate(index);
+						true
+					},
+					None => false,
+				}
+			},
+			None =>
+			{
+				let opened = match (name, value)
+				{
+					("b", None) => { next.bold = true; Some("b") },
+					("i", None) => { next.italic = true; Some("i") },
+					("color" | "colour", Some(value)) => { next.colour = Some(value.to_string()); Some("color") },
+					("size", Some(value)) => value.parse::<f32>().ok()
+						.filter(|size| *size > 0.0)
+						.map(|size| { next.size = span.size * size; "size" }),
+					("font", Some(value)) => { next.font = Some(value.to_string()); Some("font") },
+					_ => None,
+				};
+				if let Some(opened) = opened
+					{ open.push((opened, span.clone())); }
+				opened.is_some()
+			},
+		};
+		if !known
+		{
+			text.push('[');
+			rest = &rest[1..];
+			continue;
+		}
+		if !text.is_empty()
+			{ sections.push((std::mem::take(&mut text), span.clone())); }
+		span = next;
+		rest = &rest[end + 1..];
+	}
+	text.push_str(rest);
+	if !text.is_empty() || sections.is_empty()
+		{ sections.push((text, span)); }
+	sections
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+	use crate::widgets::TextLabel;
+
+	#[test]
+	fn markup_is_split_into_sections()
+	{
+		let sections = parse_markup("Press [color=primary][b]Jump[/b][/color] to [size=2][size=0.75]leap[/size][/size] [[or [wave]");
+		let values: Vec<&str> = sections.iter().map(|(value, _)| value.as_str()).collect();
+		assert_eq!(values, ["Press ", "Jump", " to ", "leap", " [or [wave]"]);
+		assert_eq!(sections[1].1, RichSpan { bold: true, colour: Some("primary".to_string()), ..Default::default() });
+		assert_eq!(sections[3].1.size, 1.5);
+		assert_eq!(sections[4].1, RichSpan::default());
+	}
+
+	fn build_label(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		TextLabel::<TestUI>::from_markup("Press [color=primary]Jump[/color] to [size=1.5]leap[/size]")
+			.with_text_size(20.0)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn coloured_sections_follow_the_theme()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_label);
+		app.update();
+		let text_entity = app.world_mut().query_filtered::<Entity, With<RichText>>().single(app.world());
+		let sections = |app: &App| app.world().get::<Text>(text_entity).unwrap().sections.iter().map(|section| (section.style.color, section.style.font_size)).collect::<Vec<_>>();
+		let built = sections(&app);
+		assert_eq!(built[1], (crate::theme::DARK.primary, 20.0));
+		assert_eq!(built[3].1, 30.0);
+
+		// Coloured sections are tweened along with the rest of the label, rather than jumping to the new colour.
+		let duration = crate::theme::LIGHT.transition.duration;
+		app.world_mut().resource_mut::<CurrentThemeData<TestUI>>().0 = crate::theme::LIGHT.clone();
+		app.update();
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(duration / 2.0));
+		app.update();
+		let halfway = sections(&app);
+		assert_ne!(halfway[1].0, built[1].0);
+		assert_ne!(halfway[1].0, crate::theme::LIGHT.primary);
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(duration));
+		app.update();
+		let recoloured = sections(&app);
+		assert_eq!(recoloured[1].0, crate::theme::LIGHT.primary);
+		assert_ne!(recoloured[0].0, built[0].0);
+		assert_eq!(recoloured[0].0, recoloured[2].0);
+	}
+}
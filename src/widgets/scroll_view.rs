@@ -0,0 +1,448 @@
+/*
+	A scroll view is a Row holding a viewport (which clips) and a scrollbar.
+	The content is an absolutely positioned Column inside the viewport, scrolling moves its top edge up by ScrollOffset::offset.
+	Every input (wheel, keys, stick, thumb dragging, focus changes) only changes ScrollOffset, apply_scroll_offset then clamps it and moves the content and thumb.
+	Keys and the stick scroll the view that contains the focused widget, or the hovered view if focus is elsewhere.
+*/
+
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy::input::gamepad::{Gamepads, GamepadAxisType};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::ui::RelativeCursorPosition;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::ThemeData;
+
+/// How far one line of mouse wheel movement scrolls, in logical pixels.
+const SCROLL_LINE_HEIGHT: f32 = 40.0;
+/// How far the right stick scrolls per second when fully pushed, in logical pixels.
+const SCROLL_STICK_SPEED: f32 = 800.0;
+const STICK_DEADZONE: f32 = 0.2;
+/// PageUp/PageDown scroll by this much of the viewport, so some context stays visible.
+const PAGE_FRACTION: f32 = 0.9;
+pub const SCROLLBAR_WIDTH: f32 = 10.0;
+
+/// How far the content is scrolled, in logical pixels from the top.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScrollOffset
+{
+	pub offset: f32,
+	/// How far the content can be scrolled. This is updated from the layout every frame.
+	pub max_offset: f32,
+}
+
+impl ScrollOffset
+{
+	/// Scrolls towards the top by `delta` (logical pixels, negative scrolls down) as far as the content allows, returning what's left over.
+	pub fn scroll_by(&mut self, delta: f32) -> f32
+	{
+		let offset = (self.offset - delta).clamp(0.0, self.max_offset.max(0.0));
+		let remaining = delta - (self.offset - offset);
+		self.offset = offset;
+		remaining
+	}
+}
+
+/// The entities that make up a scroll view, lives on the scroll view.
+#[derive(Component)]
+pub struct ScrollViewParts
+{
+	pub viewport: Entity,
+	pub content: Entity,
+	pub scrollbar: Entity,
+	pub thumb: Entity,
+}
+
+/// Added to a scroll view while its thumb is being dragged.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ScrollThumbDrag
+{
+	pub start_cursor: f32,
+	pub start_offset: f32,
+}
+
+/// The offset needed to bring `target` inside `viewport`. Targets taller than the viewport have their top shown.
+pub fn scroll_into_view(offset: f32, viewport: Rect, target: Rect) -> f32
+{
+	if target.min.y < viewport.min.y
+		{ offset - (viewport.min.y - target.min.y) }
+	else if target.max.y > viewport.max.y
+		{ offset + (target.max.y - viewport.max.y).min(target.min.y - viewport.min.y) }
+	else
+		{ offset }
+}
+
+/// Finds the innermost scroll view that contains the entity.
+fn scroll_view_of(entity: Entity, parent_query: &Query<&Parent>, scroll_query: &Query<(Entity, &mut ScrollOffset, &ScrollViewParts, &RelativeCursorPosition)>) -> Option<Entity>
+{
+	parent_query.iter_ancestors(entity).find(|ancestor| scroll_query.contains(*ancestor))
+}
+
+/// Scrolls the innermost hovered view, views around it only get what it couldn't use once it reaches its top or bottom.
+pub fn scroll_with_wheel
+(
+	mut wheel_reader: EventReader<MouseWheel>,
+	mut scroll_query: Query<(Entity, &mut ScrollOffset, &RelativeCursorPosition)>,
+	parent_query: Query<&Parent>,
+)
+{
+	let delta: f32 = wheel_reader.read().map(|wheel| match wheel.unit
+	{
+		MouseScrollUnit::Line => wheel.y * SCROLL_LINE_HEIGHT,
+		MouseScrollUnit::Pixel => wheel.y,
+	}).sum();
+	if delta == 0.0
+		{ return; }
+	// Nested views (a ListView in a scrolling page, etc.) have more ancestors than the views around them.
+	let mut hovered: Vec<(usize, Entity)> = scroll_query.iter()
+		.filter(|(_, _, cursor)| cursor.mouse_over())
+		.map(|(entity, _, _)| (parent_query.iter_ancestors(entity).count(), entity))
+		.collect();
+	hovered.sort_by_key(|(depth, _)| std::cmp::Reverse(*depth));
+	let mut remaining = delta;
+	for (_, entity) in hovered
+	{
+		if remaining == 0.0
+			{ break; }
+		if let Ok((_, mut scroll, _)) = scroll_query.get_mut(entity)
+			{ remaining = scroll.scroll_by(remaining); }
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn scroll_with_input
+(
+	mut scroll_query: Query<(Entity, &mut ScrollOffset, &ScrollViewParts, &RelativeCursorPosition)>,
+	focus_query: Query<(Entity, &Focusable)>,
+	text_query: Query<(), With<EditableText>>,
+	parent_query: Query<&Parent>,
+	node_query: Query<&Node>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepad_axes: Res<Axis<GamepadAxis>>,
+	gamepads: Res<Gamepads>,
+	time: Res<Time>,
+)
+{
+	let focused = focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused).map(|(entity, _)| entity);
+	let focused_view = focused.and_then(|focused| scroll_view_of(focused, &parent_query, &scroll_query));
+	// Text inputs use Home/End (and multi-line ones the page keys) to move their caret, and scroll to it themselves.
+	let keys_scroll = !focused.is_some_and(|focused| text_query.contains(focused));
+	let stick: f32 = gamepads.iter()
+		.map(|gamepad| gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY)).unwrap_or(0.0))
+		.sum();
+
+	for (entity, mut scroll, parts, cursor) in scroll_query.iter_mut()
+	{
+		let targeted = match focused_view
+		{
+			Some(focused_view) => focused_view == entity,
+			None => cursor.mouse_over(),
+		};
+		if !targeted
+			{ continue; }
+		if keys_scroll
+		{
+			let page = node_query.get(parts.viewport).map_or(0.0, |node| node.size().y) * PAGE_FRACTION;
+			if keyboard_input.just_pressed(KeyCode::PageDown)
+				{ scroll.offset += page; }
+			if keyboard_input.just_pressed(KeyCode::PageUp)
+				{ scroll.offset -= page; }
+			if keyboard_input.just_pressed(KeyCode::Home)
+				{ scroll.offset = 0.0; }
+			if keyboard_input.just_pressed(KeyCode::End)
+				{ scroll.offset = scroll.max_offset; }
+		}
+		// Pushing the stick up scrolls up.
+		if stick.abs() > STICK_DEADZONE
+			{ scroll.offset -= stick * SCROLL_STICK_SPEED * time.delta_seconds(); }
+	}
+}
+
+pub fn drag_scroll_thumb
+(
+	mut commands: Commands,
+	mut scroll_query: Query<(Entity, &mut ScrollOffset, &ScrollViewParts, Option<&ScrollThumbDrag>)>,
+	interaction_query: Query<Ref<Interaction>>,
+	node_query: Query<&Node>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+	mouse_input: Res<ButtonInput<MouseButton>>,
+)
+{
+	let Some(cursor) = window_query.get_single().ok().and_then(|window| window.cursor_position())
+		else { return; };
+	for (entity, mut scroll, parts, drag) in scroll_query.iter_mut()
+	{
+		let Some(drag) = drag
+		else
+		{
+			let Ok(interaction) = interaction_query.get(parts.thumb)
+				else { continue; };
+			if interaction.is_changed() && *interaction == Interaction::Pressed
+				{ commands.entity(entity).insert(ScrollThumbDrag { start_cursor: cursor.y, start_offset: scroll.offset }); }
+			continue;
+		};
+		if !mouse_input.pressed(MouseButton::Left)
+		{
+			commands.entity(entity).remove::<ScrollThumbDrag>();
+			continue;
+		}
+		// Moving the thumb by the whole free length of the track scrolls through the whole content.
+		let (Ok(scrollbar), Ok(thumb)) = (node_query.get(parts.scrollbar), node_query.get(parts.thumb))
+			else { continue; };
+		let free_length = scrollbar.size().y - thumb.size().y;
+		if free_length > 0.0
+			{ scroll.offset = drag.start_offset + (cursor.y - drag.start_cursor) / free_length * scroll.max_offset; }
+	}
+}
+
+pub fn scroll_focused_into_view
+(
+	mut nav_events: EventReader<NavEvent>,
+	mut scroll_query: Query<(Entity, &mut ScrollOffset, &ScrollViewParts, &RelativeCursorPosition)>,
+	parent_query: Query<&Parent>,
+	rect_query: Query<(&Node, &GlobalTransform)>,
+)
+{
+	for event in nav_events.read()
+	{
+		let NavEvent::FocusChanged { to, .. } = event
+			else { continue; };
+		let focused = *to.first();
+		// Nested scroll views each bring their part of the chain into view.
+		let views: Vec<Entity> = parent_query.iter_ancestors(focused).filter(|ancestor| scroll_query.contains(*ancestor)).collect();
+		for view in views
+		{
+			let Ok((_, mut scroll, parts, _)) = scroll_query.get_mut(view)
+				else { continue; };
+			let (Ok((viewport_node, viewport_transform)), Ok((node, transform))) = (rect_query.get(parts.viewport), rect_query.get(focused))
+				else { continue; };
+			let new_offset = scroll_into_view(scroll.offset, viewport_node.logical_rect(viewport_transform), node.logical_rect(transform));
+			if new_offset != scroll.offset
+				{ scroll.offset = new_offset; }
+		}
+	}
+}
+
+pub fn apply_scroll_offset
+(
+	mut scroll_query: Query<(&mut ScrollOffset, &ScrollViewParts)>,
+	node_query: Query<&Node>,
+	mut style_query: Query<&mut Style>,
+)
+{
+	for (mut scroll, parts) in scroll_query.iter_mut()
+	{
+		let (Ok(viewport), Ok(content)) = (node_query.get(parts.viewport), node_query.get(parts.content))
+			else { continue; };
+		let viewport_height = viewport.size().y;
+		let content_height = content.size().y;
+		let max_offset = (content_height - viewport_height).max(0.0);
+		let offset = scroll.offset.clamp(0.0, max_offset);
+		// Only write when something moved, so change detection on ScrollOffset stays meaningful.
+		if scroll.offset != offset || scroll.max_offset != max_offset
+			{ *scroll = ScrollOffset { offset, max_offset }; }
+
+		if let Ok(mut style) = style_query.get_mut(parts.content)
+		{
+			if style.top != Val::Px(-offset)
+				{ style.top = Val::Px(-offset); }
+		}
+		if let Ok(mut style) = style_query.get_mut(parts.thumb)
+		{
+			let visible = if content_height > 0.0 { (viewport_height / content_height).min(1.0) } else { 1.0 };
+			let position = if max_offset > 0.0 { offset / max_offset } else { 0.0 };
+			let (height, top) = (Val::Percent(visible * 100.0), Val::Percent((1.0 - visible) * position * 100.0));
+			if style.height != height || style.top != top
+			{
+				style.height = height;
+				style.top = top;
+			}
+		}
+	}
+}
+
+pub struct ScrollView<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	/// Holds the viewport and the scrollbar.
+	pub container: Container<U, M>,
+	/// Holds the scrolled children.
+	pub content: Container<U, ()>,
+	pub show_scrollbar: bool,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> ScrollView<U, M>
+{
+	pub fn new() -> Self
+	{
+		let mut content = Container::new()
+			.with_direction(FlexDirection::Column)
+			.with_paint_mode(PaintMode::Invisible);
+		content.node_bundle.style.position_type = PositionType::Absolute;
+		content.node_bundle.style.top = Val::Px(0.0);
+		content.node_bundle.style.left = Val::Px(0.0);
+		content.node_bundle.style.height = Val::Auto;
+		content.node_bundle.style.min_height = Val::Percent(100.0);
+		content.node_bundle.style.justify_content = JustifyContent::FlexStart;
+		content.node_bundle.style.overflow = Overflow::visible();
+		Self
+		{
+			container: Container::new()
+				.with_direction(FlexDirection::Row),
+			content,
+			show_scrollbar: true,
+		}
+	}
+
+	/// Children are stacked in a column, and should have a definite height (e.g. `Val::Px`),
+	/// unless the content height is set with `with_content_height`.
+	pub fn push(mut self, child: impl Into<Box<dyn WidgetBuilder<U>>>) -> Self
+		{ self.content = self.content.push(child); self }
+
+	/// By default the content is as tall as its children. Percentages are of the viewport, `Val::Percent(300.0)` is three viewports tall.
+	pub fn with_content_height(mut self, height: Val) -> Self
+		{ self.content.node_bundle.style.height = height; self }
+
+	pub fn with_scrollbar(mut self, show_scrollbar: bool) -> Self
+		{ self.show_scrollbar = show_scrollbar; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for ScrollView<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	// The scroll view itself is always a row (viewport, scrollbar), so the direction applies to the content.
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.content = self.content.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.content = self.content.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.content = self.content.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for ScrollView<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let child_parent_data = parent_data.from_current(self.container.theme);
+		let content_entity = self.content.build(ui_tree, theme_data, child_parent_data, commands);
+
+		let mut viewport = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Invisible)
+			.push(content_entity);
+		viewport.node_bundle.style.justify_content = JustifyContent::FlexStart;
+		viewport.node_bundle.style.align_items = AlignItems::FlexStart;
+		let viewport_entity = viewport.build(ui_tree, theme_data, child_parent_data, commands);
+
+		let mut thumb = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Foreground);
+		thumb.node_bundle.style.position_type = PositionType::Absolute;
+		thumb.node_bundle.style.top = Val::Percent(0.0);
+		thumb.node_bundle.focus_policy = bevy::ui::FocusPolicy::Block;
+		thumb.node_bundle.border_radius = BorderRadius::MAX;
+		let thumb_entity = thumb.build(ui_tree, theme_data, child_parent_data, commands);
+		commands.entity(thumb_entity).insert(Interaction::default());
+
+		let mut scrollbar = Container::<U, ()>::new()
+			.push(thumb_entity);
+		scrollbar.node_bundle.style.width = Val::Px(SCROLLBAR_WIDTH);
+		scrollbar.node_bundle.style.flex_shrink = 0.0;
+		if !self.show_scrollbar
+			{ scrollbar.node_bundle.style.display = Display::None; }
+		let scrollbar_entity = scrollbar.build(ui_tree, theme_data, child_parent_data, commands);
+
+		self.container.children.push(viewport_entity.into());
+		self.container.children.push(scrollbar_entity.into());
+		let scroll_view_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		self.container.children.clear();
+
+		commands.entity(scroll_view_entity)
+			.insert(RelativeCursorPosition::default())
+			.insert(ScrollOffset::default())
+			.insert(ScrollViewParts
+			{
+				viewport: viewport_entity,
+				content: content_entity,
+				scrollbar: scrollbar_entity,
+				thumb: thumb_entity,
+			});
+		scroll_view_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for ScrollView<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::test::TestUI;
+
+	#[test]
+	fn offset_is_clamped_to_the_content()
+	{
+		fn build_scroll_view(mut commands: Commands, mut ui_tree: ResMut<crate::UIHierarchy<TestUI>>, theme: Res<crate::theme::CurrentThemeData<TestUI>>)
+		{
+			ScrollView::<TestUI>::new()
+				.push(TextButton::<TestUI>::new("Item"))
+				.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		}
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_scroll_view);
+		app.update();
+		let scroll_view = app.world_mut().query_filtered::<Entity, With<ScrollViewParts>>().single(app.world());
+		// Without a layout, the content fits in the viewport, so it can't be scrolled.
+		app.world_mut().get_mut::<ScrollOffset>(scroll_view).unwrap().offset = 500.0;
+		app.update();
+		assert_eq!(*app.world().get::<ScrollOffset>(scroll_view).unwrap(), ScrollOffset { offset: 0.0, max_offset: 0.0 });
+	}
+
+	#[test]
+	fn wheel_movement_is_passed_on_at_the_limits()
+	{
+		let mut inner = ScrollOffset { offset: 20.0, max_offset: 100.0 };
+		// Scrolling up by 50 uses the 20 the view has left, and passes the rest on.
+		assert_eq!(inner.scroll_by(50.0), 30.0);
+		assert_eq!(inner.offset, 0.0);
+		assert_eq!(inner.scroll_by(-30.0), 0.0);
+		assert_eq!(inner.offset, 30.0);
+		assert_eq!(inner.scroll_by(-100.0), -30.0);
+		assert_eq!(inner.offset, 100.0);
+	}
+
+	#[test]
+	fn targets_are_scrolled_into_view()
+	{
+		let viewport = Rect::new(0.0, 100.0, 200.0, 300.0);
+		// Already visible.
+		assert_eq!(scroll_into_view(50.0, viewport, Rect::new(0.0, 150.0, 200.0, 200.0)), 50.0);
+		// Above the viewport, scroll up until its top is at the top.
+		assert_eq!(scroll_into_view(50.0, viewport, Rect::new(0.0, 80.0, 200.0, 130.0)), 30.0);
+		// Below the viewport, scroll down until its bottom is at the bottom.
+		assert_eq!(scroll_into_view(50.0, viewport, Rect::new(0.0, 320.0, 200.0, 370.0)), 120.0);
+		// Taller than the viewport, its top is shown.
+		assert_eq!(scroll_into_view(50.0, viewport, Rect::new(0.0, 250.0, 200.0, 600.0)), 200.0);
+	}
+}
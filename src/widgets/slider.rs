@@ -0,0 +1,582 @@
+/*
+	The slider is a BaseButton holding a track (with a fill and a thumb inside it), and optionally a value label.
+	The fill and thumb are absolutely positioned inside the track, update_slider_visuals moves them whenever SliderValue changes.
+	Dragging works on the whole button, the track's rect is used to turn the cursor position into a value.
+	While focused, arrow keys/the d-pad/the left stick along the slider's axis adjust the value instead of navigating away.
+*/
+
+use std::sync::Arc;
+
+use bevy::input::gamepad::{Gamepads, GamepadAxisType, GamepadButtonType};
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use super::base_button::BaseButton;
+use crate::theme::ThemeData;
+
+/// The thickness of the thumb, along the slider's axis.
+const THUMB_THICKNESS: f32 = 12.0;
+/// How far the gamepad stick must be pushed before it adjusts the slider.
+const STICK_DEADZONE: f32 = 0.5;
+/// How often a held stick adjusts the slider.
+const STICK_REPEAT_SECONDS: f32 = 0.15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SliderOrientation
+{
+	#[default]
+	Horizontal,
+	/// The minimum is at the bottom.
+	Vertical,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SliderValue
+{
+	pub value: f32,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SliderRange
+{
+	pub min: f32,
+	pub max: f32,
+	/// A step of 0 makes the slider continuous.
+	pub step: f32,
+}
+
+impl SliderRange
+{
+	/// Clamps the value to the range, and rounds it to the nearest step. NaN values snap to the minimum.
+	pub fn snap(&self, value: f32) -> f32
+	{
+		if value.is_nan()
+			{ return self.min; }
+		// Not f32::clamp, which panics if a bound is NaN or the bounds are reversed.
+		let value = value.max(self.min).min(self.max);
+		if self.step <= 0.0
+			{ return value; }
+		let steps = ((value - self.min) / self.step).round();
+		(self.min + steps * self.step).max(self.min).min(self.max)
+	}
+
+	/// Where the value lies in the range, from 0 to 1.
+	pub fn fraction(&self, value: f32) -> f32
+	{
+		if self.max <= self.min
+			{ return 0.0; }
+		((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+	}
+
+	/// How much a single key press or stick push changes the value. Continuous sliders move by a hundredth of their range.
+	pub fn increment(&self) -> f32
+	{
+		if self.step > 0.0
+			{ self.step }
+		else
+			{ (self.max - self.min) / 100.0 }
+	}
+}
+
+/// The entities that make up a slider, lives on the slider's button.
+#[derive(Component)]
+pub struct SliderParts
+{
+	pub orientation: SliderOrientation,
+	pub track: Entity,
+	pub fill: Entity,
+	pub thumb: Entity,
+	pub label: Option<Entity>,
+}
+
+/// Turns the value into the text of the value label.
+#[derive(Component, Clone)]
+pub struct SliderLabelFormat(pub Arc<dyn Fn(f32) -> String + Send + Sync>);
+
+/// Added to a slider while the mouse is dragging its thumb.
+#[derive(Component)]
+pub struct SliderDragging;
+
+/// Sent when the user changes a slider's value, setting SliderValue from code does not send it.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct SliderChanged
+{
+	pub slider: Entity,
+	pub value: f32,
+}
+
+pub fn drag_slider
+(
+	mut commands: Commands,
+	mut slider_query: Query<(Entity, Ref<Interaction>, &SliderRange, &SliderParts, &mut SliderValue, Has<SliderDragging>)>,
+	track_query: Query<(&Node, &GlobalTransform)>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+	mouse_input: Res<ButtonInput<MouseButton>>,
+	mut change_writer: EventWriter<SliderChanged>,
+)
+{
+	let cursor = window_query.get_single().ok().and_then(|window| window.cursor_position());
+	for (entity, interaction, range, parts, mut value, mut dragging) in slider_query.iter_mut()
+	{
+		// Keyboard presses also set Interaction::Pressed, only the mouse starts a drag.
+		if interaction.is_changed() && *interaction == Interaction::Pressed && mouse_input.pressed(MouseButton::Left) && !dragging
+		{
+			commands.entity(entity).insert(SliderDragging);
+			dragging = true;
+		}
+		if !dragging
+			{ continue; }
+		if !mouse_input.pressed(MouseButton::Left)
+		{
+			commands.entity(entity).remove::<SliderDragging>();
+			continue;
+		}
+
+		let (Some(cursor), Ok((node, transform))) = (cursor, track_query.get(parts.track))
+			else { continue; };
+		let rect = node.logical_rect(transform);
+		let fraction = match parts.orientation
+		{
+			SliderOrientation::Horizontal => (cursor.x - rect.min.x) / rect.width().max(1.0),
+			SliderOrientation::Vertical => (rect.max.y - cursor.y) / rect.height().max(1.0),
+		};
+		let new_value = range.snap(range.min + fraction * (range.max - range.min));
+		if new_value != value.value
+		{
+			value.value = new_value;
+			change_writer.send(SliderChanged { slider: entity, value: new_value });
+		}
+	}
+}
+
+/// Returns -1, 0 or 1 depending on the input along the given axis this frame (1 is right or up).
+/// Other widgets that take arrow keys while focused (radio groups, etc.) use this too.
+pub(crate) fn axis_input
+(
+	orientation: SliderOrientation,
+	keyboard_input: &ButtonInput<KeyCode>,
+	gamepad_buttons: &ButtonInput<GamepadButton>,
+	gamepads: &Gamepads,
+	stick: Option<Vec2>,
+) -> f32
+{
+	let (increase_keys, decrease_keys) = match orientation
+	{
+		SliderOrientation::Horizontal => ([KeyCode::ArrowRight, KeyCode::KeyD], [KeyCode::ArrowLeft, KeyCode::KeyA]),
+		SliderOrientation::Vertical => ([KeyCode::ArrowUp, KeyCode::KeyW], [KeyCode::ArrowDown, KeyCode::KeyS]),
+	};
+	if keyboard_input.any_just_pressed(increase_keys)
+		{ return 1.0; }
+	if keyboard_input.any_just_pressed(decrease_keys)
+		{ return -1.0; }
+	let d_pad = d_pad_input(orientation, gamepad_buttons, gamepads);
+	if d_pad != 0.0
+		{ return d_pad; }
+	let stick = match (stick, orientation)
+	{
+		(Some(stick), SliderOrientation::Horizontal) => stick.x,
+		(Some(stick), SliderOrientation::Vertical) => stick.y,
+		(None, _) => 0.0,
+	};
+	if stick.abs() > STICK_DEADZONE
+		{ stick.signum() }
+	else
+		{ 0.0 }
+}
+
+/// Like axis_input, for the d-pad alone. Used by widgets that need the arrow keys for something else (text inputs, etc.).
+pub(crate) fn d_pad_input
+(
+	orientation: SliderOrientation,
+	gamepad_buttons: &ButtonInput<GamepadButton>,
+	gamepads: &Gamepads,
+) -> f32
+{
+	let (increase_pad, decrease_pad) = match orientation
+	{
+		SliderOrientation::Horizontal => (GamepadButtonType::DPadRight, GamepadButtonType::DPadLeft),
+		SliderOrientation::Vertical => (GamepadButtonType::DPadUp, GamepadButtonType::DPadDown),
+	};
+	let pad_pressed = |button_type| gamepads.iter().any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)));
+	if pad_pressed(increase_pad)
+		{ 1.0 }
+	else if pad_pressed(decrease_pad)
+		{ -1.0 }
+	else
+		{ 0.0 }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn adjust_slider_with_input
+(
+	mut slider_query: Query<(Entity, &Focusable, &SliderRange, &SliderParts, &mut SliderValue)>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	gamepad_axes: Res<Axis<GamepadAxis>>,
+	gamepads: Res<Gamepads>,
+	time: Res<Time>,
+	mut stick_cooldown: Local<f32>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+	mut change_writer: EventWriter<SliderChanged>,
+)
+{
+	// The stick adjusts the slider once when pushed, then repeatedly while held.
+	let stick = gamepads.iter().fold(Vec2::ZERO, |stick, gamepad|
+	{
+		let x = gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.0);
+		let y = gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.0);
+		stick + Vec2::new(x, y)
+	});
+	*stick_cooldown -= time.delta_seconds();
+	let stick = if stick.abs().max_element() <= STICK_DEADZONE
+	{
+		*stick_cooldown = 0.0;
+		None
+	}
+	else if *stick_cooldown <= 0.0
+	{
+		*stick_cooldown = STICK_REPEAT_SECONDS;
+		Some(stick)
+	}
+	else
+		{ None };
+
+	// The navigation crate reads the same input, so it may have just moved focus away from the slider.
+	let left_slider = nav_events.read().find_map(|event| match event
+	{
+		NavEvent::FocusChanged { to, from } if to.first() != from.first() && slider_query.contains(*from.first()) => Some(*from.first()),
+		_ => None,
+	});
+
+	for (entity, focusable, range, parts, mut value) in slider_query.iter_mut()
+	{
+		let was_focused = left_slider == Some(entity);
+		if focusable.state() != FocusState::Focused && !was_focused
+			{ continue; }
+		let direction = axis_input(parts.orientation, &keyboard_input, &gamepad_buttons, &gamepads, stick);
+		if direction == 0.0
+			{ continue; }
+		// Input along the slider's axis adjusts it, so take the focus back.
+		if was_focused
+			{ nav_writer.send(NavRequest::FocusOn(entity)); }
+		let new_value = range.snap(value.value + direction * range.increment());
+		if new_value != value.value
+		{
+			value.value = new_value;
+			change_writer.send(SliderChanged { slider: entity, value: new_value });
+		}
+	}
+}
+
+pub fn update_slider_visuals
+(
+	slider_query: Query<(&SliderValue, &SliderRange, &SliderParts, Option<&SliderLabelFormat>), Changed<SliderValue>>,
+	mut style_query: Query<&mut Style>,
+	children_query: Query<&Children>,
+	mut text_query: Query<&mut Text>,
+)
+{
+	for (value, range, parts, label_format) in slider_query.iter()
+	{
+		let percent = range.fraction(value.value) * 100.0;
+		if let Ok(mut style) = style_query.get_mut(parts.fill)
+		{
+			match parts.orientation
+			{
+				SliderOrientation::Horizontal => style.width = Val::Percent(percent),
+				SliderOrientation::Vertical => style.height = Val::Percent(percent),
+			}
+		}
+		if let Ok(mut style) = style_query.get_mut(parts.thumb)
+		{
+			match parts.orientation
+			{
+				SliderOrientation::Horizontal => style.left = Val::Percent(percent),
+				SliderOrientation::Vertical => style.bottom = Val::Percent(percent),
+			}
+		}
+
+		// The hierarchy is: TextLabel Container -> TextBundle.
+		let (Some(label), Some(label_format)) = (parts.label, label_format)
+			else { continue; };
+		let Ok(children) = children_query.get(label)
+			else { continue; };
+		if let Ok(mut text) = text_query.get_mut(children[0])
+			{ text.sections[0].value = (label_format.0)(value.value); }
+	}
+}
+
+/// Shows as many decimals as the step needs, continuous sliders show two.
+fn default_label_format(step: f32) -> SliderLabelFormat
+{
+	let decimals = if step > 0.0 { (-step.log10()).ceil().max(0.0) as usize } else { 2 };
+	SliderLabelFormat(Arc::new(move |value| format!("{value:.decimals$}")))
+}
+
+pub struct Slider<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub base_button: BaseButton<U, M>,
+	pub range: SliderRange,
+	/// The initial value.
+	pub value: f32,
+	pub orientation: SliderOrientation,
+	/// If this is set, a label showing the value is placed next to the track.
+	pub label_format: Option<SliderLabelFormat>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Slider<U, M>
+{
+	/// Reversed bounds are swapped, and bounds that aren't numbers fall back to 0..1.
+	pub fn new(min: f32, max: f32) -> Self
+	{
+		let (min, max) = if min.is_nan() || max.is_nan()
+		{
+			warn!("Slider bounds must be numbers, got {min}..{max}, using 0..1 instead.");
+			(0.0, 1.0)
+		}
+		else if min > max { (max, min) } else { (min, max) };
+		Self
+		{
+			base_button: BaseButton::new()
+				.with_auto_style(true),
+			range: SliderRange { min, max, step: 0.0 },
+			value: min,
+			orientation: SliderOrientation::Horizontal,
+			label_format: None,
+		}
+	}
+
+	pub fn with_step(mut self, step: f32) -> Self
+		{ self.range.step = step; self }
+
+	pub fn with_value(mut self, value: f32) -> Self
+		{ self.value = value; self }
+
+	pub fn with_orientation(mut self, orientation: SliderOrientation) -> Self
+		{ self.orientation = orientation; self }
+
+	/// Shows the value next to the track, formatted according to the step.
+	pub fn with_value_label(mut self, show_value_label: bool) -> Self
+	{
+		self.label_format = show_value_label.then(|| default_label_format(self.range.step));
+		self
+	}
+
+	/// Shows the value next to the track, formatted with the given function (e.g. `|value| format!("{value:.0}%")`).
+	pub fn with_label_format(mut self, format: impl Fn(f32) -> String + Send + Sync + 'static) -> Self
+	{
+		self.label_format = Some(SliderLabelFormat(Arc::new(format)));
+		self
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Slider<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.base_button = self.base_button.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.base_button = self.base_button.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.base_button = self.base_button.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.base_button = self.base_button.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.base_button = self.base_button.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.base_button = self.base_button.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.base_button = self.base_button.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.base_button = self.base_button.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.base_button = self.base_button.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.base_button = self.base_button.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.base_button = self.base_button.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.base_button = self.base_button.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Slider<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let value = self.range.snap(self.value);
+		let percent = self.range.fraction(value) * 100.0;
+		let horizontal = self.orientation == SliderOrientation::Horizontal;
+		let child_parent_data = parent_data.from_current(self.base_button.theme);
+
+		// The fill grows from the start of the track.
+		let mut fill = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Background);
+		fill.node_bundle.style = Style
+		{
+			position_type: PositionType::Absolute,
+			left: Val::Px(0.0),
+			bottom: Val::Px(0.0),
+			width: if horizontal { Val::Percent(percent) } else { Val::Percent(100.0) },
+			height: if horizontal { Val::Percent(100.0) } else { Val::Percent(percent) },
+			..Default::default()
+		};
+		let fill_entity = fill.build(ui_tree, theme_data, child_parent_data, commands);
+
+		// The thumb is centred on the value, and sticks out of both sides of the track.
+		let mut thumb = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Foreground);
+		thumb.node_bundle.style = if horizontal
+		{
+			Style
+			{
+				position_type: PositionType::Absolute,
+				left: Val::Percent(percent),
+				top: Val::Percent(-75.0),
+				width: Val::Px(THUMB_THICKNESS),
+				height: Val::Percent(250.0),
+				margin: UiRect::left(Val::Px(-THUMB_THICKNESS / 2.0)),
+				..Default::default()
+			}
+		}
+		else
+		{
+			Style
+			{
+				position_type: PositionType::Absolute,
+				bottom: Val::Percent(percent),
+				left: Val::Percent(-75.0),
+				width: Val::Percent(250.0),
+				height: Val::Px(THUMB_THICKNESS),
+				margin: UiRect::bottom(Val::Px(-THUMB_THICKNESS / 2.0)),
+				..Default::default()
+			}
+		};
+		let thumb_entity = thumb.build(ui_tree, theme_data, child_parent_data, commands);
+
+		let mut track = Container::<U, ()>::new()
+			.push(fill_entity)
+			.push(thumb_entity);
+		track.node_bundle.style.overflow = Overflow::visible();
+		if horizontal
+			{ track.node_bundle.style.height = Val::Percent(40.0); }
+		else
+			{ track.node_bundle.style.width = Val::Percent(40.0); }
+		let track_entity = track.build(ui_tree, theme_data, child_parent_data, commands);
+
+		// Leave room for the thumb at both ends of the track.
+		let padding = if horizontal { UiRect::horizontal(Val::Px(THUMB_THICKNESS)) } else { UiRect::vertical(Val::Px(THUMB_THICKNESS)) };
+		let mut track_area = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Invisible)
+			.with_padding(padding)
+			.with_fill_portion(if self.label_format.is_some() { 0.8 } else { 1.0 })
+			.push(track_entity);
+		let track_area_entity = track_area.build(ui_tree, theme_data, child_parent_data, commands);
+
+		let label_entity = self.label_format.as_ref().map(|label_format|
+		{
+			TextLabel::<U, ()>::new((label_format.0)(value))
+				.with_paint_mode(PaintMode::Invisible)
+				.with_fill_portion(0.2)
+				.build(ui_tree, theme_data, child_parent_data, commands)
+		});
+
+		self.base_button.button_bundle.style.flex_direction = if horizontal { FlexDirection::Row } else { FlexDirection::Column };
+		let button_entity = self.base_button.build(ui_tree, theme_data, parent_data, commands);
+
+		let mut button = commands.entity(button_entity);
+		button
+			.push_children(&[track_area_entity])
+			.insert(SliderValue { value })
+			.insert(self.range)
+			.insert(SliderParts
+			{
+				orientation: self.orientation,
+				track: track_entity,
+				fill: fill_entity,
+				thumb: thumb_entity,
+				label: label_entity,
+			});
+		if let (Some(label_entity), Some(label_format)) = (label_entity, &self.label_format)
+		{
+			button
+				.push_children(&[label_entity])
+				.insert(label_format.clone());
+		}
+		button_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Slider<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_slider(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Slider::<TestUI>::new(0.0, 10.0)
+			.with_step(1.0)
+			.with_value(5.2)
+			.with_value_label(true)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn values_snap_to_steps()
+	{
+		let range = SliderRange { min: 0.0, max: 1.0, step: 0.25 };
+		assert_eq!(range.snap(0.3), 0.25);
+		assert_eq!(range.snap(0.9), 1.0);
+		assert_eq!(range.snap(-3.0), 0.0);
+		let continuous = SliderRange { step: 0.0, ..range };
+		assert_eq!(continuous.snap(0.3), 0.3);
+		assert_eq!(range.snap(f32::NAN), 0.0);
+		// Ranges built by hand may be reversed, they mustn't panic.
+		let reversed = SliderRange { min: 1.0, max: 0.0, step: 0.25 };
+		assert_eq!(reversed.snap(0.5), 0.0);
+		assert_eq!(Slider::<TestUI>::new(10.0, 0.0).range, SliderRange { min: 0.0, max: 10.0, step: 0.0 });
+		assert_eq!(Slider::<TestUI>::new(f32::NAN, 10.0).range, SliderRange { min: 0.0, max: 1.0, step: 0.0 });
+	}
+
+	#[test]
+	fn arrow_keys_adjust_the_focused_slider()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_slider);
+		app.update();
+		let (slider, fill) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &SliderValue, &SliderParts)>();
+			let (entity, value, parts) = query.single(app.world());
+			assert_eq!(value.value, 5.0);
+			(entity, parts.fill)
+		};
+
+		// Unfocused sliders ignore the keyboard.
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ArrowRight);
+		app.update();
+		assert_eq!(app.world().get::<SliderValue>(slider).unwrap().value, 5.0);
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().reset_all();
+
+		app.world_mut().send_event(NavRequest::FocusOn(slider));
+		app.update();
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ArrowRight);
+		app.update();
+		assert_eq!(app.world().get::<SliderValue>(slider).unwrap().value, 6.0);
+		let Val::Percent(fill_width) = app.world().get::<Style>(fill).unwrap().width
+			else { panic!("The fill's width should be a percentage."); };
+		assert!((fill_width - 60.0).abs() < 0.01);
+		let events = app.world().resource::<Events<SliderChanged>>();
+		let event = events.get_reader().read(events).last().copied();
+		assert_eq!(event, Some(SliderChanged { slider, value: 6.0 }));
+	}
+}
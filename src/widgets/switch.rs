@@ -0,0 +1,238 @@
+/*
+	A switch is a pill shaped BaseButton (the track) with a round thumb inside it.
+	It uses CheckBoxState, so toggle_checkbox toggles it and existing CheckBoxState queries work with switches too.
+	The track is painted with the primary role when on, and the surface (container) role when off, the colours are tweened by changing its ThemePaint.
+	The thumb slides between the two ends of the track, over the theme's transition duration.
+*/
+
+use super::*;
+use super::base_button::BaseButton;
+use crate::theme::{ThemeData, ThemePaint, CurrentThemeData};
+
+/// The gap between the thumb and the ends of the track, as a percentage of the track's width.
+const THUMB_INSET: f32 = 5.0;
+/// The thumb's width, as a percentage of the track's width.
+const THUMB_WIDTH: f32 = 40.0;
+
+/// Lives on the switch's button.
+#[derive(Component)]
+pub struct SwitchParts
+{
+	pub thumb: Entity,
+	/// The theme used when the switch is off.
+	pub off_theme: Theme,
+	/// How far the thumb has travelled from the off end (0.0) to the on end (1.0), before easing.
+	pub position: f32,
+}
+
+/// The theme and paint mode of the track in each state.
+fn track_paint(checked: bool, off_theme: Theme) -> (Theme, PaintMode)
+{
+	if checked
+		{ (Theme::Primary, PaintMode::Background) }
+	else
+		{ (off_theme, PaintMode::BackgroundContainer) }
+}
+
+fn thumb_left(position: f32) -> Val
+{
+	Val::Percent(THUMB_INSET + (100.0 - THUMB_WIDTH - 2.0 * THUMB_INSET) * position)
+}
+
+pub fn apply_switch_state<U: Component>
+(
+	switch_query: Query<(&CheckBoxState, &SwitchParts, Entity), Changed<CheckBoxState>>,
+	mut paint_query: Query<&mut ThemePaint<U>>,
+)
+{
+	for (state, parts, entity) in switch_query.iter()
+	{
+		let (theme, paint_mode) = track_paint(state.checked, parts.off_theme);
+		if let Ok(mut paint) = paint_query.get_mut(entity)
+		{
+			paint.theme = theme;
+			paint.paint_mode = paint_mode;
+		}
+		if let Ok(mut paint) = paint_query.get_mut(parts.thumb)
+			{ paint.theme = theme; }
+	}
+}
+
+pub fn animate_switch_thumbs<U: Component>
+(
+	time: Res<Time>,
+	theme_data: Res<CurrentThemeData<U>>,
+	mut switch_query: Query<(&CheckBoxState, &mut SwitchParts)>,
+	mut style_query: Query<&mut Style>,
+)
+{
+	let transition = theme_data.0.transition;
+	for (state, mut parts) in switch_query.iter_mut()
+	{
+		let target = if state.checked { 1.0 } else { 0.0 };
+		if parts.position == target
+			{ continue; }
+		let travel = if transition.duration <= 0.0 { 1.0 } else { time.delta_seconds() / transition.duration };
+		parts.position = if state.checked { (parts.position + travel).min(1.0) } else { (parts.position - travel).max(0.0) };
+		// Ease away from the end the thumb started at.
+		let eased = if state.checked { transition.easing.apply(parts.position) } else { 1.0 - transition.easing.apply(1.0 - parts.position) };
+		if let Ok(mut style) = style_query.get_mut(parts.thumb)
+			{ style.left = thumb_left(eased); }
+	}
+}
+
+pub struct Switch<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub base_button: BaseButton<U, M>,
+	pub initial_checked_state: bool,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Switch<U, M>
+{
+	pub fn new() -> Self
+	{
+		let mut base_button = BaseButton::new()
+			.with_auto_style(true)
+			.with_aspect_ratio(2f32);
+		base_button.button_bundle.border_radius = BorderRadius::MAX;
+		Self
+		{
+			base_button,
+			initial_checked_state: false,
+		}
+	}
+
+	pub fn with_checked(mut self, checked: bool) -> Self
+		{ self.initial_checked_state = checked; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Switch<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.base_button = self.base_button.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.base_button = self.base_button.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.base_button = self.base_button.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.base_button = self.base_button.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.base_button = self.base_button.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.base_button = self.base_button.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.base_button = self.base_button.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.base_button = self.base_button.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.base_button = self.base_button.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.base_button = self.base_button.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.base_button = self.base_button.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.base_button = self.base_button.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Switch<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let checked = self.initial_checked_state;
+		let off_theme = match self.base_button.theme
+		{
+			Theme::Auto => parent_data.resolve_theme(),
+			theme => theme,
+		};
+		let (theme, paint_mode) = track_paint(checked, off_theme);
+
+		let mut thumb = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Foreground)
+			.with_theme(theme);
+		thumb.node_bundle.style = Style
+		{
+			position_type: PositionType::Absolute,
+			left: thumb_left(if checked { 1.0 } else { 0.0 }),
+			top: Val::Percent(10.0),
+			width: Val::Percent(THUMB_WIDTH),
+			height: Val::Percent(80.0),
+			..Default::default()
+		};
+		thumb.node_bundle.border_radius = BorderRadius::MAX;
+		let thumb_entity = thumb.build(ui_tree, theme_data, parent_data, commands);
+
+		// Build the track with the current state's paint, the builder's own theme is left untouched.
+		let (builder_theme, builder_paint_mode) = (self.base_button.theme, self.base_button.paint_mode);
+		self.base_button.theme = theme;
+		self.base_button.paint_mode = paint_mode;
+		let button_entity = self.base_button.build(ui_tree, theme_data, parent_data, commands);
+		self.base_button.theme = builder_theme;
+		self.base_button.paint_mode = builder_paint_mode;
+
+		commands.entity(button_entity)
+			.push_children(&[thumb_entity])
+			.insert(CheckBoxState { checked })
+			.insert(SwitchParts
+			{
+				thumb: thumb_entity,
+				off_theme,
+				position: if checked { 1.0 } else { 0.0 },
+			});
+		button_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Switch<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_switch(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Switch::<TestUI>::new()
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn toggling_slides_the_thumb_and_repaints_the_track()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_switch);
+		app.update();
+		let (switch, thumb) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &SwitchParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.thumb)
+		};
+		assert_eq!(app.world().get::<Style>(thumb).unwrap().left, thumb_left(0.0));
+
+		*app.world_mut().get_mut::<Interaction>(switch).unwrap() = Interaction::Pressed;
+		app.update();
+		assert!(app.world().get::<CheckBoxState>(switch).unwrap().checked);
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(switch).unwrap().theme, Theme::Primary);
+
+		let duration = crate::theme::DARK.transition.duration;
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(duration / 2.0));
+		app.update();
+		let Val::Percent(halfway) = app.world().get::<Style>(thumb).unwrap().left
+			else { panic!("The thumb should be positioned with a percentage."); };
+		assert!(halfway > THUMB_INSET && halfway < 100.0 - THUMB_WIDTH - THUMB_INSET);
+
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(duration));
+		app.update();
+		assert_eq!(app.world().get::<Style>(thumb).unwrap().left, thumb_left(1.0));
+	}
+}
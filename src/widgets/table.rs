@@ -0,0 +1,623 @@
+/*
+	A table is a Column holding a header row above a ScrollView of rows. Every row is built up front, one cell per column.
+	Header and row cells get their width from the same TableColumnWidth, and the header ends with a spacer as wide as the scrollbar, so columns line up.
+	Values are type-erased once the table is built: TableSource keeps them (in their original order) with each column's comparison, and TableRow::value gets them back.
+	Pressing a sortable column's header sorts by it, pressing it again reverses the order. apply_table_sort reorders the rows and repaints their stripes.
+	Rows are BaseButtons, pressing one (or Enter) selects it, which lights up the indicator on its left edge.
+*/
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use super::*;
+use super::base_button::BaseButton;
+use super::scroll_view::SCROLLBAR_WIDTH;
+use crate::theme::{ThemeData, ThemePaint};
+
+/// The width of the selection indicator on the left of each row, in logical pixels.
+const ROW_INDICATOR_WIDTH: f32 = 4.0;
+
+/// A value of a built table, see TableRow::value.
+pub type TableValue = Arc<dyn Any + Send + Sync>;
+
+pub type TableComparison = Arc<dyn Fn(&TableValue, &TableValue) -> Ordering + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableColumnWidth
+{
+	/// Shares the width left by fixed columns with the other fill columns, in proportion to this.
+	Fill(f32),
+	/// In logical pixels.
+	Px(f32),
+}
+
+impl TableColumnWidth
+{
+	pub fn apply(&self, style: &mut Style)
+	{
+		match self
+		{
+			TableColumnWidth::Fill(portion) =>
+			{
+				style.width = Val::Auto;
+				// Without these, long cells would widen their column in some rows only.
+				style.min_width = Val::Px(0.0);
+				style.flex_basis = Val::Px(0.0);
+				style.flex_grow = *portion;
+			},
+			TableColumnWidth::Px(width) =>
+			{
+				style.width = Val::Px(*width);
+				style.flex_shrink = 0.0;
+			},
+		}
+	}
+}
+
+fn stripe_paint_mode(position: usize) -> PaintMode
+{
+	if position % 2 == 0
+		{ PaintMode::Background }
+	else
+		{ PaintMode::BackgroundContainer }
+}
+
+fn sort_indicator(sorted: bool, ascending: bool) -> &'static str
+{
+	match (sorted, ascending)
+	{
+		(false, _) => "",
+		(true, true) => "^",
+		(true, false) => "v",
+	}
+}
+
+/// Lives on a table.
+#[derive(Component, Clone)]
+pub struct TableSource
+{
+	/// In their original order, indexed by TableRow::index.
+	pub values: Vec<TableValue>,
+	/// One per column, None for columns that can't be sorted by.
+	pub comparisons: Vec<Option<TableComparison>>,
+}
+
+/// Lives on a table.
+#[derive(Component, Debug, Clone)]
+pub struct TableParts
+{
+	pub header: Entity,
+	/// One per column.
+	pub headers: Vec<Entity>,
+	pub body: Entity,
+	/// In the values' original order.
+	pub rows: Vec<Entity>,
+}
+
+/// The column the rows are sorted by, lives on a table.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort
+{
+	/// None keeps the values' original order.
+	pub column: Option<usize>,
+	pub ascending: bool,
+}
+
+impl Default for TableSort
+{
+	fn default() -> Self
+	{
+		Self { column: None, ascending: true }
+	}
+}
+
+/// The selected row, lives on a table.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TableSelection
+{
+	/// The index of the row's value.
+	pub selected: Option<usize>,
+}
+
+/// Lives on the header of a sortable column.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableHeader
+{
+	pub table: Entity,
+	pub column: usize,
+	/// The label showing which way the column is sorted.
+	pub indicator: Entity,
+}
+
+/// Lives on a row.
+#[derive(Component)]
+pub struct TableRow
+{
+	pub table: Entity,
+	/// The index of the row's value.
+	pub index: usize,
+	pub value: TableValue,
+	pub indicator: Entity,
+}
+
+impl TableRow
+{
+	/// The value this row was built from.
+	pub fn value<T: 'static>(&self) -> Option<&T>
+	{
+		self.value.downcast_ref()
+	}
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableSorted
+{
+	pub table: Entity,
+	pub column: usize,
+	pub ascending: bool,
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableRowSelected
+{
+	pub table: Entity,
+	pub row: Entity,
+	pub index: usize,
+}
+
+/// The indices of the values, in the order their rows are shown.
+pub fn sorted_table_rows(source: &TableSource, sort: &TableSort) -> Vec<usize>
+{
+	let mut order: Vec<usize> = (0..source.values.len()).collect();
+	let Some(comparison) = sort.column.and_then(|column| source.comparisons.get(column).cloned().flatten())
+		else { return order; };
+	// Stable, so equal values keep their original order both ways.
+	order.sort_by(|a, b|
+	{
+		let ordering = comparison(&source.values[*a], &source.values[*b]);
+		if sort.ascending { ordering } else { ordering.reverse() }
+	});
+	order
+}
+
+pub fn sort_tables_on_press
+(
+	header_query: Query<(&Interaction, &TableHeader), Changed<Interaction>>,
+	mut table_query: Query<&mut TableSort>,
+	mut sort_writer: EventWriter<TableSorted>,
+)
+{
+	for (interaction, header) in header_query.iter()
+	{
+		if *interaction != Interaction::Pressed
+			{ continue; }
+		let Ok(mut sort) = table_query.get_mut(header.table)
+			else { continue; };
+		let ascending = sort.column != Some(header.column) || !sort.ascending;
+		*sort = TableSort { column: Some(header.column), ascending };
+		sort_writer.send(TableSorted { table: header.table, column: header.column, ascending });
+	}
+}
+
+pub fn select_table_rows
+(
+	row_query: Query<(Entity, &Interaction, &TableRow), Changed<Interaction>>,
+	mut table_query: Query<&mut TableSelection>,
+	mut selection_writer: EventWriter<TableRowSelected>,
+)
+{
+	for (entity, interaction, row) in row_query.iter()
+	{
+		if *interaction != Interaction::Pressed
+			{ continue; }
+		let Ok(mut selection) = table_query.get_mut(row.table)
+			else { continue; };
+		if selection.selected != Some(row.index)
+		{
+			selection.selected = Some(row.index);
+			selection_writer.send(TableRowSelected { table: row.table, row: entity, index: row.index });
+		}
+	}
+}
+
+/// Reorders the rows of re-sorted tables, and repaints their stripes and the sort indicators.
+pub fn apply_table_sort<U: Component>
+(
+	mut commands: Commands,
+	table_query: Query<(&TableSort, &TableSource, &TableParts), Changed<TableSort>>,
+	scroll_query: Query<&ScrollViewParts>,
+	header_query: Query<&TableHeader>,
+	children_query: Query<&Children>,
+	mut text_query: Query<&mut Text>,
+	mut paint_query: Query<&mut ThemePaint<U>>,
+)
+{
+	for (sort, source, parts) in table_query.iter()
+	{
+		let rows: Vec<Entity> = sorted_table_rows(source, sort).into_iter().map(|index| parts.rows[index]).collect();
+		if let Ok(scroll_parts) = scroll_query.get(parts.body)
+			{ commands.entity(scroll_parts.content).replace_children(&rows); }
+		for (position, row) in rows.iter().enumerate()
+		{
+			if let Ok(mut paint) = paint_query.get_mut(*row)
+			{
+				if paint.paint_mode != stripe_paint_mode(position)
+					{ paint.paint_mode = stripe_paint_mode(position); }
+			}
+		}
+
+		for header in parts.headers.iter().filter_map(|header| header_query.get(*header).ok())
+		{
+			let indicator = sort_indicator(sort.column == Some(header.column), sort.ascending);
+			// The hierarchy is: TextLabel Container -> TextBundle.
+			if let Some(mut text) = children_query.get(header.indicator).ok().and_then(|children| text_query.get_mut(children[0]).ok())
+			{
+				if text.sections[0].value != indicator
+					{ text.sections[0].value = indicator.to_string(); }
+			}
+		}
+	}
+}
+
+/// Shows the indicator of the selected row.
+pub fn apply_table_selection<U: Component>
+(
+	table_query: Query<(&TableSelection, &TableParts), Changed<TableSelection>>,
+	row_query: Query<&TableRow>,
+	mut paint_query: Query<&mut ThemePaint<U>>,
+)
+{
+	for (selection, parts) in table_query.iter()
+	{
+		for row in parts.rows.iter().filter_map(|row| row_query.get(*row).ok())
+		{
+			let paint_mode = if selection.selected == Some(row.index) { PaintMode::Foreground } else { PaintMode::Invisible };
+			if let Ok(mut paint) = paint_query.get_mut(row.indicator)
+			{
+				if paint.paint_mode != paint_mode
+					{ paint.paint_mode = paint_mode; }
+			}
+		}
+	}
+}
+
+fn table_label<U: Component + Default>(text: impl Into<String>) -> TextLabel<U, ()>
+{
+	let mut label = TextLabel::new(text)
+		.with_paint_mode(PaintMode::Invisible)
+		.with_text_size(BASE_TEXT_SIZE);
+	label.container.node_bundle.style.width = Val::Auto;
+	label.container.node_bundle.style.height = Val::Auto;
+	label.label.text.justify = JustifyText::Left;
+	label
+}
+
+pub struct TableColumn<U, T>
+	where U: Component + Default
+{
+	pub title: String,
+	pub width: TableColumnWidth,
+	pub build_cell: Arc<dyn Fn(&T) -> Box<dyn WidgetBuilder<U>> + Send + Sync>,
+	/// Columns without one can't be sorted by.
+	pub compare: Option<Arc<dyn Fn(&T, &T) -> Ordering + Send + Sync>>,
+}
+
+impl<U: Component + Default, T> TableColumn<U, T>
+{
+	/// `build_cell` builds this column's cell for a row's value.
+	pub fn new<W: Into<Box<dyn WidgetBuilder<U>>>>(title: impl Into<String>, build_cell: impl Fn(&T) -> W + Send + Sync + 'static) -> Self
+	{
+		Self
+		{
+			title: title.into(),
+			width: TableColumnWidth::Fill(1.0),
+			build_cell: Arc::new(move |value| build_cell(value).into()),
+			compare: None,
+		}
+	}
+
+	/// A column of text labels.
+	pub fn text(title: impl Into<String>, text: impl Fn(&T) -> String + Send + Sync + 'static) -> Self
+	{
+		Self::new(title, move |value| table_label::<U>(text(value)))
+	}
+
+	pub fn with_width(mut self, width: TableColumnWidth) -> Self
+		{ self.width = width; self }
+
+	/// Makes the column sortable, `compare` orders values ascending.
+	pub fn with_compare(mut self, compare: impl Fn(&T, &T) -> Ordering + Send + Sync + 'static) -> Self
+		{ self.compare = Some(Arc::new(compare)); self }
+
+	/// Makes the column sortable by a key of the values.
+	pub fn with_sort_key<K: Ord>(self, key: impl Fn(&T) -> K + Send + Sync + 'static) -> Self
+		{ self.with_compare(move |a, b| key(a).cmp(&key(b))) }
+}
+
+pub struct Table<U, T>
+	where U: Component + Default, T: Clone + Send + Sync + 'static
+{
+	/// Holds the header row and the body.
+	pub container: Container<U, ()>,
+	pub columns: Vec<TableColumn<U, T>>,
+	pub rows: Vec<T>,
+	/// In logical pixels, the header has the same height.
+	pub row_height: f32,
+	pub sort: TableSort,
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> Table<U, T>
+{
+	pub fn new(rows: impl IntoIterator<Item = T>) -> Self
+	{
+		Self
+		{
+			container: Container::new()
+				.with_direction(FlexDirection::Column)
+				.with_paint_mode(PaintMode::Invisible),
+			columns: Vec::new(),
+			rows: rows.into_iter().collect(),
+			row_height: 36.0,
+			sort: TableSort::default(),
+		}
+	}
+
+	pub fn push_column(mut self, column: TableColumn<U, T>) -> Self
+		{ self.columns.push(column); self }
+
+	pub fn with_row_height(mut self, row_height: f32) -> Self
+		{ self.row_height = row_height; self }
+
+	/// Sorts the rows by a sortable column from the start.
+	pub fn with_sort(mut self, column: usize, ascending: bool) -> Self
+		{ self.sort = TableSort { column: Some(column), ascending }; self }
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> Widget for Table<U, T>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> WidgetBuilder<U> for Table<U, T>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let child_parent_data = parent_data.from_current(self.container.theme);
+
+		// The header, sortable columns get a button with a sort indicator.
+		let mut header_row = Container::<U, ()>::new()
+			.with_direction(FlexDirection::Row)
+			.with_paint_mode(PaintMode::BackgroundContainer);
+		header_row.node_bundle.style.height = Val::Px(self.row_height);
+		header_row.node_bundle.style.flex_shrink = 0.0;
+		header_row.node_bundle.style.padding = UiRect::left(Val::Px(ROW_INDICATOR_WIDTH));
+		let mut headers = Vec::new();
+		let mut sortable = Vec::new();
+		for (column_index, column) in self.columns.iter().enumerate()
+		{
+			let title = table_label::<U>(column.title.clone());
+			let header_entity = if column.compare.is_some()
+			{
+				let indicator = table_label::<U>(sort_indicator(self.sort.column == Some(column_index), self.sort.ascending))
+					.build(ui_tree, theme_data, child_parent_data, commands);
+				let mut button = BaseButton::<U, ()>::new()
+					.with_auto_style(true)
+					.with_paint_mode(PaintMode::BackgroundContainer)
+					.with_direction(FlexDirection::Row)
+					.push(title)
+					.push(indicator);
+				button.button_bundle.style.justify_content = JustifyContent::FlexStart;
+				button.button_bundle.style.column_gap = Val::Px(4.0);
+				column.width.apply(&mut button.button_bundle.style);
+				sortable.push((column_index, indicator));
+				button.build(ui_tree, theme_data, child_parent_data, commands)
+			}
+			else
+			{
+				let mut cell = Container::<U, ()>::new()
+					.with_paint_mode(PaintMode::Invisible)
+					.push(title);
+				cell.node_bundle.style.justify_content = JustifyContent::FlexStart;
+				column.width.apply(&mut cell.node_bundle.style);
+				cell.build(ui_tree, theme_data, child_parent_data, commands)
+			};
+			header_row = header_row.push(header_entity);
+			headers.push(header_entity);
+		}
+		// Lines the header up with the rows, which are narrowed by the scrollbar.
+		let mut spacer = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Invisible);
+		spacer.node_bundle.style.width = Val::Px(SCROLLBAR_WIDTH);
+		spacer.node_bundle.style.flex_shrink = 0.0;
+		let header_entity = header_row
+			.push(spacer)
+			.build(ui_tree, theme_data, child_parent_data, commands);
+
+		// The rows, in the values' original order, apply_table_sort sorts them on the next frame.
+		let source = TableSource
+		{
+			values: self.rows.iter().map(|value| Arc::new(value.clone()) as TableValue).collect(),
+			comparisons: self.columns.iter()
+				.map(|column| column.compare.clone().map(|compare| Arc::new(move |a: &TableValue, b: &TableValue|
+				{
+					let (Some(a), Some(b)) = (a.downcast_ref::<T>(), b.downcast_ref::<T>())
+						else { return Ordering::Equal; };
+					compare(a, b)
+				}) as TableComparison))
+				.collect(),
+		};
+		let mut body = ScrollView::<U, ()>::new()
+			.with_paint_mode(PaintMode::Background);
+		body.container.node_bundle.style.flex_grow = 1.0;
+		body.container.node_bundle.style.min_height = Val::Px(0.0);
+		let body_parent_data = child_parent_data.from_current(body.container.theme);
+		let mut rows = Vec::new();
+		for (index, value) in self.rows.iter().enumerate()
+		{
+			let mut indicator = Container::<U, ()>::new()
+				.with_paint_mode(PaintMode::Invisible);
+			indicator.node_bundle.style.width = Val::Px(ROW_INDICATOR_WIDTH);
+			indicator.node_bundle.style.flex_shrink = 0.0;
+			let indicator_entity = indicator.build(ui_tree, theme_data, body_parent_data, commands);
+			let mut row = BaseButton::<U, ()>::new()
+				.with_auto_style(true)
+				.with_paint_mode(stripe_paint_mode(index))
+				.with_direction(FlexDirection::Row)
+				.push(indicator_entity);
+			row.button_bundle.style.height = Val::Px(self.row_height);
+			row.button_bundle.style.flex_shrink = 0.0;
+			for column in self.columns.iter()
+			{
+				let mut cell = Container::<U, ()>::new()
+					.with_paint_mode(PaintMode::Invisible)
+					.push((column.build_cell)(value));
+				cell.node_bundle.style.justify_content = JustifyContent::FlexStart;
+				column.width.apply(&mut cell.node_bundle.style);
+				row = row.push(cell);
+			}
+			let row_entity = row.build(ui_tree, theme_data, body_parent_data, commands);
+			body.content.children.push(row_entity.into());
+			rows.push((row_entity, indicator_entity));
+		}
+		let body_entity = body.build(ui_tree, theme_data, child_parent_data, commands);
+
+		self.container.children.push(header_entity.into());
+		self.container.children.push(body_entity.into());
+		let table_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		self.container.children.clear();
+		for (column, indicator) in sortable
+		{
+			commands.entity(headers[column]).insert(TableHeader { table: table_entity, column, indicator });
+		}
+		for (index, (row, indicator)) in rows.iter().enumerate()
+		{
+			commands.entity(*row).insert(TableRow { table: table_entity, index, value: source.values[index].clone(), indicator: *indicator });
+		}
+		commands.entity(table_entity)
+			.insert(TableParts
+			{
+				header: header_entity,
+				headers,
+				body: body_entity,
+				rows: rows.into_iter().map(|(row, _)| row).collect(),
+			})
+			.insert(source)
+			.insert(self.sort)
+			.insert(TableSelection::default());
+		table_entity
+	}
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> Into<Box<dyn WidgetBuilder<U>>> for Table<U, T>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, press};
+
+	#[derive(Clone)]
+	struct Player
+	{
+		name: &'static str,
+		score: u32,
+	}
+
+	fn build_table(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		let players = [Player { name: "Ada", score: 20 }, Player { name: "Cy", score: 35 }, Player { name: "Bo", score: 10 }];
+		Table::<TestUI, Player>::new(players)
+			.push_column(TableColumn::<TestUI, Player>::text("Rank", |_| String::new()).with_width(TableColumnWidth::Px(60.0)))
+			.push_column(TableColumn::<TestUI, Player>::text("Name", |player| player.name.to_string()).with_sort_key(|player| player.name).with_width(TableColumnWidth::Fill(2.0)))
+			.push_column(TableColumn::<TestUI, Player>::text("Score", |player| player.score.to_string()).with_sort_key(|player| player.score))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	/// The names of the shown rows, from top to bottom.
+	fn shown_names(app: &mut App, table: Entity) -> Vec<&'static str>
+	{
+		let parts = app.world().get::<TableParts>(table).unwrap().clone();
+		let content = app.world().get::<ScrollViewParts>(parts.body).unwrap().content;
+		app.world().get::<Children>(content).unwrap().iter()
+			.map(|row| app.world().get::<TableRow>(*row).unwrap().value::<Player>().unwrap().name)
+			.collect()
+	}
+
+	fn indicator_text(app: &mut App, header: Entity) -> String
+	{
+		let indicator = app.world().get::<TableHeader>(header).unwrap().indicator;
+		let text = app.world().get::<Children>(indicator).unwrap()[0];
+		app.world().get::<Text>(text).unwrap().sections[0].value.clone()
+	}
+
+	#[test]
+	fn columns_sort_and_rows_select()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_table);
+		app.update();
+		let table = app.world_mut().query_filtered::<Entity, With<TableParts>>().single(app.world());
+		let parts = app.world().get::<TableParts>(table).unwrap().clone();
+		// Only the sortable columns have a header button.
+		assert!(app.world().get::<TableHeader>(parts.headers[0]).is_none());
+		assert_eq!(app.world().get::<TableHeader>(parts.headers[2]).unwrap().table, table);
+		assert_eq!(shown_names(&mut app, table), vec!["Ada", "Cy", "Bo"]);
+
+		// Sorting by score, then reversing it, restripes the rows by their new position.
+		press(&mut app, parts.headers[2]);
+		assert_eq!(*app.world().get::<TableSort>(table).unwrap(), TableSort { column: Some(2), ascending: true });
+		assert_eq!(shown_names(&mut app, table), vec!["Bo", "Ada", "Cy"]);
+		assert_eq!(indicator_text(&mut app, parts.headers[2]), "^");
+		press(&mut app, parts.headers[2]);
+		assert_eq!(shown_names(&mut app, table), vec!["Cy", "Ada", "Bo"]);
+		assert_eq!(indicator_text(&mut app, parts.headers[2]), "v");
+		let events = app.world().resource::<Events<TableSorted>>();
+		let event = events.get_reader().read(events).last().copied();
+		assert_eq!(event, Some(TableSorted { table, column: 2, ascending: false }));
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(parts.rows[1]).unwrap().paint_mode, PaintMode::Background);
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(parts.rows[0]).unwrap().paint_mode, PaintMode::BackgroundContainer);
+
+		// Sorting by another column starts ascending, and clears the other indicator.
+		press(&mut app, parts.headers[1]);
+		assert_eq!(shown_names(&mut app, table), vec!["Ada", "Bo", "Cy"]);
+		assert_eq!(indicator_text(&mut app, parts.headers[2]), "");
+
+		press(&mut app, parts.rows[1]);
+		assert_eq!(app.world().get::<TableSelection>(table).unwrap().selected, Some(1));
+		let events = app.world().resource::<Events<TableRowSelected>>();
+		let event = events.get_reader().read(events).last().copied();
+		assert_eq!(event, Some(TableRowSelected { table, row: parts.rows[1], index: 1 }));
+		let indicator = app.world().get::<TableRow>(parts.rows[1]).unwrap().indicator;
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(indicator).unwrap().paint_mode, PaintMode::Foreground);
+	}
+}
@@ -0,0 +1,383 @@
+/*
+	Tabs are a Column holding a tab bar (a Row of TextButtons) and a content area holding one panel per tab.
+	Inactive panels are hidden with Display::None rather than despawned, so their state is kept.
+	Each panel is a navigation sub-menu of its tab button: activating a tab enters its panel, and cancelling goes back to the tab bar.
+	The active tab is painted like the content area, the others like the tab bar.
+*/
+
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy::input::gamepad::{Gamepads, GamepadButtonType};
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::{ThemeData, ThemePaint};
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveTab
+{
+	pub index: usize,
+}
+
+/// The entities that make up a tabs widget, lives on the tabs widget.
+#[derive(Component)]
+pub struct TabsParts
+{
+	pub buttons: Vec<Entity>,
+	pub panels: Vec<Entity>,
+}
+
+/// Lives on each tab button.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabButton
+{
+	/// The tabs widget this button belongs to.
+	pub tabs: Entity,
+	pub index: usize,
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TabChanged
+{
+	pub tabs: Entity,
+	pub index: usize,
+}
+
+fn tab_paint_mode(active: bool) -> PaintMode
+{
+	if active
+		{ PaintMode::Background }
+	else
+		{ PaintMode::BackgroundContainer }
+}
+
+pub fn select_tab_on_press
+(
+	button_query: Query<(&Interaction, &TabButton), Changed<Interaction>>,
+	mut tabs_query: Query<&mut ActiveTab>,
+	mut tab_writer: EventWriter<TabChanged>,
+)
+{
+	for (interaction, button) in button_query.iter()
+	{
+		if *interaction != Interaction::Pressed
+			{ continue; }
+		let Ok(mut active_tab) = tabs_query.get_mut(button.tabs)
+			else { continue; };
+		if active_tab.index != button.index
+		{
+			active_tab.index = button.index;
+			tab_writer.send(TabChanged { tabs: button.tabs, index: button.index });
+		}
+	}
+}
+
+/// Is the entity shown, i.e. not inside a hidden panel (or anything else with Display::None)?
+fn is_displayed(entity: Entity, style_query: &Query<&Style>, parent_query: &Query<&Parent>) -> bool
+{
+	std::iter::once(entity)
+		.chain(parent_query.iter_ancestors(entity))
+		.all(|entity| style_query.get(entity).map_or(true, |style| style.display != Display::None))
+}
+
+/// Switches the tabs widget holding the focus. With the focus elsewhere, it switches the last tabs widget that held the focus,
+/// or if that one is gone or hidden, the outermost shown tabs widget.
+#[allow(clippy::too_many_arguments)]
+pub fn switch_tabs_with_shortcuts
+(
+	mut tabs_query: Query<(Entity, &mut ActiveTab, &TabsParts)>,
+	focus_query: Query<(Entity, &Focusable)>,
+	parent_query: Query<&Parent>,
+	style_query: Query<&Style>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	gamepads: Res<Gamepads>,
+	mut nav_writer: EventWriter<NavRequest>,
+	mut tab_writer: EventWriter<TabChanged>,
+	mut last_focused_tabs: Local<Option<Entity>>,
+)
+{
+	let focused = focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused).map(|(entity, _)| entity);
+	let focused_tabs = focused.and_then(|focused| parent_query.iter_ancestors(focused).find(|ancestor| tabs_query.contains(*ancestor)));
+	if focused_tabs.is_some()
+		{ *last_focused_tabs = focused_tabs; }
+
+	let control = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+	let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+	let tab = control && keyboard_input.just_pressed(KeyCode::Tab);
+	let pad_pressed = |button_type| gamepads.iter().any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)));
+	let step: isize = if (tab && !shift) || pad_pressed(GamepadButtonType::RightTrigger)
+		{ 1 }
+	else if (tab && shift) || pad_pressed(GamepadButtonType::LeftTrigger)
+		{ -1 }
+	else
+		{ return; };
+
+	let shown = |tabs: &Entity| tabs_query.contains(*tabs) && is_displayed(*tabs, &style_query, &parent_query);
+	let target = focused_tabs
+		.or(last_focused_tabs.filter(shown))
+		.or_else(|| tabs_query.iter()
+			.map(|(tabs, _, _)| tabs)
+			.filter(shown)
+			.min_by_key(|tabs| parent_query.iter_ancestors(*tabs).count()));
+	let Some(target) = target
+		else { return; };
+	let Ok((tabs, mut active_tab, parts)) = tabs_query.get_mut(target)
+		else { return; };
+	let count = parts.buttons.len() as isize;
+	if count == 0
+		{ return; }
+	active_tab.index = (active_tab.index as isize + step).rem_euclid(count) as usize;
+	tab_writer.send(TabChanged { tabs, index: active_tab.index });
+	// The previous panel is about to be hidden, so don't leave the focus inside it.
+	if focused_tabs.is_some()
+		{ nav_writer.send(NavRequest::FocusOn(parts.buttons[active_tab.index])); }
+}
+
+pub fn apply_active_tab<U: Component>
+(
+	tabs_query: Query<(&ActiveTab, &TabsParts), Changed<ActiveTab>>,
+	mut style_query: Query<&mut Style>,
+	mut paint_query: Query<&mut ThemePaint<U>>,
+)
+{
+	for (active_tab, parts) in tabs_query.iter()
+	{
+		for (index, (button, panel)) in parts.buttons.iter().zip(parts.panels.iter()).enumerate()
+		{
+			let active = index == active_tab.index;
+			if let Ok(mut style) = style_query.get_mut(*panel)
+				{ style.display = if active { Display::Flex } else { Display::None }; }
+			if let Ok(mut paint) = paint_query.get_mut(*button)
+			{
+				if paint.paint_mode != tab_paint_mode(active)
+					{ paint.paint_mode = tab_paint_mode(active); }
+			}
+		}
+	}
+}
+
+pub struct Tabs<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	/// Holds the tab bar and the content area.
+	pub container: Container<U, M>,
+	pub tabs: Vec<(String, Box<dyn WidgetBuilder<U>>)>,
+	/// The index of the initially active tab.
+	pub active: usize,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Tabs<U, M>
+{
+	pub fn new() -> Self
+	{
+		Self
+		{
+			container: Container::new()
+				.with_direction(FlexDirection::Column)
+				.with_paint_mode(PaintMode::Invisible),
+			tabs: Vec::new(),
+			active: 0,
+		}
+	}
+
+	pub fn push_tab(mut self, title: impl Into<String>, panel: impl Into<Box<dyn WidgetBuilder<U>>>) -> Self
+	{
+		self.tabs.push((title.into(), panel.into()));
+		self
+	}
+
+	pub fn with_active(mut self, active: usize) -> Self
+		{ self.active = active; self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Tabs<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Tabs<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let active = self.active.min(self.tabs.len().saturating_sub(1));
+		let child_parent_data = parent_data.from_current(self.container.theme);
+		let mut tab_bar = Container::<U, ()>::new()
+			.with_direction(FlexDirection::Row)
+			.with_fill_portion(0.1);
+		let mut content = Container::<U, ()>::new()
+			.with_paint_mode(PaintMode::Invisible)
+			.with_fill_portion(0.9);
+		let mut buttons = Vec::new();
+		let mut panels = Vec::new();
+		for (index, (title, panel)) in self.tabs.iter_mut().enumerate()
+		{
+			let button_entity = TextButton::<U, ()>::new(title.clone())
+				.with_paint_mode(tab_paint_mode(index == active))
+				.build(ui_tree, theme_data, child_parent_data, commands);
+			tab_bar = tab_bar.push(button_entity);
+
+			let panel_entity = panel.build(ui_tree, theme_data, child_parent_data, commands);
+			let mut panel_container = Container::<U, ()>::new()
+				.with_paint_mode(PaintMode::Background)
+				.push(panel_entity);
+			if index != active
+				{ panel_container.node_bundle.style.display = Display::None; }
+			let panel_container_entity = panel_container.build(ui_tree, theme_data, child_parent_data, commands);
+			commands.entity(panel_container_entity)
+				.insert(MenuSetting::new())
+				.insert(MenuBuilder::EntityParent(button_entity));
+			content = content.push(panel_container_entity);
+
+			buttons.push(button_entity);
+			panels.push(panel_container_entity);
+		}
+		let tab_bar_entity = tab_bar.build(ui_tree, theme_data, child_parent_data, commands);
+		let content_entity = content.build(ui_tree, theme_data, child_parent_data, commands);
+
+		self.container.children.push(tab_bar_entity.into());
+		self.container.children.push(content_entity.into());
+		let tabs_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		self.container.children.clear();
+
+		for (index, button) in buttons.iter().enumerate()
+		{
+			commands.entity(*button).insert(TabButton { tabs: tabs_entity, index });
+		}
+		commands.entity(tabs_entity)
+			.insert(ActiveTab { index: active })
+			.insert(TabsParts { buttons, panels });
+		tabs_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Tabs<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_tabs(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Tabs::<TestUI>::new()
+			.push_tab("Video", TextButton::<TestUI>::new("Resolution"))
+			.push_tab("Audio", TextButton::<TestUI>::new("Volume"))
+			.push_tab("Controls", TextButton::<TestUI>::new("Sensitivity"))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn switching_tabs_hides_the_other_panels()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_tabs);
+		app.update();
+		let (tabs, buttons, panels) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &TabsParts)>();
+			let (entity, parts) = query.single(app.world());
+			(entity, parts.buttons.clone(), parts.panels.clone())
+		};
+		let shown_panels = |app: &App| panels.iter().map(|panel| app.world().get::<Style>(*panel).unwrap().display == Display::Flex).collect::<Vec<_>>();
+		assert_eq!(shown_panels(&app), [true, false, false]);
+
+		*app.world_mut().get_mut::<Interaction>(buttons[2]).unwrap() = Interaction::Pressed;
+		app.update();
+		assert_eq!(app.world().get::<ActiveTab>(tabs).unwrap().index, 2);
+		assert_eq!(shown_panels(&app), [false, false, true]);
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(buttons[2]).unwrap().paint_mode, PaintMode::Background);
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(buttons[0]).unwrap().paint_mode, PaintMode::BackgroundContainer);
+
+		// Ctrl+Tab wraps around to the first tab.
+		let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+		keyboard_input.press(KeyCode::ControlLeft);
+		keyboard_input.press(KeyCode::Tab);
+		app.update();
+		assert_eq!(app.world().get::<ActiveTab>(tabs).unwrap().index, 0);
+		assert_eq!(shown_panels(&app), [true, false, false]);
+		// Panels are kept alive.
+		assert!(panels.iter().all(|panel| app.world().get_entity(*panel).is_some()));
+	}
+
+	fn build_two_tabs(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		let inner = Tabs::<TestUI>::new()
+			.push_tab("Keyboard", TextButton::<TestUI>::new("Bindings"))
+			.push_tab("Gamepad", TextButton::<TestUI>::new("Vibration"));
+		Tabs::<TestUI>::new()
+			.push_tab("Controls", inner)
+			.push_tab("Audio", TextButton::<TestUI>::new("Volume"))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		TextButton::<TestUI>::new("Back")
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn shortcuts_switch_the_last_focused_tabs()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_two_tabs);
+		app.update();
+		let mut tabs: Vec<(Entity, Vec<Entity>)> = app.world_mut().query::<(Entity, &TabsParts)>().iter(app.world())
+			.map(|(entity, parts)| (entity, parts.buttons.clone()))
+			.collect();
+		// The inner tabs are the ones with a parent.
+		tabs.sort_by_key(|(entity, _)| std::cmp::Reverse(app.world().get::<Parent>(*entity).is_some()));
+		let ((inner, inner_buttons), (outer, _)) = (tabs[0].clone(), tabs[1].clone());
+		let back = app.world_mut().query_filtered::<Entity, (With<Focusable>, Without<TabButton>, Without<Parent>)>().single(app.world());
+		let switch = |app: &mut App|
+		{
+			let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+			keyboard_input.press(KeyCode::ControlLeft);
+			keyboard_input.press(KeyCode::Tab);
+			app.update();
+			let mut keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+			keyboard_input.release_all();
+			keyboard_input.clear();
+		};
+
+		// Nothing was focused yet, so the outermost tabs switch.
+		switch(&mut app);
+		assert_eq!(app.world().get::<ActiveTab>(outer).unwrap().index, 1);
+		assert_eq!(app.world().get::<ActiveTab>(inner).unwrap().index, 0);
+		switch(&mut app);
+
+		// After focusing the inner tabs and leaving them, they are switched.
+		app.world_mut().send_event(NavRequest::FocusOn(inner_buttons[0]));
+		app.update();
+		app.world_mut().send_event(NavRequest::FocusOn(back));
+		app.update();
+		switch(&mut app);
+		assert_eq!(app.world().get::<ActiveTab>(inner).unwrap().index, 1);
+		assert_eq!(app.world().get::<ActiveTab>(outer).unwrap().index, 0);
+	}
+}
@@ -0,0 +1,456 @@
+/*
+	A text area is a ScrollView whose content is a row holding the optional line numbers and the editor, a TextLabel aligned to the top left.
+	The editor is a multi-line text input (EditableText, EditCursor and AllowsNewlines), so handle_text_input does the typing,
+	and its text is split into three sections: the text before the caret, the caret, and the text after it.
+	Up/Down move the caret between lines, keeping the column it started in across lines too short for it, PageUp/PageDown move it by a viewport of lines.
+	Once the text has been laid out, the caret's glyph is scrolled into view and the line numbers are spaced out to follow wrapped lines.
+*/
+
+use bevy::text::{PositionedGlyph, TextLayoutInfo};
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::ThemeData;
+
+/// The height of a line of text, as a multiple of the font size.
+const LINE_HEIGHT: f32 = 1.2;
+const CARET: &str = "|";
+
+/// Lives on the text area.
+#[derive(Component)]
+pub struct TextAreaParts
+{
+	pub editor: Entity,
+	pub line_numbers: Option<Entity>,
+}
+
+/// Lives on a text area's editor.
+#[derive(Component)]
+pub struct TextAreaEditor
+{
+	pub text_area: Entity,
+	/// The column Up/Down keep the caret in, forgotten as soon as the caret moves any other way.
+	pub column: Option<usize>,
+	pub last_position: usize,
+}
+
+/// The closest character boundary at or before `position`.
+fn floor_char_boundary(text: &str, position: usize) -> usize
+{
+	let mut position = position.min(text.len());
+	while !text.is_char_boundary(position)
+		{ position -= 1; }
+	position
+}
+
+/// The line and column (in characters) of a caret at byte `position`.
+pub fn caret_line_column(text: &str, position: usize) -> (usize, usize)
+{
+	let before = &text[..floor_char_boundary(text, position)];
+	let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+	(before.matches('\n').count(), before[line_start..].chars().count())
+}
+
+/// The byte position of `column` on `line`, or of the end of the line if it is shorter.
+pub fn caret_position(text: &str, line: usize, column: usize) -> usize
+{
+	let mut line_start = 0;
+	for (index, text_line) in text.split('\n').enumerate()
+	{
+		if index == line
+			{ return line_start + text_line.char_indices().nth(column).map_or(text_line.len(), |(byte, _)| byte); }
+		line_start += text_line.len() + 1;
+	}
+	text.len()
+}
+
+/// The line numbers for lines taking up `rows` rows each, wrapped lines leave blank rows after their number.
+pub fn line_numbers(rows: &[usize]) -> String
+{
+	rows.iter().enumerate()
+		.map(|(index, rows)| format!("{}{}", index + 1, "\n".repeat(rows.saturating_sub(1))))
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+/// How many rows each line of `text` takes up, from the glyphs of an editor with its caret at byte `caret`.
+fn wrapped_rows(text: &str, caret: usize, glyphs: &[PositionedGlyph], row_height: f32) -> Vec<usize>
+{
+	let line_starts: Vec<usize> = std::iter::once(0).chain(text.match_indices('\n').map(|(index, _)| index + 1)).collect();
+	let mut rows = vec![1; line_starts.len()];
+	// The line and the top of the row the previous glyph was on.
+	let mut row: Option<(usize, f32)> = None;
+	for glyph in glyphs
+	{
+		let byte = match glyph.section_index
+		{
+			0 => glyph.byte_index,
+			2 => caret + glyph.byte_index,
+			_ => continue,
+		};
+		let line = line_starts.partition_point(|start| *start <= byte).saturating_sub(1).min(rows.len() - 1);
+		match row
+		{
+			Some((row_line, row_top)) if row_line == line && glyph.position.y - row_top < row_height / 2.0 => {},
+			Some((row_line, _)) if row_line == line =>
+			{
+				rows[line] += 1;
+				row = Some((line, glyph.position.y));
+			},
+			_ => row = Some((line, glyph.position.y)),
+		}
+	}
+	rows
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn move_text_area_caret
+(
+	mut editor_query: Query<(Entity, &EditableText, &mut EditCursor, &mut TextAreaEditor, &Focusable, &Children)>,
+	parts_query: Query<&ScrollViewParts>,
+	node_query: Query<&Node>,
+	text_query: Query<&Text>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	// The navigation crate reads the same arrow keys, so it may have just moved focus away from the editor.
+	let left_editor = nav_events.read().find_map(|event| match event
+	{
+		NavEvent::FocusChanged { to, from } if to.first() != from.first() && editor_query.contains(*from.first()) => Some(*from.first()),
+		_ => None,
+	});
+
+	for (entity, text, mut cursor, mut editor, focusable, children) in editor_query.iter_mut()
+	{
+		if editor.last_position != cursor.position
+			{ editor.column = None; }
+		let was_focused = left_editor == Some(entity);
+		if focusable.state() != FocusState::Focused && !was_focused
+		{
+			editor.last_position = cursor.position;
+			continue;
+		}
+
+		let font_size = text_query.get(children[0]).ok().and_then(|text| text.sections.first()).map_or(BASE_TEXT_SIZE, |section| section.style.font_size);
+		let viewport_height = parts_query.get(editor.text_area).ok().and_then(|parts| node_query.get(parts.viewport).ok()).map_or(0.0, |node| node.size().y);
+		let page = (viewport_height / (font_size * LINE_HEIGHT)).floor().max(1.0) as isize;
+		let lines =
+			if keyboard_input.just_pressed(KeyCode::ArrowUp) { -1 }
+			else if keyboard_input.just_pressed(KeyCode::ArrowDown) { 1 }
+			else if keyboard_input.just_pressed(KeyCode::PageUp) { -page }
+			else if keyboard_input.just_pressed(KeyCode::PageDown) { page }
+			else { 0 };
+		if lines == 0
+		{
+			editor.last_position = cursor.position;
+			continue;
+		}
+		// Up and down move the caret, so take the focus back.
+		if was_focused
+			{ nav_writer.send(NavRequest::FocusOn(entity)); }
+
+		let (line, column) = caret_line_column(&text.text, cursor.position);
+		let column = *editor.column.get_or_insert(column);
+		let last_line = text.text.matches('\n').count() as isize;
+		let target = line as isize + lines;
+		// Moving past the first or last line goes to the start or end of the text, like most editors.
+		let position = if target < 0
+			{ 0 }
+		else if target > last_line
+			{ text.text.len() }
+		else
+			{ caret_position(&text.text, target as usize, column) };
+		if cursor.position != position
+			{ cursor.position = position; }
+		editor.last_position = position;
+	}
+}
+
+pub fn update_text_area_sections
+(
+	editor_query: Query<(&EditableText, &EditCursor, &Focusable, Option<&PlaceholderText>, &Children), (With<TextAreaEditor>, Or<(Changed<EditableText>, Changed<EditCursor>, Changed<Focusable>)>)>,
+	mut text_query: Query<&mut Text>,
+)
+{
+	for (text, cursor, focusable, placeholder, children) in editor_query.iter()
+	{
+		let Ok(mut text_bundle) = text_query.get_mut(children[0])
+			else { continue; };
+		let style = text_bundle.sections[0].style.clone();
+		let focused = focusable.state() == FocusState::Focused;
+		let caret = floor_char_boundary(&text.text, cursor.position);
+		let (before, after) = match placeholder
+		{
+			Some(placeholder) if text.text.is_empty() && !focused => (placeholder.text.clone(), String::new()),
+			_ => (text.text[..caret].to_string(), text.text[caret..].to_string()),
+		};
+		let caret = if focused { CARET } else { "" };
+		text_bundle.sections = vec!
+		[
+			TextSection::new(before, style.clone()),
+			TextSection::new(caret, style.clone()),
+			TextSection::new(after, style),
+		];
+	}
+}
+
+pub fn update_line_numbers
+(
+	text_area_query: Query<&TextAreaParts>,
+	editor_query: Query<(Ref<EditableText>, &EditCursor, &Children)>,
+	children_query: Query<&Children>,
+	mut text_query: Query<(&mut Text, Ref<TextLayoutInfo>)>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+)
+{
+	let scale_factor = window_query.get_single().map_or(1.0, |window| window.scale_factor());
+	for parts in text_area_query.iter()
+	{
+		let (Some(numbers_entity), Ok((text, cursor, children))) = (parts.line_numbers, editor_query.get(parts.editor))
+			else { continue; };
+		let Some(numbers_text) = children_query.get(numbers_entity).ok().map(|children| children[0])
+			else { continue; };
+		let rows =
+		{
+			let Ok((editor_text, layout)) = text_query.get(children[0])
+				else { continue; };
+			if !text.is_changed() && !layout.is_changed()
+				{ continue; }
+			// Glyphs are positioned in physical pixels.
+			let row_height = editor_text.sections[0].style.font_size * scale_factor;
+			wrapped_rows(&text.text, floor_char_boundary(&text.text, cursor.position), &layout.glyphs, row_height)
+		};
+		let Ok((mut numbers, _)) = text_query.get_mut(numbers_text)
+			else { continue; };
+		let value = line_numbers(&rows);
+		if numbers.sections[0].value != value
+			{ numbers.sections[0].value = value; }
+	}
+}
+
+/// Keeps the caret of a focused text area visible, once its text has been laid out.
+pub fn scroll_to_caret
+(
+	mut text_area_query: Query<(&TextAreaParts, &ScrollViewParts, &mut ScrollOffset)>,
+	editor_query: Query<(&Focusable, &Children)>,
+	layout_query: Query<(Ref<TextLayoutInfo>, &Node, &GlobalTransform)>,
+	rect_query: Query<(&Node, &GlobalTransform)>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+)
+{
+	let scale_factor = window_query.get_single().map_or(1.0, |window| window.scale_factor());
+	for (parts, scroll_parts, mut scroll) in text_area_query.iter_mut()
+	{
+		let Ok((focusable, children)) = editor_query.get(parts.editor)
+			else { continue; };
+		if focusable.state() != FocusState::Focused
+			{ continue; }
+		let Ok((layout, node, transform)) = layout_query.get(children[0])
+			else { continue; };
+		if !layout.is_changed()
+			{ continue; }
+		let (Some(caret), Ok((viewport_node, viewport_transform))) = (layout.glyphs.iter().find(|glyph| glyph.section_index == 1), rect_query.get(scroll_parts.viewport))
+			else { continue; };
+		let caret_rect = Rect::from_center_size(node.logical_rect(transform).min + caret.position / scale_factor, caret.size / scale_factor);
+		let new_offset = scroll_into_view(scroll.offset, viewport_node.logical_rect(viewport_transform), caret_rect);
+		if new_offset != scroll.offset
+			{ scroll.offset = new_offset; }
+	}
+}
+
+pub struct TextArea<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub scroll_view: ScrollView<U, M>,
+	pub editor: TextLabel<U, ()>,
+	pub line_numbers: Option<TextLabel<U, ()>>,
+	pub text: String,
+	pub placeholder: Option<String>,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> TextArea<U, M>
+{
+	pub fn new() -> Self
+	{
+		let mut scroll_view = ScrollView::new()
+			.with_direction(FlexDirection::Row);
+		scroll_view.content.node_bundle.style.width = Val::Percent(100.0);
+		scroll_view.content.node_bundle.style.align_items = AlignItems::FlexStart;
+
+		let mut editor = TextLabel::new("")
+			.with_text_size(BASE_TEXT_SIZE)
+			.with_paint_mode(PaintMode::Invisible)
+			.with_padding(UiRect::all(Val::Px(4.0)));
+		editor.label.text.justify = JustifyText::Left;
+		let style = &mut editor.container.node_bundle.style;
+		style.width = Val::Auto;
+		style.height = Val::Auto;
+		style.flex_grow = 1.0;
+		style.min_height = Val::Percent(100.0);
+		style.justify_content = JustifyContent::FlexStart;
+		style.align_items = AlignItems::FlexStart;
+		Self
+		{
+			scroll_view,
+			editor,
+			line_numbers: None,
+			text: String::new(),
+			placeholder: None,
+		}
+	}
+
+	pub fn with_text(mut self, text: impl Into<String>) -> Self
+		{ self.text = text.into(); self }
+
+	pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self
+		{ self.placeholder = Some(placeholder.into()); self }
+
+	/// Shows the number of each line on the left.
+	pub fn with_line_numbers(mut self, line_numbers: bool) -> Self
+	{
+		self.line_numbers = line_numbers.then(||
+		{
+			let mut label = TextLabel::new("1")
+				.with_text_size(self.editor.fixed_text_size.unwrap_or(BASE_TEXT_SIZE))
+				.with_paint_mode(PaintMode::Invisible)
+				.with_padding(UiRect::new(Val::Px(4.0), Val::Px(8.0), Val::Px(4.0), Val::Px(4.0)));
+			label.label.text.justify = JustifyText::Right;
+			let style = &mut label.container.node_bundle.style;
+			style.width = Val::Auto;
+			style.height = Val::Auto;
+			style.flex_shrink = 0.0;
+			style.align_items = AlignItems::FlexStart;
+			label
+		});
+		self
+	}
+
+	pub fn with_text_size(mut self, text_size: f32) -> Self
+	{
+		self.editor = self.editor.with_text_size(text_size);
+		self.line_numbers = self.line_numbers.map(|label| label.with_text_size(text_size));
+		self
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for TextArea<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.scroll_view = self.scroll_view.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.scroll_view = self.scroll_view.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.scroll_view = self.scroll_view.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.scroll_view = self.scroll_view.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.scroll_view = self.scroll_view.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.scroll_view = self.scroll_view.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.scroll_view = self.scroll_view.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.scroll_view = self.scroll_view.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.scroll_view = self.scroll_view.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for TextArea<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let child_parent_data = parent_data.from_current(self.scroll_view.container.theme);
+		let line_numbers_entity = self.line_numbers.as_mut().map(|label| label.build(ui_tree, theme_data, child_parent_data, commands));
+		let editor_entity = self.editor.build(ui_tree, theme_data, child_parent_data, commands);
+		let mut editor = commands.entity(editor_entity);
+		editor
+			.insert(Focusable::default())
+			.insert(EditableText { text: self.text.clone() })
+			.insert(EditCursor { position: self.text.len() })
+			.insert(AllowsNewlines);
+		if let Some(placeholder) = &self.placeholder
+			{ editor.insert(PlaceholderText { text: placeholder.clone() }); }
+
+		if let Some(line_numbers_entity) = line_numbers_entity
+			{ self.scroll_view.content.children.push(line_numbers_entity.into()); }
+		self.scroll_view.content.children.push(editor_entity.into());
+		let text_area_entity = self.scroll_view.build(ui_tree, theme_data, parent_data, commands);
+		self.scroll_view.content.children.clear();
+
+		commands.entity(editor_entity)
+			.insert(TextAreaEditor { text_area: text_area_entity, column: None, last_position: self.text.len() });
+		commands.entity(text_area_entity)
+			.insert(TextAreaParts { editor: editor_entity, line_numbers: line_numbers_entity });
+		text_area_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for TextArea<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, press_key};
+
+	const NOTE: &str = "Buy milk\nok\nCall the bank";
+
+	fn build_text_area(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		TextArea::<TestUI>::new()
+			.with_text(NOTE)
+			.with_line_numbers(true)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn carets_keep_their_column_between_lines()
+	{
+		assert_eq!(caret_line_column(NOTE, 13), (2, 1));
+		assert_eq!(caret_position(NOTE, 1, 7), 11);
+		assert_eq!(line_numbers(&[1, 3, 1]), "1\n2\n\n\n3");
+
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_text_area);
+		app.update();
+		let (editor, line_numbers) =
+		{
+			let mut query = app.world_mut().query::<&TextAreaParts>();
+			let parts = query.single(app.world());
+			(parts.editor, parts.line_numbers.unwrap())
+		};
+		let caret = |app: &App| app.world().get::<EditCursor>(editor).unwrap().position;
+		let text_of = |app: &App, entity: Entity| app.world().get::<Text>(app.world().get::<Children>(entity).unwrap()[0]).unwrap().sections.iter().map(|section| section.value.clone()).collect::<Vec<_>>();
+		app.update();
+		assert_eq!(text_of(&app, line_numbers), ["1\n2\n3"]);
+
+		app.world_mut().send_event(NavRequest::FocusOn(editor));
+		app.update();
+		app.world_mut().get_mut::<EditCursor>(editor).unwrap().position = 7;
+		press_key(&mut app, KeyCode::ArrowDown);
+		// "ok" is too short, so the caret goes to its end.
+		assert_eq!(caret(&app), 11);
+		press_key(&mut app, KeyCode::ArrowDown);
+		// And back to the column it started in.
+		assert_eq!(caret(&app), 19);
+		assert_eq!(text_of(&app, editor), ["Buy milk\nok\nCall th", "|", "e bank"]);
+		press_key(&mut app, KeyCode::Home);
+		assert_eq!(caret(&app), 12);
+		press_key(&mut app, KeyCode::PageUp);
+		assert_eq!(caret(&app), 9);
+	}
+}
@@ -28,9 +28,13 @@ pub struct PlaceholderText
 #[derive(Component, Default)]
 pub struct AllowsNewlines;
 
+/// Only characters the function accepts can be typed into the input.
+#[derive(Component, Clone, Copy)]
+pub struct InputFilter(pub fn(char) -> bool);
+
 pub fn update_text_sections
 (
-	mut query: Query<(&Children, &EditableText, Option<&PlaceholderText>), Changed<EditableText>>,
+	mut query: Query<(&Children, &EditableText, Option<&PlaceholderText>), (Changed<EditableText>, Without<super::text_area::TextAreaEditor>)>,
 	mut text_query: Query<&mut Text>,
 )
 {
@@ -52,12 +56,12 @@ pub fn update_text_sections
 // TODO: Support IME
 pub fn handle_text_input
 (
-	mut query: Query<(&mut EditableText, &mut EditCursor, &Focusable, Option<&AllowsNewlines>)>,
+	mut query: Query<(&mut EditableText, &mut EditCursor, &Focusable, Option<&AllowsNewlines>, Option<&InputFilter>)>,
 	mut text_input: EventReader<ReceivedCharacter>,
 	keyboard_input: ResMut<ButtonInput<KeyCode>>,
 )
 {
-	for (mut text, mut cursor, focusable, allows_newlines) in query.iter_mut()
+	for (mut text, mut cursor, focusable, allows_newlines, filter) in query.iter_mut()
 	{
 		if focusable.state() != FocusState::Focused
 			{ continue; }
@@ -98,13 +102,22 @@ pub fn handle_text_input
 				cursor.position += 1;
 			}
 		}
+		// Multi-line inputs go to the start or end of the current line.
 		else if keyboard_input.just_pressed(KeyCode::Home)
 		{
-			cursor.position = 0;
+			cursor.position = match allows_newlines
+			{
+				Some(_) => text.text[..cursor.position].rfind('\n').map_or(0, |index| index + 1),
+				None => 0,
+			};
 		}
 		else if keyboard_input.just_pressed(KeyCode::End)
 		{
-			cursor.position = text.text.len();
+			cursor.position = match allows_newlines
+			{
+				Some(_) => text.text[cursor.position..].find('\n').map_or(text.text.len(), |index| cursor.position + index),
+				None => text.text.len(),
+			};
 		}
 		for event in text_input.read()
 		{
@@ -116,7 +129,7 @@ pub fn handle_text_input
 			// let character : char = event.char.chars().next().expect("ReceivedCharacter event contained no characters.");
 			for character in event.char.chars()
 			{
-				if character.is_control()
+				if character.is_control() || filter.is_some_and(|filter| !(filter.0)(character))
 					{ continue; }
 				text.text.insert(cursor.position, character);
 				cursor.position += 1;
@@ -131,6 +144,7 @@ pub struct TextInput<U: Component + Default, M: UIOptionalUniqueIdentifier = ()>
 	pub label: TextLabel<U, M>,
 	pub placeholder: Option<String>,
 	pub allows_newlines: bool,
+	pub filter: Option<fn(char) -> bool>,
 	phantom: PhantomData<M>,
 }
 
@@ -145,6 +159,7 @@ impl<U: Component + Default, M: UIOptionalUniqueIdentifier> TextInput<U, M>
 				,
 			placeholder: text,
 			allows_newlines: false,
+			filter: None,
 			phantom: PhantomData,
 		}
 	}
@@ -154,6 +169,13 @@ impl<U: Component + Default, M: UIOptionalUniqueIdentifier> TextInput<U, M>
 		self.allows_newlines = allows_newlines;
 		self
 	}
+
+	/// Only lets characters accepted by `filter` be typed.
+	pub fn with_filter(mut self, filter: fn(char) -> bool) -> Self
+	{
+		self.filter = Some(filter);
+		self
+	}
 }
 
 impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for TextInput<U, M>
@@ -235,6 +257,8 @@ impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for
 		{ entity.insert(PlaceholderText { text: placeholder.clone() }); }
 		if self.allows_newlines
 		{ entity.insert(AllowsNewlines); }
+		if let Some(filter) = self.filter
+		{ entity.insert(InputFilter(filter)); }
 		entity.id()
 	}
 }
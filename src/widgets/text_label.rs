@@ -5,6 +5,8 @@ use crate::theme::PaintMode;
 use crate::theme::Theme;
 use crate::theme::ThemeApplicator;
 use crate::theme::ThemeData;
+use crate::theme::ThemePaint;
+use crate::theme::ColourTween;
 
 #[derive(Clone, Copy, Component, PartialEq, Eq, Debug)]
 pub struct AutoSizedText;
@@ -17,7 +19,7 @@ pub struct TextResizeEvent;
 pub fn resize_text
 (
 	container_query: Query<(&Children, &Node, Option<&AspectRatio>), With<AutoSizedText>>,
-	mut text_query: Query<&mut Text>,
+	mut text_query: Query<(&mut Text, Option<&RichText>)>,
 	resize_reader: EventReader<TextResizeEvent>,
 	// mut resize_writer: EventWriter<TextResizeEvent>,
 )
@@ -26,7 +28,7 @@ pub fn resize_text
 		{ return; }
 	for (children, node, aspect_ratio) in container_query.iter()
 	{
-		let mut text = text_query.get_mut(children[0]).unwrap();
+		let (mut text, rich_text) = text_query.get_mut(children[0]).unwrap();
 		let size = node.size();
 		let text_divisor = match aspect_ratio
 		{
@@ -36,9 +38,10 @@ pub fn resize_text
 		// let text_divisor = 10.1f32; //Temporary number for testing.
 		let text_size = size.y / text_divisor;
 
-		for section in text.sections.iter_mut()
+		// Rich text keeps the relative sizes of its sections.
+		for (index, section) in text.sections.iter_mut().enumerate()
 		{
-			section.style.font_size = text_size;
+			section.style.font_size = text_size * rich_text.map_or(1.0, |rich_text| rich_text.size(index));
 		}
 	}
 }
@@ -50,7 +53,9 @@ pub struct TextLabel<U, M = ()>
 	pub label: TextBundle,
 	pub custom_font: Option<Handle<Font>>,
 	/// If this is set, text size will no longer be automatically determined by the size of the container.
-	pub fixed_text_size: Option<f32>
+	pub fixed_text_size: Option<f32>,
+	/// The span of each section, for labels built from markup.
+	pub rich_text: Option<RichText>,
 }
 
 impl<U: Component + Default, M: Default + std::any::Any + Reflect> TextLabel<U, M>
@@ -81,9 +86,25 @@ impl<U: Component + Default, M: Default + std::any::Any + Reflect> TextLabel<U,
 			},
 			custom_font: None,
 			fixed_text_size: None,
+			rich_text: None,
 		}
 	}
 
+	/// Builds the label's sections from markup, see the rich_text module for the tags.
+	///
+	/// `[b]` and `[i]` pick the theme's "bold", "italic" and "bold_italic" fonts (see ThemeData::fonts), register them for those tags to show.
+	/// Without them the section keeps the regular font, and a warning is logged once.
+	pub fn from_markup(markup: &str) -> Self
+	{
+		let (sections, spans): (Vec<_>, Vec<_>) = parse_markup(markup).into_iter().unzip();
+		let mut label = Self::new("");
+		label.label.text.sections = sections.into_iter()
+			.map(|value| TextSection { value, ..Default::default() })
+			.collect();
+		label.rich_text = Some(RichText { spans, ..Default::default() });
+		label
+	}
+
 	pub fn with_font(mut self, font: Handle<Font>) -> Self
 	{
 		self.custom_font = Some(font);
@@ -151,26 +172,13 @@ impl<U: Component + Default, M: Default + std::any::Any + Reflect> ThemeApplicat
 		}
 
 		// Apply background colour.
-		match self.container.paint_mode
-		{
-			PaintMode::Background =>
-				self.label.background_color = self.theme.get_background(theme_data).into(),
-			PaintMode::BackgroundContainer =>
-				self.label.background_color = self.theme.get_background_container(theme_data).into(),
-			PaintMode::Invisible =>
-				self.label.background_color = Color::NONE.into(),
-		}
+		self.label.background_color = self.container.paint_mode.get_background(self.theme, theme_data).into();
 
 		// Apply theme's text colour.
+		let foreground = self.container.paint_mode.get_foreground(self.theme, theme_data);
 		for section in self.label.text.sections.iter_mut()
 		{
-			match self.container.paint_mode
-			{
-				PaintMode::BackgroundContainer =>
-					{ section.style.color = self.theme.get_foreground_container(theme_data).into(); }
-				_ =>
-					{ section.style.color = self.theme.get_foreground(theme_data).into(); }
-			}
+			section.style.color = foreground;
 		}
 	}
 }
@@ -216,6 +224,8 @@ impl<U: Component + Default, M: Default + 'static + Reflect> WidgetBuilder<U> fo
 		{
 			section.style.font_size = font_size;
 		}
+		if let Some(rich_text) = &self.rich_text
+			{ rich_text.apply(&mut self.label.text, theme_data); }
 
 		let container = self.container.build(ui_tree, theme_data, parent_data, commands);
 
@@ -223,10 +233,16 @@ impl<U: Component + Default, M: Default + 'static + Reflect> WidgetBuilder<U> fo
 		if self.fixed_text_size.is_none()
 			{ container.insert(AutoSizedText); }
 		let container = container.id();
+		let paint = ThemePaint::<U>::new(self.theme, self.container.paint_mode);
 		let label = commands
 			.spawn(clone_text_bundle(&self.label))
 			.insert(U::default())
+			.insert(paint.text_target(theme_data))
+			.insert(ColourTween::default())
+			.insert(paint)
 			.id();
+		if let Some(rich_text) = &self.rich_text
+			{ commands.entity(label).insert(rich_text.clone()); }
 		commands.entity(container).add_child(label);
 		container
 	}
@@ -0,0 +1,388 @@
+/*
+	Toasts is a full-screen, invisible Container with a global Z index above everything else, holding a column of toasts in one of its corners.
+	Build it once with the rest of a UI, then send ShowToast events from anywhere: show_toasts builds each toast as a TextLabel themed by its severity,
+	newest closest to the corner. A toast slides in from the side of the screen, stays for its duration, then slides back out and is despawned.
+	If more than ToastStack::max_toasts are showing, the oldest ones leave early.
+	Toasts aren't focusable and let the cursor through, so they never get in the way of the UI below.
+*/
+
+use super::*;
+use crate::theme::ThemeData;
+
+/// How long a toast takes to slide in or out, in seconds.
+const TOAST_ANIMATION: f32 = 0.25;
+/// How far off its resting place a toast slides from, in logical pixels.
+const TOAST_SLIDE_DISTANCE: f32 = 400.0;
+const TOAST_GAP: f32 = 8.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastSeverity
+{
+	#[default]
+	Info,
+	Success,
+	Warning,
+	Destructive,
+}
+
+impl ToastSeverity
+{
+	pub fn theme(self) -> Theme
+	{
+		match self
+		{
+			ToastSeverity::Info => Theme::Primary,
+			ToastSeverity::Success => Theme::Success,
+			ToastSeverity::Warning => Theme::Warning,
+			ToastSeverity::Destructive => Theme::Destructive,
+		}
+	}
+}
+
+/// Send this to show a toast in every Toasts stack.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ShowToast
+{
+	pub text: String,
+	pub severity: ToastSeverity,
+	/// How long the toast stays once it has slid in, in seconds.
+	pub duration: f32,
+}
+
+impl ShowToast
+{
+	pub const DEFAULT_DURATION: f32 = 3.0;
+
+	pub fn new(text: impl Into<String>, severity: ToastSeverity) -> Self
+	{
+		Self { text: text.into(), severity, duration: Self::DEFAULT_DURATION }
+	}
+
+	pub fn info(text: impl Into<String>) -> Self
+		{ Self::new(text, ToastSeverity::Info) }
+	pub fn success(text: impl Into<String>) -> Self
+		{ Self::new(text, ToastSeverity::Success) }
+	pub fn warning(text: impl Into<String>) -> Self
+		{ Self::new(text, ToastSeverity::Warning) }
+	pub fn destructive(text: impl Into<String>) -> Self
+		{ Self::new(text, ToastSeverity::Destructive) }
+
+	pub fn with_duration(mut self, duration: f32) -> Self
+	{
+		self.duration = duration;
+		self
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastCorner
+{
+	TopLeft,
+	#[default]
+	TopRight,
+	BottomLeft,
+	BottomRight,
+}
+
+impl ToastCorner
+{
+	pub fn is_top(self) -> bool
+	{
+		matches!(self, ToastCorner::TopLeft | ToastCorner::TopRight)
+	}
+
+	pub fn is_left(self) -> bool
+	{
+		matches!(self, ToastCorner::TopLeft | ToastCorner::BottomLeft)
+	}
+}
+
+/// Lives on the Toasts container.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ToastStack
+{
+	pub corner: ToastCorner,
+	pub max_toasts: usize,
+	/// What toasts are built with, as if they were children of the stack.
+	pub parent_data: ParentData,
+}
+
+/// Lives on a toast.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Toast
+{
+	pub stack: Entity,
+	/// How long the toast has been shown for, including sliding in.
+	pub elapsed: f32,
+	pub duration: f32,
+	/// How long the toast has been sliding out for, once it's leaving.
+	pub leaving: Option<f32>,
+}
+
+impl Toast
+{
+	/// How far the toast is from its resting place, 0.0 once it has slid in and 1.0 when it's off screen.
+	pub fn slide(&self) -> f32
+	{
+		let shown = match self.leaving
+		{
+			Some(leaving) => 1.0 - leaving / TOAST_ANIMATION,
+			None => self.elapsed / TOAST_ANIMATION,
+		}
+			.clamp(0.0, 1.0);
+		// Smoothstep
+		1.0 - shown * shown * (3.0 - 2.0 * shown)
+	}
+}
+
+pub fn show_toasts<D: Component + Default>
+(
+	mut commands: Commands,
+	mut ui_tree: ResMut<crate::UIHierarchy<D>>,
+	theme: Res<crate::theme::CurrentThemeData<D>>,
+	mut show_reader: EventReader<ShowToast>,
+	stack_query: Query<(Entity, &ToastStack), With<D>>,
+)
+{
+	for show in show_reader.read()
+	{
+		for (stack_entity, stack) in stack_query.iter()
+		{
+			let mut label = TextLabel::<D>::new(show.text.clone())
+				.with_paint_mode(PaintMode::Background)
+				.with_theme(show.severity.theme())
+				.with_text_size(BASE_TEXT_SIZE)
+				.with_padding(UiRect::axes(Val::Px(12.0), Val::Px(8.0)));
+			label.container.node_bundle.style.width = Val::Auto;
+			label.container.node_bundle.style.height = Val::Auto;
+			label.container.node_bundle.style.max_width = Val::Px(TOAST_SLIDE_DISTANCE);
+			label.container.node_bundle.focus_policy = bevy::ui::FocusPolicy::Pass;
+			let toast_entity = label.build(&mut ui_tree, &theme.0, stack.parent_data, &mut commands);
+			commands.entity(toast_entity)
+				.insert(Toast { stack: stack_entity, elapsed: 0.0, duration: show.duration, leaving: None });
+			let mut stack_commands = commands.entity(stack_entity);
+			// The newest toast is the closest to the corner.
+			if stack.corner.is_top()
+				{ stack_commands.insert_children(0, &[toast_entity]); }
+			else
+				{ stack_commands.add_child(toast_entity); }
+		}
+	}
+}
+
+pub fn animate_toasts
+(
+	mut commands: Commands,
+	time: Res<Time>,
+	stack_query: Query<(&ToastStack, Option<&Children>)>,
+	mut toast_query: Query<(Entity, &mut Toast, &mut Style)>,
+)
+{
+	// Make the oldest toasts leave once there are too many.
+	for (stack, children) in stack_query.iter()
+	{
+		let Some(children) = children
+			else { continue; };
+		let mut showing: Vec<Entity> = children.iter()
+			.copied()
+			.filter(|child| toast_query.get(*child).is_ok_and(|(_, toast, _)| toast.leaving.is_none()))
+			.collect();
+		if stack.corner.is_top()
+			{ showing.reverse(); }
+		let excess = showing.len().saturating_sub(stack.max_toasts);
+		for entity in showing.into_iter().take(excess)
+		{
+			if let Ok((_, mut toast, _)) = toast_query.get_mut(entity)
+				{ toast.leaving = Some(0.0); }
+		}
+	}
+
+	for (entity, mut toast, mut style) in toast_query.iter_mut()
+	{
+		let Ok((stack, _)) = stack_query.get(toast.stack)
+			else { continue; };
+		match toast.leaving
+		{
+			Some(leaving) if leaving >= TOAST_ANIMATION =>
+			{
+				commands.entity(entity).despawn_recursive();
+				continue;
+			},
+			Some(leaving) => toast.leaving = Some(leaving + time.delta_seconds()),
+			None =>
+			{
+				toast.elapsed += time.delta_seconds();
+				if toast.elapsed >= toast.duration + TOAST_ANIMATION
+					{ toast.leaving = Some(0.0); }
+			},
+		}
+		let side = if stack.corner.is_left() { -1.0 } else { 1.0 };
+		style.left = Val::Px(side * toast.slide() * TOAST_SLIDE_DISTANCE);
+	}
+}
+
+/// A corner stack for toasts, see ShowToast.
+/// Build one per UI, at the top level.
+pub struct Toasts<U, M = ()>
+	where U: Component + Default, M: UIOptionalUniqueIdentifier
+{
+	pub container: Container<U, M>,
+	pub corner: ToastCorner,
+	pub max_toasts: usize,
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Toasts<U, M>
+{
+	pub fn new() -> Self
+	{
+		let mut container = Container::new()
+			.with_direction(FlexDirection::Column)
+			.with_paint_mode(PaintMode::Invisible)
+			.with_padding(UiRect::all(Val::Px(16.0)));
+		container.node_bundle.style.position_type = PositionType::Absolute;
+		container.node_bundle.style.left = Val::Px(0.0);
+		container.node_bundle.style.top = Val::Px(0.0);
+		container.node_bundle.style.row_gap = Val::Px(TOAST_GAP);
+		container.node_bundle.style.overflow = Overflow::clip();
+		container.node_bundle.focus_policy = bevy::ui::FocusPolicy::Pass;
+		container.node_bundle.z_index = ZIndex::Global(TOAST_Z_INDEX);
+		Self
+		{
+			container,
+			corner: ToastCorner::default(),
+			max_toasts: 4,
+		}
+	}
+
+	/// Sets the corner toasts are shown in, the top right one by default.
+	pub fn with_corner(mut self, corner: ToastCorner) -> Self
+	{
+		self.corner = corner;
+		self
+	}
+
+	/// Sets how many toasts can be shown at once, 4 by default.
+	pub fn with_max_toasts(mut self, max_toasts: usize) -> Self
+	{
+		self.max_toasts = max_toasts.max(1);
+		self
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Widget for Toasts<U, M>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.container = self.container.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.container = self.container.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.container = self.container.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.container = self.container.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.container = self.container.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.container = self.container.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.container = self.container.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.container = self.container.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.container = self.container.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.container = self.container.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.container = self.container.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.container = self.container.with_theme(theme); self }
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> WidgetBuilder<U> for Toasts<U, M>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let style = &mut self.container.node_bundle.style;
+		style.align_items = if self.corner.is_left() { AlignItems::FlexStart } else { AlignItems::FlexEnd };
+		style.justify_content = if self.corner.is_top() { JustifyContent::FlexStart } else { JustifyContent::FlexEnd };
+		let stack_entity = self.container.build(ui_tree, theme_data, parent_data, commands);
+		let toast_parent_data = parent_data.from_current(self.container.theme);
+		commands.entity(stack_entity)
+			.insert(ToastStack { corner: self.corner, max_toasts: self.max_toasts, parent_data: toast_parent_data });
+		stack_entity
+	}
+}
+
+impl<U: Component + Default, M: UIOptionalUniqueIdentifier> Into<Box<dyn WidgetBuilder<U>>> for Toasts<U, M>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use bevy_alt_ui_navigation_lite::prelude::*;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, focused};
+
+	fn build_ui(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		TextButton::<TestUI>::new("Save")
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+		Toasts::<TestUI>::new()
+			.with_max_toasts(2)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn toasts_stack_without_stealing_focus_and_leave()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_ui);
+		app.update();
+		let button = app.world_mut().query_filtered::<Entity, With<Focusable>>().single(app.world());
+		app.world_mut().send_event(NavRequest::FocusOn(button));
+		app.update();
+
+		app.world_mut().send_event(ShowToast::info("Settings saved"));
+		app.update();
+		app.world_mut().send_event(ShowToast::success("Achievement unlocked").with_duration(10.0));
+		app.update();
+		assert_eq!(focused(&mut app), Some(button));
+		let showing = |app: &mut App| app.world_mut().query::<&Toast>().iter(app.world())
+			.filter(|toast| toast.leaving.is_none())
+			.count();
+		assert_eq!(showing(&mut app), 2);
+
+		// A third toast pushes the oldest one out.
+		app.world_mut().send_event(ShowToast::warning("Low battery"));
+		app.update();
+		app.update();
+		assert_eq!(showing(&mut app), 2);
+		let texts = |app: &mut App| app.world_mut().query::<(&Toast, &Children)>().iter(app.world())
+			.filter(|(toast, _)| toast.leaving.is_none())
+			.map(|(_, children)| children[0])
+			.collect::<Vec<_>>()
+			.into_iter()
+			.map(|text| app.world().get::<Text>(text).unwrap().sections[0].value.clone())
+			.collect::<Vec<_>>();
+		let showing_texts = texts(&mut app);
+		assert!(!showing_texts.contains(&"Settings saved".to_string()));
+
+		// The short toast times out and is despawned, the long one stays.
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(ShowToast::DEFAULT_DURATION + TOAST_ANIMATION));
+		app.update();
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(TOAST_ANIMATION));
+		app.update();
+		app.update();
+		assert_eq!(texts(&mut app), vec!["Achievement unlocked".to_string()]);
+		let toasts = app.world_mut().query::<&Toast>().iter(app.world()).count();
+		assert_eq!(toasts, 1);
+		assert_eq!(focused(&mut app), Some(button));
+	}
+}
@@ -0,0 +1,264 @@
+/*
+	with_tooltip wraps any widget builder, building the widget as usual plus a hidden top-level TextLabel (the popup) with a global Z index.
+	The popup is painted with the base theme's foreground as its background, so it stands out from any surface.
+	A widget's tooltip is shown once it has been hovered or focused for TooltipSettings::delay, so gamepad players see them too, and is hidden as soon as it isn't.
+	Popups are despawned along with their widget.
+*/
+
+use bevy::ui::RelativeCursorPosition;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use crate::theme::ThemeData;
+
+/// The gap between a widget and its tooltip, in logical pixels.
+const TOOLTIP_GAP: f32 = 6.0;
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TooltipSettings
+{
+	/// How long a widget must be hovered or focused before its tooltip appears, in seconds.
+	pub delay: f32,
+}
+
+impl Default for TooltipSettings
+{
+	fn default() -> Self
+	{
+		Self { delay: 0.5 }
+	}
+}
+
+/// Lives on a widget with a tooltip.
+#[derive(Component)]
+pub struct Tooltip
+{
+	pub popup: Entity,
+	/// How long the widget has been hovered or focused for.
+	pub timer: f32,
+	pub shown: bool,
+}
+
+/// Lives on a tooltip's popup.
+#[derive(Component)]
+pub struct TooltipPopup
+{
+	pub target: Entity,
+}
+
+/// Places a tooltip of `size` centred below `target` (or above it, if there is no room below), inside the window.
+pub fn tooltip_position(target: Rect, size: Vec2, window: Vec2) -> Vec2
+{
+	let x = (target.center().x - size.x / 2.0).clamp(0.0, (window.x - size.x).max(0.0));
+	let below = target.max.y + TOOLTIP_GAP;
+	let y = if below + size.y <= window.y
+		{ below }
+	else
+		{ (target.min.y - TOOLTIP_GAP - size.y).max(0.0) };
+	Vec2::new(x, y)
+}
+
+pub fn update_tooltips
+(
+	mut commands: Commands,
+	time: Res<Time>,
+	settings: Res<TooltipSettings>,
+	mut tooltip_query: Query<(&mut Tooltip, Option<&RelativeCursorPosition>, Option<&Focusable>)>,
+	popup_query: Query<(Entity, &TooltipPopup)>,
+	mut style_query: Query<&mut Style>,
+)
+{
+	for (mut tooltip, cursor_position, focusable) in tooltip_query.iter_mut()
+	{
+		let hovered = cursor_position.is_some_and(|cursor_position| cursor_position.mouse_over());
+		let focused = focusable.is_some_and(|focusable| focusable.state() == FocusState::Focused);
+		let show = if hovered || focused
+		{
+			tooltip.timer += time.delta_seconds();
+			tooltip.timer >= settings.delay
+		}
+		else
+		{
+			tooltip.timer = 0.0;
+			false
+		};
+		if show == tooltip.shown
+			{ continue; }
+		tooltip.shown = show;
+		if let Ok(mut style) = style_query.get_mut(tooltip.popup)
+			{ style.display = if show { Display::Flex } else { Display::None }; }
+	}
+
+	for (popup, popup_data) in popup_query.iter()
+	{
+		if !tooltip_query.contains(popup_data.target)
+			{ commands.entity(popup).despawn_recursive(); }
+	}
+}
+
+pub fn position_tooltips
+(
+	tooltip_query: Query<(&Tooltip, &Node, &GlobalTransform)>,
+	mut popup_query: Query<(&Node, &mut Style), With<TooltipPopup>>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+)
+{
+	let window_size = window_query.get_single().map_or(Vec2::INFINITY, |window| window.size());
+	for (tooltip, node, transform) in tooltip_query.iter()
+	{
+		if !tooltip.shown
+			{ continue; }
+		let Ok((popup_node, mut style)) = popup_query.get_mut(tooltip.popup)
+			else { continue; };
+		let position = tooltip_position(node.logical_rect(transform), popup_node.size(), window_size);
+		style.left = Val::Px(position.x);
+		style.top = Val::Px(position.y);
+	}
+}
+
+/// A widget with a tooltip, see WithTooltip::with_tooltip.
+pub struct Tooltipped<U, W>
+	where U: Component + Default, W: WidgetBuilder<U>
+{
+	pub widget: W,
+	pub tooltip: TextLabel<U, ()>,
+}
+
+pub trait WithTooltip<U>
+	where U: Component + Default, Self: WidgetBuilder<U> + Sized
+{
+	/// Shows `text` next to the widget when it's hovered or focused.
+	fn with_tooltip(self, text: impl Into<String>) -> Tooltipped<U, Self>;
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U>> WithTooltip<U> for W
+{
+	fn with_tooltip(self, text: impl Into<String>) -> Tooltipped<U, Self>
+	{
+		let mut tooltip = TextLabel::new(text)
+			.with_paint_mode(PaintMode::Foreground)
+			.with_theme(Theme::Base)
+			.with_text_size(BASE_TEXT_SIZE)
+			.with_padding(UiRect::axes(Val::Px(8.0), Val::Px(4.0)));
+		tooltip.container.node_bundle.style.position_type = PositionType::Absolute;
+		tooltip.container.node_bundle.style.width = Val::Auto;
+		tooltip.container.node_bundle.style.height = Val::Auto;
+		tooltip.container.node_bundle.style.display = Display::None;
+		tooltip.container.node_bundle.z_index = ZIndex::Global(OVERLAY_Z_INDEX + 1);
+		Tooltipped { widget: self, tooltip }
+	}
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U> + Widget> Widget for Tooltipped<U, W>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.widget = self.widget.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.widget = self.widget.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.widget = self.widget.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.widget = self.widget.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.widget = self.widget.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.widget = self.widget.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.widget = self.widget.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.widget = self.widget.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.widget = self.widget.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.widget = self.widget.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.widget = self.widget.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.widget = self.widget.with_theme(theme); self }
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U>> WidgetBuilder<U> for Tooltipped<U, W>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let widget_entity = self.widget.build(ui_tree, theme_data, parent_data, commands);
+		let popup_entity = self.tooltip.build(ui_tree, theme_data, parent_data, commands);
+		commands.entity(popup_entity)
+			.insert(TooltipPopup { target: widget_entity });
+		commands.entity(widget_entity)
+			.insert(RelativeCursorPosition::default())
+			.insert(Tooltip { popup: popup_entity, timer: 0.0, shown: false });
+		widget_entity
+	}
+}
+
+impl<U: Component + Default, W: WidgetBuilder<U> + 'static> Into<Box<dyn WidgetBuilder<U>>> for Tooltipped<U, W>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::Duration;
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::TestUI;
+
+	fn build_buttons(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		Row::<TestUI>::new()
+			.push(TextButton::<TestUI>::new("Vsync").with_tooltip("Limits the frame rate to the display's refresh rate."))
+			.push(TextButton::<TestUI>::new("Back"))
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	#[test]
+	fn tooltips_stay_inside_the_window()
+	{
+		let window = Vec2::new(800.0, 600.0);
+		let size = Vec2::new(200.0, 30.0);
+		// Centred below the widget.
+		let position = tooltip_position(Rect::new(300.0, 100.0, 500.0, 140.0), size, window);
+		assert_eq!(position, Vec2::new(300.0, 140.0 + TOOLTIP_GAP));
+		// Pushed in from the right edge, and above a widget at the bottom.
+		let position = tooltip_position(Rect::new(700.0, 560.0, 800.0, 600.0), size, window);
+		assert_eq!(position, Vec2::new(600.0, 560.0 - TOOLTIP_GAP - size.y));
+	}
+
+	#[test]
+	fn focused_widgets_show_their_tooltip_after_the_delay()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_buttons);
+		app.update();
+		let (vsync, popup) =
+		{
+			let mut query = app.world_mut().query::<(Entity, &Tooltip)>();
+			let (entity, tooltip) = query.single(app.world());
+			(entity, tooltip.popup)
+		};
+		let back =
+		{
+			let mut query = app.world_mut().query_filtered::<Entity, (With<Focusable>, Without<Tooltip>)>();
+			query.single(app.world())
+		};
+		let popup_shown = |app: &App| app.world().get::<Style>(popup).unwrap().display == Display::Flex;
+
+		app.world_mut().send_event(NavRequest::FocusOn(vsync));
+		app.update();
+		assert!(!popup_shown(&app));
+
+		let delay = TooltipSettings::default().delay;
+		app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(delay));
+		app.update();
+		assert!(popup_shown(&app));
+
+		app.world_mut().send_event(NavRequest::FocusOn(back));
+		app.update();
+		assert!(!popup_shown(&app));
+	}
+}
@@ -0,0 +1,726 @@
+/*
+	A tree view is a ScrollView holding one node per root value. A node is a Container with the node's row (a BaseButton with an expander,
+	an optional icon and a TextLabel, indented by its depth) above a Container for its children.
+	Values are type-erased once the tree is built: TreeViewSource describes a value and lists its children, and TreeNode::value gets it back.
+	Children are only listed and built the first time their node is expanded (by build_tree_children), collapsing just hides them.
+	Right expands the focused node or moves into its children, Left collapses it or moves to its parent. Clicking the expander toggles a node,
+	clicking the rest of the row (or Enter) selects it, holding Ctrl or Shift extends the selection of trees that allow multiple selected nodes.
+*/
+
+use std::any::Any;
+use std::sync::Arc;
+
+use bevy::hierarchy::HierarchyQueryExt;
+use bevy::input::gamepad::Gamepads;
+use bevy::ui::RelativeCursorPosition;
+use bevy_alt_ui_navigation_lite::prelude::*;
+
+use super::*;
+use super::base_button::BaseButton;
+use super::slider::{axis_input, SliderOrientation};
+use crate::theme::{ThemeData, ThemePaint, CurrentThemeData};
+
+/// A value of a built tree view, see TreeNode::value.
+pub type TreeValue = Arc<dyn Any + Send + Sync>;
+
+/// What a node's row shows.
+#[derive(Clone)]
+pub struct TreeItem
+{
+	pub label: String,
+	pub icon: Option<UiImage>,
+	pub has_children: bool,
+}
+
+/// Lives on a tree view.
+#[derive(Component, Clone)]
+pub struct TreeViewSource
+{
+	pub describe: Arc<dyn Fn(&TreeValue) -> TreeItem + Send + Sync>,
+	/// Called when a node is expanded for the first time.
+	pub children: Arc<dyn Fn(&TreeValue) -> Vec<TreeValue> + Send + Sync>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreeSelectionMode
+{
+	#[default]
+	Single,
+	/// Ctrl toggles nodes in and out of the selection, Shift selects every visible node from the last one clicked.
+	Multiple,
+}
+
+/// Lives on a tree view.
+#[derive(Component, Debug)]
+pub struct TreeViewState
+{
+	/// The rows of the root nodes.
+	pub roots: Vec<Entity>,
+	pub selection_mode: TreeSelectionMode,
+	/// How far each level is indented, in logical pixels.
+	pub indent: f32,
+	pub row_height: f32,
+	/// Children are built at runtime, with the theme the tree view was built with.
+	pub parent_data: ParentData,
+}
+
+/// The selected rows of a tree view, lives on the tree view.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TreeSelection
+{
+	pub selected: Vec<Entity>,
+	/// Where Shift selections start from.
+	pub anchor: Option<Entity>,
+}
+
+/// Lives on a node's row.
+#[derive(Component)]
+pub struct TreeNode
+{
+	pub tree: Entity,
+	pub value: TreeValue,
+	pub depth: usize,
+	/// The row of the parent node.
+	pub parent: Option<Entity>,
+	pub has_children: bool,
+	pub expanded: bool,
+	/// The rows of the child nodes, None until the node is first expanded.
+	pub children: Option<Vec<Entity>>,
+	pub expander: Entity,
+	pub children_container: Entity,
+}
+
+impl TreeNode
+{
+	/// The value this node was built from.
+	pub fn value<T: 'static>(&self) -> Option<&T>
+	{
+		self.value.downcast_ref()
+	}
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TreeSelectionChanged
+{
+	pub tree: Entity,
+	pub selected: Vec<Entity>,
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeNodeToggled
+{
+	pub tree: Entity,
+	pub node: Entity,
+	pub expanded: bool,
+}
+
+/// The rows that are shown (every root, and the children of expanded nodes), from top to bottom.
+pub fn visible_tree_nodes(roots: &[Entity], node_query: &Query<(Entity, Ref<Interaction>, &mut TreeNode)>) -> Vec<Entity>
+{
+	let mut visible = Vec::new();
+	let mut stack: Vec<Entity> = roots.iter().rev().copied().collect();
+	while let Some(entity) = stack.pop()
+	{
+		visible.push(entity);
+		let Ok((_, _, node)) = node_query.get(entity)
+			else { continue; };
+		if let (true, Some(children)) = (node.expanded, &node.children)
+			{ stack.extend(children.iter().rev()); }
+	}
+	visible
+}
+
+fn expander_text(node: &TreeNode) -> &'static str
+{
+	match (node.has_children, node.expanded)
+	{
+		(false, _) => "",
+		(true, false) => ">",
+		(true, true) => "v",
+	}
+}
+
+pub fn press_tree_nodes
+(
+	mut node_query: Query<(Entity, Ref<Interaction>, &mut TreeNode)>,
+	expander_query: Query<&RelativeCursorPosition>,
+	mut tree_query: Query<(&TreeViewState, &mut TreeSelection)>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	mut selection_writer: EventWriter<TreeSelectionChanged>,
+	mut toggle_writer: EventWriter<TreeNodeToggled>,
+)
+{
+	let pressed: Vec<(Entity, bool)> = node_query.iter()
+		.filter(|(_, interaction, _)| interaction.is_changed() && **interaction == Interaction::Pressed)
+		.map(|(entity, _, node)| (entity, node.has_children && expander_query.get(node.expander).is_ok_and(|cursor| cursor.mouse_over())))
+		.collect();
+	let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+	let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+	for (entity, on_expander) in pressed
+	{
+		if on_expander
+		{
+			let Ok((_, _, mut node)) = node_query.get_mut(entity)
+				else { continue; };
+			node.expanded = !node.expanded;
+			toggle_writer.send(TreeNodeToggled { tree: node.tree, node: entity, expanded: node.expanded });
+			continue;
+		}
+		let Ok((_, _, node)) = node_query.get(entity)
+			else { continue; };
+		let tree = node.tree;
+		let Ok((state, mut selection)) = tree_query.get_mut(tree)
+			else { continue; };
+		let mut selected = selection.selected.clone();
+		let mut anchor = Some(entity);
+		match state.selection_mode
+		{
+			TreeSelectionMode::Multiple if ctrl =>
+			{
+				match selected.iter().position(|selected| *selected == entity)
+				{
+					Some(index) => { selected.remove(index); },
+					None => selected.push(entity),
+				}
+			},
+			TreeSelectionMode::Multiple if shift && selection.anchor.is_some() =>
+			{
+				let visible = visible_tree_nodes(&state.roots, &node_query);
+				let start = visible.iter().position(|node| Some(*node) == selection.anchor);
+				let end = visible.iter().position(|node| *node == entity);
+				if let (Some(start), Some(end)) = (start, end)
+					{ selected = visible[start.min(end)..=start.max(end)].to_vec(); }
+				anchor = selection.anchor;
+			},
+			_ => selected = vec![entity],
+		}
+		if selection.anchor != anchor
+			{ selection.anchor = anchor; }
+		if selection.selected != selected
+		{
+			selection.selected = selected.clone();
+			selection_writer.send(TreeSelectionChanged { tree, selected });
+		}
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn navigate_trees
+(
+	mut node_query: Query<&mut TreeNode>,
+	focus_query: Query<(Entity, &Focusable)>,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	gamepads: Res<Gamepads>,
+	mut nav_events: EventReader<NavEvent>,
+	mut nav_writer: EventWriter<NavRequest>,
+	mut toggle_writer: EventWriter<TreeNodeToggled>,
+)
+{
+	let horizontal = axis_input(SliderOrientation::Horizontal, &keyboard_input, &gamepad_buttons, &gamepads, None);
+	if horizontal == 0.0
+	{
+		nav_events.clear();
+		return;
+	}
+
+	// The navigation crate reads the same input, so it may have just moved focus out of the tree.
+	let (from, to) = match nav_events.read().find_map(|event| match event
+	{
+		NavEvent::FocusChanged { to, from } => Some((*from.first(), Some(*to.first()))),
+		_ => None,
+	})
+	{
+		Some(change) => change,
+		None => match focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused)
+		{
+			Some((focused, _)) => (focused, None),
+			None => return,
+		},
+	};
+	let Ok(mut node) = node_query.get_mut(from)
+		else { return; };
+	let mut target = from;
+	if horizontal > 0.0
+	{
+		if node.has_children && !node.expanded
+		{
+			node.expanded = true;
+			toggle_writer.send(TreeNodeToggled { tree: node.tree, node: from, expanded: true });
+		}
+		else if let Some(first_child) = node.children.as_ref().filter(|_| node.expanded).and_then(|children| children.first())
+			{ target = *first_child; }
+	}
+	else if node.expanded
+	{
+		node.expanded = false;
+		toggle_writer.send(TreeNodeToggled { tree: node.tree, node: from, expanded: false });
+	}
+	else if let Some(parent) = node.parent
+		{ target = parent; }
+	if to != Some(target)
+		{ nav_writer.send(NavRequest::FocusOn(target)); }
+}
+
+/// Shows or hides the children of expanded and collapsed nodes.
+pub fn apply_tree_expansion
+(
+	node_query: Query<(Entity, Ref<TreeNode>)>,
+	children_query: Query<&Children>,
+	mut style_query: Query<&mut Style>,
+	mut text_query: Query<&mut Text>,
+	focus_query: Query<(Entity, &Focusable)>,
+	parent_query: Query<&Parent>,
+	mut nav_writer: EventWriter<NavRequest>,
+)
+{
+	let focused = focus_query.iter().find(|(_, focusable)| focusable.state() == FocusState::Focused).map(|(entity, _)| entity);
+	for (entity, node) in node_query.iter()
+	{
+		if !node.is_changed()
+			{ continue; }
+		if let Ok(mut style) = style_query.get_mut(node.children_container)
+		{
+			let display = if node.expanded { Display::Flex } else { Display::None };
+			if style.display != display
+				{ style.display = display; }
+		}
+		// The hierarchy is: TextLabel Container -> TextBundle.
+		if let Some(mut text) = children_query.get(node.expander).ok().and_then(|children| text_query.get_mut(children[0]).ok())
+		{
+			if text.sections[0].value != expander_text(&node)
+				{ text.sections[0].value = expander_text(&node).to_string(); }
+		}
+		// Collapsing a node hides the focus, so focus the node instead.
+		let focus_hidden = focused.is_some_and(|focused| parent_query.iter_ancestors(focused).any(|ancestor| ancestor == node.children_container));
+		if !node.expanded && focus_hidden
+			{ nav_writer.send(NavRequest::FocusOn(entity)); }
+	}
+}
+
+/// Builds the children of nodes expanded for the first time.
+pub fn build_tree_children<U: Component + Default>
+(
+	mut commands: Commands,
+	mut node_query: Query<(Entity, &mut TreeNode), With<U>>,
+	tree_query: Query<(&TreeViewSource, &TreeViewState)>,
+	mut ui_tree: ResMut<crate::UIHierarchy<U>>,
+	theme_data: Res<CurrentThemeData<U>>,
+)
+{
+	for (entity, mut node) in node_query.iter_mut()
+	{
+		if !node.expanded || node.children.is_some()
+			{ continue; }
+		let Ok((source, state)) = tree_query.get(node.tree)
+			else { continue; };
+		let mut rows = Vec::new();
+		for value in (source.children)(&node.value)
+		{
+			let (container, row, mut child) = build_tree_node::<U>(value, node.depth + 1, source, state, &mut ui_tree, &theme_data.0, &mut commands);
+			child.tree = node.tree;
+			child.parent = Some(entity);
+			commands.entity(row).insert(child);
+			commands.entity(node.children_container).add_child(container);
+			rows.push(row);
+		}
+		node.children = Some(rows);
+	}
+}
+
+/// Paints selected rows like an active tab.
+pub fn apply_tree_selection<U: Component>
+(
+	tree_query: Query<(Entity, &TreeSelection), Changed<TreeSelection>>,
+	mut node_query: Query<(Entity, &TreeNode, &mut ThemePaint<U>)>,
+)
+{
+	for (tree, selection) in tree_query.iter()
+	{
+		for (entity, node, mut paint) in node_query.iter_mut()
+		{
+			if node.tree != tree
+				{ continue; }
+			let paint_mode = if selection.selected.contains(&entity) { PaintMode::Background } else { PaintMode::BackgroundContainer };
+			if paint.paint_mode != paint_mode
+				{ paint.paint_mode = paint_mode; }
+		}
+	}
+}
+
+fn tree_label<U: Component + Default>(text: impl Into<String>) -> TextLabel<U, ()>
+{
+	let mut label = TextLabel::new(text)
+		.with_paint_mode(PaintMode::Invisible)
+		.with_text_size(BASE_TEXT_SIZE);
+	label.container.node_bundle.style.width = Val::Auto;
+	label.container.node_bundle.style.height = Val::Auto;
+	label.label.text.justify = JustifyText::Left;
+	label
+}
+
+/// Builds a node, returning its container, its row and its TreeNode (without its tree or parent) for the caller to insert.
+fn build_tree_node<U: Component + Default>
+(
+	value: TreeValue,
+	depth: usize,
+	source: &TreeViewSource,
+	state: &TreeViewState,
+	ui_tree: &mut crate::UIHierarchy<U>,
+	theme_data: &ThemeData,
+	commands: &mut Commands,
+) -> (Entity, Entity, TreeNode)
+{
+	let item = (source.describe)(&value);
+	let mut row = BaseButton::<U, ()>::new()
+		.with_auto_style(true)
+		.with_paint_mode(PaintMode::BackgroundContainer)
+		.with_direction(FlexDirection::Row)
+		.with_padding(UiRect::left(Val::Px(4.0 + depth as f32 * state.indent)));
+	let style = &mut row.button_bundle.style;
+	style.height = Val::Px(state.row_height);
+	style.flex_shrink = 0.0;
+	style.justify_content = JustifyContent::FlexStart;
+	style.column_gap = Val::Px(4.0);
+	let row_parent_data = state.parent_data.from_current(row.theme);
+
+	let mut expander = tree_label::<U>(if item.has_children { ">" } else { "" });
+	expander.container.node_bundle.style.width = Val::Px(16.0);
+	expander.container.node_bundle.style.flex_shrink = 0.0;
+	let expander_entity = expander.build(ui_tree, theme_data, row_parent_data, commands);
+	commands.entity(expander_entity).insert(RelativeCursorPosition::default());
+	row = row.push(expander_entity);
+	if let Some(icon) = item.icon
+	{
+		let icon_entity = commands.spawn(ImageBundle
+		{
+			style: Style
+			{
+				height: Val::Percent(70.0),
+				aspect_ratio: Some(1.0),
+				flex_shrink: 0.0,
+				..Default::default()
+			},
+			image: icon,
+			..Default::default()
+		})
+			.insert(U::default())
+			.id();
+		row = row.push(icon_entity);
+	}
+	row = row.push(tree_label::<U>(item.label));
+	let row_entity = row.build(ui_tree, theme_data, state.parent_data, commands);
+
+	let mut children_container = Container::<U, ()>::new()
+		.with_direction(FlexDirection::Column)
+		.with_paint_mode(PaintMode::Invisible);
+	children_container.node_bundle.style.height = Val::Auto;
+	children_container.node_bundle.style.display = Display::None;
+	let children_entity = children_container.build(ui_tree, theme_data, state.parent_data, commands);
+
+	let mut container = Container::<U, ()>::new()
+		.with_direction(FlexDirection::Column)
+		.with_paint_mode(PaintMode::Invisible)
+		.push(row_entity)
+		.push(children_entity);
+	container.node_bundle.style.height = Val::Auto;
+	container.node_bundle.style.justify_content = JustifyContent::FlexStart;
+	let container_entity = container.build(ui_tree, theme_data, state.parent_data, commands);
+
+	let node = TreeNode
+	{
+		tree: Entity::PLACEHOLDER,
+		value,
+		depth,
+		parent: None,
+		has_children: item.has_children,
+		expanded: false,
+		children: None,
+		expander: expander_entity,
+		children_container: children_entity,
+	};
+	(container_entity, row_entity, node)
+}
+
+pub struct TreeView<U, T>
+	where U: Component + Default, T: Clone + Send + Sync + 'static
+{
+	pub scroll_view: ScrollView<U, ()>,
+	pub roots: Vec<T>,
+	pub children: Arc<dyn Fn(&T) -> Vec<T> + Send + Sync>,
+	pub label: Arc<dyn Fn(&T) -> String + Send + Sync>,
+	pub icon: Option<Arc<dyn Fn(&T) -> Option<UiImage> + Send + Sync>>,
+	/// Without this, a node's children are listed to know if it has any.
+	pub has_children: Option<Arc<dyn Fn(&T) -> bool + Send + Sync>>,
+	pub selection_mode: TreeSelectionMode,
+	pub indent: f32,
+	pub row_height: f32,
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> TreeView<U, T>
+{
+	/// `children` lists the children of a value when its node is first expanded, `label` is the text of its row.
+	pub fn new(roots: impl IntoIterator<Item = T>, children: impl Fn(&T) -> Vec<T> + Send + Sync + 'static, label: impl Fn(&T) -> String + Send + Sync + 'static) -> Self
+	{
+		Self
+		{
+			scroll_view: ScrollView::new()
+				.with_paint_mode(PaintMode::BackgroundContainer),
+			roots: roots.into_iter().collect(),
+			children: Arc::new(children),
+			label: Arc::new(label),
+			icon: None,
+			has_children: None,
+			selection_mode: TreeSelectionMode::default(),
+			indent: 16.0,
+			row_height: 28.0,
+		}
+	}
+
+	pub fn with_label(mut self, label: impl Fn(&T) -> String + Send + Sync + 'static) -> Self
+		{ self.label = Arc::new(label); self }
+
+	pub fn with_icon(mut self, icon: impl Fn(&T) -> Option<UiImage> + Send + Sync + 'static) -> Self
+		{ self.icon = Some(Arc::new(icon)); self }
+
+	/// Tells if a value has children without listing them, so nodes are only listed when expanded.
+	pub fn with_has_children(mut self, has_children: impl Fn(&T) -> bool + Send + Sync + 'static) -> Self
+		{ self.has_children = Some(Arc::new(has_children)); self }
+
+	pub fn with_selection_mode(mut self, selection_mode: TreeSelectionMode) -> Self
+		{ self.selection_mode = selection_mode; self }
+
+	/// Sets how far each level is indented, in logical pixels.
+	pub fn with_indent(mut self, indent: f32) -> Self
+		{ self.indent = indent; self }
+
+	pub fn with_row_height(mut self, row_height: f32) -> Self
+		{ self.row_height = row_height; self }
+
+	fn source(&self) -> TreeViewSource
+	{
+		let (children, label, icon, has_children) = (self.children.clone(), self.label.clone(), self.icon.clone(), self.has_children.clone());
+		let list_children = children.clone();
+		TreeViewSource
+		{
+			describe: Arc::new(move |value|
+			{
+				let value = value.downcast_ref::<T>().expect("Tree values are all of the tree view's type.");
+				TreeItem
+				{
+					label: label(value),
+					icon: icon.as_ref().and_then(|icon| icon(value)),
+					has_children: has_children.as_ref().map_or_else(|| !children(value).is_empty(), |has_children| has_children(value)),
+				}
+			}),
+			children: Arc::new(move |value|
+			{
+				let value = value.downcast_ref::<T>().expect("Tree values are all of the tree view's type.");
+				list_children(value).into_iter().map(|child| Arc::new(child) as TreeValue).collect()
+			}),
+		}
+	}
+}
+
+impl<U: Component + Default> TreeView<U, indextree::NodeId>
+{
+	/// Shows the named elements of a UI for debugging, labelled with their type names unless with_label is used.
+	pub fn from_ui_hierarchy<H: Component>(hierarchy: &crate::UIHierarchy<H>) -> Self
+	{
+		let arena = hierarchy.0.clone();
+		let roots: Vec<indextree::NodeId> =
+		{
+			let arena = arena.lock().unwrap();
+			arena.iter()
+				.filter(|node| !node.is_removed() && node.parent().is_none())
+				.filter_map(|node| arena.get_node_id(node))
+				.collect()
+		};
+		let label_arena = arena.clone();
+		Self::new
+		(
+			roots,
+			move |node| node.children(&arena.lock().unwrap()).collect(),
+			move |node| label_arena.lock().unwrap().get(*node).map_or_else(String::new, |node| node.get().type_name.to_string()),
+		)
+	}
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> Widget for TreeView<U, T>
+{
+	fn with_paint_mode(mut self, paint_mode: PaintMode) -> Self
+		{ self.scroll_view = self.scroll_view.with_paint_mode(paint_mode); self }
+	fn with_colour(mut self, background: Color, foreground: Color) -> Self
+		{ self.scroll_view = self.scroll_view.with_colour(background, foreground); self }
+	fn with_border(mut self, border: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_border(border); self }
+	fn with_aspect_ratio(mut self, aspect_ratio: f32) -> Self
+		{ self.scroll_view = self.scroll_view.with_aspect_ratio(aspect_ratio); self }
+	fn with_direction(mut self, direction: FlexDirection) -> Self
+		{ self.scroll_view = self.scroll_view.with_direction(direction); self }
+	fn with_wrap(mut self, wrap: FlexWrap) -> Self
+		{ self.scroll_view = self.scroll_view.with_wrap(wrap); self }
+	fn with_align_self(mut self, align_self: AlignSelf) -> Self
+		{ self.scroll_view = self.scroll_view.with_align_self(align_self); self }
+	fn with_align_content(mut self, align_content: AlignContent) -> Self
+		{ self.scroll_view = self.scroll_view.with_align_content(align_content); self }
+	fn with_padding(mut self, padding: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_padding(padding); self }
+	fn with_margin(mut self, margin: UiRect) -> Self
+		{ self.scroll_view = self.scroll_view.with_margin(margin); self }
+	fn with_fill_portion(mut self, fill_portion: f32) -> Self
+		{ self.scroll_view = self.scroll_view.with_fill_portion(fill_portion); self }
+	fn with_theme(mut self, theme: Theme) -> Self
+		{ self.scroll_view = self.scroll_view.with_theme(theme); self }
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> WidgetBuilder<U> for TreeView<U, T>
+{
+	fn build(&mut self, ui_tree: &mut crate::UIHierarchy<U>, theme_data: &ThemeData, parent_data: ParentData, commands: &mut Commands) -> Entity
+	{
+		let source = self.source();
+		let mut state = TreeViewState
+		{
+			roots: Vec::new(),
+			selection_mode: self.selection_mode,
+			indent: self.indent,
+			row_height: self.row_height,
+			parent_data: parent_data.from_current(self.scroll_view.container.theme),
+		};
+		let mut roots = Vec::new();
+		for value in self.roots.iter()
+		{
+			let (container, row, node) = build_tree_node::<U>(Arc::new(value.clone()), 0, &source, &state, ui_tree, theme_data, commands);
+			self.scroll_view.content.children.push(container.into());
+			roots.push((row, node));
+		}
+		let tree_entity = self.scroll_view.build(ui_tree, theme_data, parent_data, commands);
+		self.scroll_view.content.children.clear();
+
+		for (row, mut node) in roots
+		{
+			node.tree = tree_entity;
+			commands.entity(row).insert(node);
+			state.roots.push(row);
+		}
+		commands.entity(tree_entity)
+			.insert(source)
+			.insert(state)
+			.insert(TreeSelection::default());
+		tree_entity
+	}
+}
+
+impl<U: Component + Default, T: Clone + Send + Sync + 'static> Into<Box<dyn WidgetBuilder<U>>> for TreeView<U, T>
+{
+	fn into(self) -> Box<dyn WidgetBuilder<U>>
+	{
+		Box::new(self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	use super::*;
+	use crate::prelude::*;
+	use crate::test::{TestUI, focused, press_key, press};
+
+	#[derive(Clone)]
+	struct Folder
+	{
+		name: &'static str,
+		children: Vec<Folder>,
+	}
+
+	static LISTED: AtomicUsize = AtomicUsize::new(0);
+
+	fn folder(name: &'static str, children: Vec<Folder>) -> Folder
+	{
+		Folder { name, children }
+	}
+
+	fn build_tree(mut commands: Commands, mut ui_tree: ResMut<UIHierarchy<TestUI>>, theme: Res<CurrentThemeData<TestUI>>)
+	{
+		let assets = folder("assets", vec![folder("fonts", vec![]), folder("levels", vec![folder("forest", vec![])])]);
+		TreeView::<TestUI, Folder>::new
+		(
+			[assets, folder("src", vec![])],
+			|folder| { LISTED.fetch_add(1, Ordering::SeqCst); folder.children.clone() },
+			|folder| folder.name.to_string(),
+		)
+			.with_has_children(|folder| !folder.children.is_empty())
+			.with_selection_mode(TreeSelectionMode::Multiple)
+			.build(&mut ui_tree, &theme.0, ParentData::default(), &mut commands);
+	}
+
+	fn node(app: &mut App, name: &str) -> Entity
+	{
+		app.world_mut().query::<(Entity, &TreeNode)>().iter(app.world())
+			.find(|(_, node)| node.value::<Folder>().is_some_and(|folder| folder.name == name))
+			.map(|(entity, _)| entity)
+			.unwrap()
+	}
+
+	#[test]
+	fn nodes_expand_lazily_and_select()
+	{
+		let mut app = crate::test::build_test_app::<TestUI, _>(build_tree);
+		app.update();
+		let tree = app.world_mut().query_filtered::<Entity, With<TreeViewState>>().single(app.world());
+		assert_eq!(app.world_mut().query::<&TreeNode>().iter(app.world()).count(), 2);
+		assert_eq!(LISTED.load(Ordering::SeqCst), 0);
+
+		// Right expands the focused node, building its children, then moves into them.
+		let assets = node(&mut app, "assets");
+		app.world_mut().send_event(NavRequest::FocusOn(assets));
+		app.update();
+		press_key(&mut app, KeyCode::ArrowRight);
+		assert!(app.world().get::<TreeNode>(assets).unwrap().expanded);
+		assert_eq!(LISTED.load(Ordering::SeqCst), 1);
+		let children_container = app.world().get::<TreeNode>(assets).unwrap().children_container;
+		assert_eq!(app.world().get::<Style>(children_container).unwrap().display, Display::Flex);
+		let toggles = app.world().resource::<Events<TreeNodeToggled>>();
+		let toggle = toggles.get_reader().read(toggles).last().copied();
+		assert_eq!(toggle, Some(TreeNodeToggled { tree, node: assets, expanded: true }));
+		press_key(&mut app, KeyCode::ArrowRight);
+		let fonts = node(&mut app, "fonts");
+		assert_eq!(focused(&mut app), Some(fonts));
+		assert_eq!(app.world().get::<TreeNode>(fonts).unwrap().depth, 1);
+
+		// Left goes back to the parent, then collapses it.
+		press_key(&mut app, KeyCode::ArrowLeft);
+		assert_eq!(focused(&mut app), Some(assets));
+		press_key(&mut app, KeyCode::ArrowLeft);
+		assert!(!app.world().get::<TreeNode>(assets).unwrap().expanded);
+		assert_eq!(app.world().get::<Style>(children_container).unwrap().display, Display::None);
+
+		// Ctrl adds to the selection.
+		let src = node(&mut app, "src");
+		press(&mut app, assets);
+		app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ControlLeft);
+		press(&mut app, src);
+		assert_eq!(app.world().get::<TreeSelection>(tree).unwrap().selected, vec![assets, src]);
+		let events = app.world().resource::<Events<TreeSelectionChanged>>();
+		let event = events.get_reader().read(events).last().cloned();
+		assert_eq!(event, Some(TreeSelectionChanged { tree, selected: vec![assets, src] }));
+		assert_eq!(app.world().get::<ThemePaint<TestUI>>(src).unwrap().paint_mode, PaintMode::Background);
+	}
+
+	#[test]
+	fn ui_hierarchies_can_be_shown()
+	{
+		#[derive(Default, Component)]
+		struct DebugUI;
+		let mut arena = indextree::Arena::new();
+		let root = arena.new_node(crate::UINode::of::<TestUI>());
+		root.append(arena.new_node(crate::UINode::of::<DebugUI>()), &mut arena);
+		let hierarchy = UIHierarchy::<TestUI>(Arc::new(std::sync::Mutex::new(arena)), std::marker::PhantomData);
+		let tree = TreeView::<TestUI, indextree::NodeId>::from_ui_hierarchy(&hierarchy);
+		assert_eq!(tree.roots, vec![root]);
+		assert_eq!((tree.children)(&root).len(), 1);
+		assert_eq!((tree.label)(&root), std::any::type_name::<TestUI>());
+	}
+}